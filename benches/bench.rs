@@ -1,215 +1,190 @@
-#[macro_use]
-extern crate bencher;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use eth_blockies::*;
 
-#[cfg(feature = "compressed_png")]
-benchmark_main!(
-    group_basic_data,
-    group_indexed_data,
-    group_png_data,
-    group_png_compressed_data,
-    group_png_base64_data,
-    group_ansiseq_ascii_data,
-    group_ansiseq_utf8_data
-);
-#[cfg(not(feature = "compressed_png"))]
-benchmark_main!(
-    group_basic_data,
-    group_indexed_data,
-    group_png_data,
-    group_png_base64_data,
-    group_ansiseq_ascii_data,
-    group_ansiseq_utf8_data
-);
+criterion_main!(group_basic_data, group_encoders);
 
-benchmark_group!(
+criterion_group!(
     group_basic_data,
     bench_data,
     bench_data_flatten,
-    bench_data_mapped
-);
-benchmark_group!(
-    group_indexed_data,
+    bench_data_mapped,
     bench_indexed_data,
-    bench_indexed_data_scale_008,
-    bench_indexed_data_scale_100,
-    bench_indexed_data_scale_128,
-    bench_indexed_data_scale_512
-);
-benchmark_group!(
-    group_png_data,
-    bench_processed_png_data_008,
-    bench_processed_png_data_100,
-    bench_processed_png_data_128,
-    bench_processed_png_data_512
+    bench_indexed_data_scale
 );
-benchmark_group!(
-    group_png_base64_data,
-    bench_processed_png_data_base64_008,
-    bench_processed_png_data_base64_100,
-    bench_processed_png_data_base64_128,
-    bench_processed_png_data_base64_512
-);
-#[cfg(feature = "compressed_png")]
-benchmark_group!(
-    group_png_compressed_data,
-    bench_processed_png_data_compressed_008,
-    bench_processed_png_data_compressed_100,
-    bench_processed_png_data_compressed_128,
-    bench_processed_png_data_compressed_512
-);
-benchmark_group!(
-    group_ansiseq_ascii_data,
-    bench_processed_ansiseq_data_ascii_08,
-    bench_processed_ansiseq_data_ascii_31,
-    bench_processed_ansiseq_data_ascii_32
-);
-benchmark_group!(
-    group_ansiseq_utf8_data,
-    bench_processed_ansiseq_data_utf8_08,
-    bench_processed_ansiseq_data_utf8_31,
-    bench_processed_ansiseq_data_utf8_32
+criterion_group!(
+    group_encoders,
+    bench_png_data,
+    bench_png_data_compressed,
+    bench_png_data_base64,
+    bench_ansiseq_data_ascii,
+    bench_ansiseq_data_utf8,
+    bench_bmp_data,
+    bench_ppm_data,
+    bench_tga_data,
+    bench_svg_data,
+    bench_qoi_data,
+    bench_webp_data
 );
 
 const SEED_ADDR: &str = "0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc";
 
-fn bench_data(bench: &mut bencher::Bencher) {
-    bench.iter(|| {
-        EthBlockies::data(SEED_ADDR);
-    });
-}
+// output dimensions of images / ansi sequences
+const IMAGE_DIMS: &[(usize, usize)] = &[(8, 8), (100, 100), (128, 128), (512, 512)];
+const ANSISEQ_DIMS: &[(usize, usize)] = &[(8, 8), (31, 31), (32, 32)];
 
-fn bench_data_flatten(bench: &mut bencher::Bencher) {
-    bench.iter(|| {
-        EthBlockies::data(SEED_ADDR).flatten();
+// benchmark an encoder for each dimension,
+// with throughput of output bytes (icons/sec is the inverse of time per iteration)
+fn bench_encoder<F, T>(c: &mut Criterion, name: &str, dims: &[(usize, usize)], encode: F)
+where
+    F: Fn(&'static str, (usize, usize)) -> T,
+    T: EncodedLen,
+{
+    let mut group = c.benchmark_group(name);
+    dims.iter().for_each(|&dim| {
+        let len = encode(SEED_ADDR, dim).encoded_len();
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", dim.0, dim.1)),
+            &dim,
+            |b, &dim| b.iter(|| encode(black_box(SEED_ADDR), dim)),
+        );
     });
+    group.finish();
 }
 
-fn bench_data_mapped(bench: &mut bencher::Bencher) {
-    fn rgb_to_grayscale((r, g, b): RgbPixel) -> u8 {
-        (r as f64 * 0.299 + g as f64 * 0.587 + b as f64 * 0.114) as u8
+// length of the encoder output in bytes
+trait EncodedLen {
+    fn encoded_len(&self) -> usize;
+}
+impl EncodedLen for Vec<u8> {
+    fn encoded_len(&self) -> usize {
+        self.len()
     }
-
-    bench.iter(|| {
-        EthBlockies::data_mapped(SEED_ADDR, rgb_to_grayscale);
-    });
 }
-
-fn bench_indexed_data(bench: &mut bencher::Bencher) {
-    bench.iter(|| {
-        EthBlockies::indexed_data(SEED_ADDR);
-    });
+impl EncodedLen for String {
+    fn encoded_len(&self) -> usize {
+        self.len()
+    }
 }
-
-// bench_indexed_data_scale
-fn bench_indexed_data_scale(bench: &mut bencher::Bencher, dimension: (usize, usize)) {
-    bench.iter(|| {
-        let (_palette, data) = EthBlockies::indexed_data(SEED_ADDR);
-        data.scale(dimension).concat();
-    });
+impl EncodedLen for Vec<String> {
+    fn encoded_len(&self) -> usize {
+        self.iter().map(|line| line.len() + 1).sum() // with newlines
+    }
 }
-fn bench_indexed_data_scale_008(bench: &mut bencher::Bencher) {
-    bench_indexed_data_scale(bench, (8, 8));
+impl<T: EncodedLen> EncodedLen for Option<T> {
+    fn encoded_len(&self) -> usize {
+        self.as_ref().map_or(0, T::encoded_len)
+    }
 }
-fn bench_indexed_data_scale_100(bench: &mut bencher::Bencher) {
-    bench_indexed_data_scale(bench, (100, 100));
+
+// benchmark generating a single icon, with throughput of icons
+fn bench_icon<T>(c: &mut Criterion, name: &str, f: impl Fn(&'static str) -> T) {
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("8x8", |b| b.iter(|| f(black_box(SEED_ADDR))));
+    group.finish();
 }
-fn bench_indexed_data_scale_128(bench: &mut bencher::Bencher) {
-    bench_indexed_data_scale(bench, (128, 128));
+
+fn bench_data(c: &mut Criterion) {
+    bench_icon(c, "data", EthBlockies::data);
 }
-fn bench_indexed_data_scale_512(bench: &mut bencher::Bencher) {
-    bench_indexed_data_scale(bench, (512, 512));
+
+fn bench_data_flatten(c: &mut Criterion) {
+    bench_icon(c, "data_flatten", |seed| EthBlockies::data(seed).flatten());
 }
 
-// bench_processed_png_data
-fn bench_processed_png_data(bench: &mut bencher::Bencher, dimension: (usize, usize)) {
-    bench.iter(|| {
-        EthBlockies::png_data(SEED_ADDR, dimension);
+fn bench_data_mapped(c: &mut Criterion) {
+    fn rgb_to_grayscale((r, g, b): RgbPixel) -> u8 {
+        (r as f64 * 0.299 + g as f64 * 0.587 + b as f64 * 0.114) as u8
+    }
+
+    bench_icon(c, "data_mapped", |seed| {
+        EthBlockies::data_mapped(seed, rgb_to_grayscale)
     });
 }
-fn bench_processed_png_data_008(bench: &mut bencher::Bencher) {
-    bench_processed_png_data(bench, (8, 8));
-}
-fn bench_processed_png_data_100(bench: &mut bencher::Bencher) {
-    bench_processed_png_data(bench, (100, 100));
-}
-fn bench_processed_png_data_128(bench: &mut bencher::Bencher) {
-    bench_processed_png_data(bench, (128, 128));
-}
-fn bench_processed_png_data_512(bench: &mut bencher::Bencher) {
-    bench_processed_png_data(bench, (512, 512));
+
+fn bench_indexed_data(c: &mut Criterion) {
+    bench_icon(c, "indexed_data", EthBlockies::indexed_data);
 }
 
-// bench_indexed_data_to_compressed_png
-#[cfg(feature = "compressed_png")]
-fn bench_processed_png_data_compressed(bench: &mut bencher::Bencher, dimension: (usize, usize)) {
-    bench.iter(|| {
-        EthBlockies::compressed_png_data(SEED_ADDR, dimension);
+fn bench_indexed_data_scale(c: &mut Criterion) {
+    let mut group = c.benchmark_group("indexed_data_scale");
+    group.throughput(Throughput::Elements(1));
+    IMAGE_DIMS.iter().for_each(|&dim| {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", dim.0, dim.1)),
+            &dim,
+            |b, &dim| {
+                b.iter(|| {
+                    let (_palette, data) = EthBlockies::indexed_data(black_box(SEED_ADDR));
+                    data.scale(dim).concat()
+                })
+            },
+        );
     });
+    group.finish();
 }
-#[cfg(feature = "compressed_png")]
-fn bench_processed_png_data_compressed_008(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_compressed(bench, (8, 8));
-}
-#[cfg(feature = "compressed_png")]
-fn bench_processed_png_data_compressed_100(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_compressed(bench, (100, 100));
-}
-#[cfg(feature = "compressed_png")]
-fn bench_processed_png_data_compressed_128(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_compressed(bench, (128, 128));
+
+fn bench_png_data(c: &mut Criterion) {
+    bench_encoder(c, "png_data", IMAGE_DIMS, EthBlockies::png_data);
 }
+
 #[cfg(feature = "compressed_png")]
-fn bench_processed_png_data_compressed_512(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_compressed(bench, (512, 512));
+fn bench_png_data_compressed(c: &mut Criterion) {
+    bench_encoder(
+        c,
+        "png_data_compressed",
+        IMAGE_DIMS,
+        EthBlockies::compressed_png_data,
+    );
 }
+#[cfg(not(feature = "compressed_png"))]
+fn bench_png_data_compressed(_: &mut Criterion) {}
 
-// bench_processed_png_data_base64
-fn bench_processed_png_data_base64(bench: &mut bencher::Bencher, dimension: (usize, usize)) {
-    bench.iter(|| {
-        EthBlockies::png_data_base64(SEED_ADDR, dimension, false);
+fn bench_png_data_base64(c: &mut Criterion) {
+    bench_encoder(c, "png_data_base64", IMAGE_DIMS, |seed, dim| {
+        EthBlockies::png_data_base64(seed, dim, false)
     });
 }
 
-fn bench_processed_png_data_base64_008(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_base64(bench, (8, 8));
-}
-fn bench_processed_png_data_base64_100(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_base64(bench, (100, 100));
-}
-fn bench_processed_png_data_base64_128(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_base64(bench, (128, 128));
-}
-fn bench_processed_png_data_base64_512(bench: &mut bencher::Bencher) {
-    bench_processed_png_data_base64(bench, (512, 512));
+fn bench_ansiseq_data_ascii(c: &mut Criterion) {
+    bench_encoder(c, "ansiseq_data_ascii", ANSISEQ_DIMS, |seed, dim| {
+        EthBlockies::ansiseq_data(seed, dim, false)
+    });
 }
 
-fn bench_processed_ansiseq_data_ascii(bench: &mut bencher::Bencher, dimension: (usize, usize)) {
-    bench.iter(|| {
-        EthBlockies::ansiseq_data(SEED_ADDR, dimension, false);
+fn bench_ansiseq_data_utf8(c: &mut Criterion) {
+    bench_encoder(c, "ansiseq_data_utf8", ANSISEQ_DIMS, |seed, dim| {
+        EthBlockies::ansiseq_data(seed, dim, true)
     });
 }
-fn bench_processed_ansiseq_data_ascii_08(bench: &mut bencher::Bencher) {
-    bench_processed_ansiseq_data_ascii(bench, (8, 8));
-}
-fn bench_processed_ansiseq_data_ascii_31(bench: &mut bencher::Bencher) {
-    bench_processed_ansiseq_data_ascii(bench, (31, 31));
+
+fn bench_bmp_data(c: &mut Criterion) {
+    bench_encoder(c, "bmp_data", IMAGE_DIMS, EthBlockies::bmp_data);
 }
-fn bench_processed_ansiseq_data_ascii_32(bench: &mut bencher::Bencher) {
-    bench_processed_ansiseq_data_ascii(bench, (32, 32));
+
+fn bench_ppm_data(c: &mut Criterion) {
+    bench_encoder(c, "ppm_data", IMAGE_DIMS, EthBlockies::ppm_data);
 }
 
-fn bench_processed_ansiseq_data_utf8(bench: &mut bencher::Bencher, dimension: (usize, usize)) {
-    bench.iter(|| {
-        EthBlockies::ansiseq_data(SEED_ADDR, dimension, true);
-    });
+fn bench_tga_data(c: &mut Criterion) {
+    bench_encoder(c, "tga_data", IMAGE_DIMS, EthBlockies::tga_data);
 }
-fn bench_processed_ansiseq_data_utf8_08(bench: &mut bencher::Bencher) {
-    bench_processed_ansiseq_data_utf8(bench, (8, 8));
+
+fn bench_svg_data(c: &mut Criterion) {
+    bench_encoder(c, "svg_data", IMAGE_DIMS, EthBlockies::svg_data);
 }
-fn bench_processed_ansiseq_data_utf8_31(bench: &mut bencher::Bencher) {
-    bench_processed_ansiseq_data_utf8(bench, (31, 31));
+
+#[cfg(feature = "qoi")]
+fn bench_qoi_data(c: &mut Criterion) {
+    bench_encoder(c, "qoi_data", IMAGE_DIMS, EthBlockies::qoi_data);
 }
-fn bench_processed_ansiseq_data_utf8_32(bench: &mut bencher::Bencher) {
-    bench_processed_ansiseq_data_utf8(bench, (32, 32));
+#[cfg(not(feature = "qoi"))]
+fn bench_qoi_data(_: &mut Criterion) {}
+
+#[cfg(feature = "webp_lossless")]
+fn bench_webp_data(c: &mut Criterion) {
+    bench_encoder(c, "webp_data", IMAGE_DIMS, EthBlockies::webp_data);
 }
+#[cfg(not(feature = "webp_lossless"))]
+fn bench_webp_data(_: &mut Criterion) {}
@@ -0,0 +1,106 @@
+/* C ABI of eth-blockies: https://github.com/snoopy3476/eth-blockies-rs */
+
+#ifndef ETH_BLOCKIES_H
+#define ETH_BLOCKIES_H
+
+/* This file is generated by cbindgen from 'ffi/src/lib.rs'. Do not edit manually. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Canonicalize the seed as an Ethereum address (to lowercase + set '0x' prefix)
+//
+// Valid seeds with this flag are Ethereum address strings (with or without '0x' prefix),
+// or raw 20-byte addresses.
+#define ETH_BLOCKIES_ETHSEED (1 << 0)
+
+// Generate compressed png data (only for `eth_blockies_png()`)
+#define ETH_BLOCKIES_COMPRESSED (1 << 1)
+
+// Minimum blockies size (width/height of blockies, in blocks)
+#define ETH_BLOCKIES_MIN_SIZE 1
+
+// Maximum blockies size (width/height of blockies, in blocks)
+#define ETH_BLOCKIES_MAX_SIZE 32
+
+// Data allocated by this library
+//
+// Must be freed with `eth_blockies_free()`.
+// Empty buffer (`data == NULL`, `len == 0`) represents an error.
+typedef struct EthBlockiesBuffer {
+  // Pointer to the data
+  uint8_t *data;
+  // Length of the data in bytes
+  size_t len;
+} EthBlockiesBuffer;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Generate png image data of blockies
+//
+// # Arguments
+//
+// * `seed` - Input seed (can be `NULL` if `seed_len` is 0)
+// * `seed_len` - Length of `seed` in bytes
+// * `blockies_size` - Blockies size (`ETH_BLOCKIES_MIN_SIZE` - `ETH_BLOCKIES_MAX_SIZE`, `8` for Ethereum blockies)
+// * `width`, `height` - Dimension of output png image (non-zero)
+// * `flags` - Bitwise OR of `ETH_BLOCKIES_ETHSEED`, `ETH_BLOCKIES_COMPRESSED` (or `0`)
+//
+// # Return
+//
+// * Png image data, or empty buffer on invalid arguments
+//
+// # Safety
+//
+// * `seed` must be valid for reads of `seed_len` bytes, if not `NULL`
+struct EthBlockiesBuffer eth_blockies_png(const uint8_t *seed,
+                                          size_t seed_len,
+                                          size_t blockies_size,
+                                          size_t width,
+                                          size_t height,
+                                          uint32_t flags);
+
+// Generate indexed data of blockies, with its rgb palette
+//
+// # Arguments
+//
+// * `seed` - Input seed (can be `NULL` if `seed_len` is 0)
+// * `seed_len` - Length of `seed` in bytes
+// * `blockies_size` - Blockies size (`ETH_BLOCKIES_MIN_SIZE` - `ETH_BLOCKIES_MAX_SIZE`, `8` for Ethereum blockies)
+// * `flags` - `ETH_BLOCKIES_ETHSEED` (or `0`)
+// * `palette` - Output of rgb palette, as 9 bytes of
+//   `{ bg_r, bg_g, bg_b, color_r, color_g, color_b, spot_r, spot_g, spot_b }`
+//   (can be `NULL` if not needed)
+//
+// # Return
+//
+// * `blockies_size * blockies_size` bytes of color class indices (row-major),
+//   each of which is `0` (bg color), `1` (color) or `2` (spot color),
+//   or empty buffer on invalid arguments
+//
+// # Safety
+//
+// * `seed` must be valid for reads of `seed_len` bytes, if not `NULL`
+// * `palette` must be valid for writes of 9 bytes, if not `NULL`
+struct EthBlockiesBuffer eth_blockies_indexed(const uint8_t *seed,
+                                              size_t seed_len,
+                                              size_t blockies_size,
+                                              uint32_t flags,
+                                              uint8_t *palette);
+
+// Free data allocated by this library
+//
+// Freeing an empty buffer does nothing.
+//
+// # Safety
+//
+// * `buffer` must be returned from this library, and not be freed before
+void eth_blockies_free(struct EthBlockiesBuffer buffer);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ETH_BLOCKIES_H */
@@ -0,0 +1,290 @@
+//! C ABI of [`eth_blockies`], to get Ethereum-style blocky identicon from C, C++, Swift, Kotlin, etc.
+//!
+//! The C header of this ABI is `include/eth_blockies.h`,
+//! generated by [cbindgen](https://github.com/mozilla/cbindgen) with `cbindgen.toml`.
+//!
+//! # Memory
+//!
+//! Every [`EthBlockiesBuffer`] returned is allocated by this library,
+//! and must be freed with [`eth_blockies_free()`] (not with `free()` of C).
+//!
+//! # Errors
+//!
+//! On invalid arguments, functions return an empty buffer (`data == NULL`, `len == 0`),
+//! which is also safe to pass to [`eth_blockies_free()`].
+//!
+//! # Example (C)
+//!
+//! ```c
+//! #include "eth_blockies.h"
+//!
+//! const char *addr = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+//!
+//! EthBlockiesBuffer png = eth_blockies_png(
+//!     (const uint8_t *)addr, strlen(addr), 8, 128, 128,
+//!     ETH_BLOCKIES_ETHSEED | ETH_BLOCKIES_COMPRESSED);
+//! if (png.data != NULL) {
+//!     fwrite(png.data, 1, png.len, fp);
+//! }
+//! eth_blockies_free(png);
+//! ```
+
+use eth_blockies::*;
+use std::panic::catch_unwind;
+
+/// Canonicalize the seed as an Ethereum address (to lowercase + set '0x' prefix)
+///
+/// Valid seeds with this flag are Ethereum address strings (with or without '0x' prefix),
+/// or raw 20-byte addresses.
+pub const ETH_BLOCKIES_ETHSEED: u32 = 1 << 0;
+/// Generate compressed png data (only for `eth_blockies_png()`)
+pub const ETH_BLOCKIES_COMPRESSED: u32 = 1 << 1;
+
+/// Minimum blockies size (width/height of blockies, in blocks)
+pub const ETH_BLOCKIES_MIN_SIZE: usize = 1;
+/// Maximum blockies size (width/height of blockies, in blocks)
+pub const ETH_BLOCKIES_MAX_SIZE: usize = 32;
+
+/// Data allocated by this library
+///
+/// Must be freed with `eth_blockies_free()`.
+/// Empty buffer (`data == NULL`, `len == 0`) represents an error.
+#[repr(C)]
+pub struct EthBlockiesBuffer {
+    /// Pointer to the data
+    pub data: *mut u8,
+    /// Length of the data in bytes
+    pub len: usize,
+}
+
+impl EthBlockiesBuffer {
+    fn empty() -> Self {
+        Self {
+            data: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+impl From<Option<Vec<u8>>> for EthBlockiesBuffer {
+    fn from(data: Option<Vec<u8>>) -> Self {
+        match data {
+            Some(data) if !data.is_empty() => {
+                let len = data.len();
+                Self {
+                    data: Box::into_raw(data.into_boxed_slice()) as *mut u8,
+                    len,
+                }
+            }
+            _ => Self::empty(),
+        }
+    }
+}
+
+// list of generator functions, one for each blockies size
+type FuncList<'a, F> = [&'a F; ETH_BLOCKIES_MAX_SIZE - ETH_BLOCKIES_MIN_SIZE + 1];
+type PngFn<'a> = dyn Fn(&[u8], (usize, usize), PngOptions) -> Vec<u8> + 'a;
+type IndexedFn<'a> = dyn Fn(&[u8]) -> (RgbPalette, Vec<u8>) + 'a;
+
+macro_rules! init_const_blockies_arr {
+
+    // wrapper
+    ( $func:ident ) => {
+        init_const_blockies_arr!(@gen_arr
+            $func, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+            22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+        )
+    };
+
+    // gen array of calls of generic fn, with given blockies_sizes (args)
+    (@gen_arr $func:ident, $( $size:literal ), *) => {
+        [
+            $(
+                &$func::<$size>,
+            )*
+        ]
+    };
+
+}
+
+fn png_data<const S: usize>(
+    seed: &[u8],
+    dimension: (usize, usize),
+    options: PngOptions,
+) -> Vec<u8> {
+    Blockies::<S>::png_data_with_options(seed, dimension, options)
+}
+
+fn compressed_png_data<const S: usize>(
+    seed: &[u8],
+    dimension: (usize, usize),
+    options: PngOptions,
+) -> Vec<u8> {
+    Blockies::<S>::compressed_png_data_with_options(seed, dimension, options)
+}
+
+fn indexed_data<const S: usize>(seed: &[u8]) -> (RgbPalette, Vec<u8>) {
+    let (palette, data) = Blockies::<S>::indexed_data(seed);
+    (palette, data.flatten().into_iter().map(u8::from).collect())
+}
+
+// get seed slice from raw parts, canonicalized if ETH_BLOCKIES_ETHSEED is set
+//
+// safety: `seed` must be valid for reads of `seed_len` bytes, if not null
+unsafe fn seed_from_raw<'a>(seed: *const u8, seed_len: usize, flags: u32) -> Option<Seed<'a>> {
+    let seed = match (seed.is_null(), seed_len) {
+        (true, 0) => &[],
+        (true, _) => return None,
+        (false, _) => std::slice::from_raw_parts(seed, seed_len),
+    };
+
+    Some(match flags & ETH_BLOCKIES_ETHSEED != 0 {
+        true => Seed::EthAddr(seed.to_ethaddr_seed()),
+        false => Seed::Raw(seed),
+    })
+}
+
+enum Seed<'a> {
+    Raw(&'a [u8]),
+    EthAddr([u8; 42]),
+}
+
+impl Seed<'_> {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Seed::Raw(seed) => seed,
+            Seed::EthAddr(seed) => seed,
+        }
+    }
+}
+
+fn is_valid_size(blockies_size: usize) -> bool {
+    (ETH_BLOCKIES_MIN_SIZE..=ETH_BLOCKIES_MAX_SIZE).contains(&blockies_size)
+}
+
+/// Generate png image data of blockies
+///
+/// # Arguments
+///
+/// * `seed` - Input seed (can be `NULL` if `seed_len` is 0)
+/// * `seed_len` - Length of `seed` in bytes
+/// * `blockies_size` - Blockies size (`ETH_BLOCKIES_MIN_SIZE` - `ETH_BLOCKIES_MAX_SIZE`, `8` for Ethereum blockies)
+/// * `width`, `height` - Dimension of output png image (non-zero)
+/// * `flags` - Bitwise OR of `ETH_BLOCKIES_ETHSEED`, `ETH_BLOCKIES_COMPRESSED` (or `0`)
+///
+/// # Return
+///
+/// * Png image data, or empty buffer on invalid arguments
+///
+/// # Safety
+///
+/// * `seed` must be valid for reads of `seed_len` bytes, if not `NULL`
+#[no_mangle]
+pub unsafe extern "C" fn eth_blockies_png(
+    seed: *const u8,
+    seed_len: usize,
+    blockies_size: usize,
+    width: usize,
+    height: usize,
+    flags: u32,
+) -> EthBlockiesBuffer {
+    if flags & !(ETH_BLOCKIES_ETHSEED | ETH_BLOCKIES_COMPRESSED) != 0
+        || !is_valid_size(blockies_size)
+        || width == 0
+        || height == 0
+    {
+        return EthBlockiesBuffer::empty();
+    }
+    let seed = match seed_from_raw(seed, seed_len, flags) {
+        Some(seed) => seed,
+        None => return EthBlockiesBuffer::empty(),
+    };
+
+    // never unwind across ffi boundary
+    catch_unwind(|| {
+        let func_list: FuncList<PngFn<'_>> = match flags & ETH_BLOCKIES_COMPRESSED != 0 {
+            true => init_const_blockies_arr!(compressed_png_data),
+            false => init_const_blockies_arr!(png_data),
+        };
+
+        func_list[blockies_size - 1](seed.as_bytes(), (width, height), PngOptions::default())
+    })
+    .ok()
+    .into()
+}
+
+/// Generate indexed data of blockies, with its rgb palette
+///
+/// # Arguments
+///
+/// * `seed` - Input seed (can be `NULL` if `seed_len` is 0)
+/// * `seed_len` - Length of `seed` in bytes
+/// * `blockies_size` - Blockies size (`ETH_BLOCKIES_MIN_SIZE` - `ETH_BLOCKIES_MAX_SIZE`, `8` for Ethereum blockies)
+/// * `flags` - `ETH_BLOCKIES_ETHSEED` (or `0`)
+/// * `palette` - Output of rgb palette, as 9 bytes of
+///   `{ bg_r, bg_g, bg_b, color_r, color_g, color_b, spot_r, spot_g, spot_b }`
+///   (can be `NULL` if not needed)
+///
+/// # Return
+///
+/// * `blockies_size * blockies_size` bytes of color class indices (row-major),
+///   each of which is `0` (bg color), `1` (color) or `2` (spot color),
+///   or empty buffer on invalid arguments
+///
+/// # Safety
+///
+/// * `seed` must be valid for reads of `seed_len` bytes, if not `NULL`
+/// * `palette` must be valid for writes of 9 bytes, if not `NULL`
+#[no_mangle]
+pub unsafe extern "C" fn eth_blockies_indexed(
+    seed: *const u8,
+    seed_len: usize,
+    blockies_size: usize,
+    flags: u32,
+    palette: *mut u8,
+) -> EthBlockiesBuffer {
+    if flags & !ETH_BLOCKIES_ETHSEED != 0 || !is_valid_size(blockies_size) {
+        return EthBlockiesBuffer::empty();
+    }
+    let seed = match seed_from_raw(seed, seed_len, flags) {
+        Some(seed) => seed,
+        None => return EthBlockiesBuffer::empty(),
+    };
+
+    // never unwind across ffi boundary
+    let (rgb_palette, data) = match catch_unwind(|| {
+        let func_list: FuncList<IndexedFn<'_>> = init_const_blockies_arr!(indexed_data);
+
+        func_list[blockies_size - 1](seed.as_bytes())
+    }) {
+        Ok(ret) => ret,
+        Err(_) => return EthBlockiesBuffer::empty(),
+    };
+
+    if !palette.is_null() {
+        let palette = std::slice::from_raw_parts_mut(palette, 9);
+        palette
+            .chunks_exact_mut(3)
+            .zip(rgb_palette)
+            .for_each(|(chunk, (r, g, b))| chunk.copy_from_slice(&[r, g, b]));
+    }
+
+    Some(data).into()
+}
+
+/// Free data allocated by this library
+///
+/// Freeing an empty buffer does nothing.
+///
+/// # Safety
+///
+/// * `buffer` must be returned from this library, and not be freed before
+#[no_mangle]
+pub unsafe extern "C" fn eth_blockies_free(buffer: EthBlockiesBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
@@ -0,0 +1,167 @@
+// Call the C ABI from Rust, and compare the results with the library.
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod abi {
+    use eth_blockies::*;
+    use eth_blockies_ffi::*;
+    use std::ptr::{null, null_mut};
+
+    const ADDR: &str = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+
+    // copy data from the buffer, and free the buffer
+    fn take(buffer: EthBlockiesBuffer) -> Option<Vec<u8>> {
+        let data = match buffer.data.is_null() {
+            true => {
+                assert_eq!(buffer.len, 0);
+                None
+            }
+            false => Some(unsafe { std::slice::from_raw_parts(buffer.data, buffer.len) }.to_vec()),
+        };
+        unsafe { eth_blockies_free(buffer) };
+        data
+    }
+
+    fn png(seed: &[u8], blockies_size: usize, dim: (usize, usize), flags: u32) -> Option<Vec<u8>> {
+        take(unsafe {
+            eth_blockies_png(
+                seed.as_ptr(),
+                seed.len(),
+                blockies_size,
+                dim.0,
+                dim.1,
+                flags,
+            )
+        })
+    }
+
+    fn indexed(seed: &[u8], blockies_size: usize, flags: u32) -> Option<(Vec<u8>, [u8; 9])> {
+        let mut palette = [0_u8; 9];
+        take(unsafe {
+            eth_blockies_indexed(
+                seed.as_ptr(),
+                seed.len(),
+                blockies_size,
+                flags,
+                palette.as_mut_ptr(),
+            )
+        })
+        .map(|data| (data, palette))
+    }
+
+    #[test]
+    fn png_same_as_library() {
+        assert_eq!(
+            png(b"generic_seed", 8, (128, 128), 0).unwrap(),
+            EthBlockies::png_data("generic_seed", (128, 128))
+        );
+        assert_eq!(
+            png(b"generic_seed", 16, (100, 64), ETH_BLOCKIES_COMPRESSED).unwrap(),
+            Blockies::<16>::compressed_png_data("generic_seed", (100, 64))
+        );
+        assert_eq!(
+            png(
+                ADDR.as_bytes(),
+                8,
+                (64, 64),
+                ETH_BLOCKIES_ETHSEED | ETH_BLOCKIES_COMPRESSED
+            )
+            .unwrap(),
+            EthBlockies::compressed_png_data(ADDR.to_ethaddr_seed(), (64, 64))
+        );
+
+        // every blockies size
+        (ETH_BLOCKIES_MIN_SIZE..=ETH_BLOCKIES_MAX_SIZE).for_each(|blockies_size| {
+            let data = png(b"generic_seed", blockies_size, (blockies_size, 1), 0).unwrap();
+            assert_eq!(data[..8], *b"\x89PNG\r\n\x1a\n");
+        });
+    }
+
+    #[test]
+    fn indexed_same_as_library() {
+        let (data, palette) = indexed(ADDR.as_bytes(), 8, ETH_BLOCKIES_ETHSEED).unwrap();
+        let (expected_palette, expected_data) = EthBlockies::indexed_data(ADDR.to_ethaddr_seed());
+
+        assert_eq!(
+            data,
+            expected_data
+                .flatten()
+                .into_iter()
+                .map(u8::from)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(palette, {
+            let [(r0, g0, b0), (r1, g1, b1), (r2, g2, b2)] = expected_palette;
+            [r0, g0, b0, r1, g1, b1, r2, g2, b2]
+        });
+
+        // palette is optional
+        let data_only = take(unsafe {
+            eth_blockies_indexed(
+                ADDR.as_ptr(),
+                ADDR.len(),
+                8,
+                ETH_BLOCKIES_ETHSEED,
+                null_mut(),
+            )
+        });
+        assert_eq!(data_only.unwrap(), data);
+
+        // blockies size
+        let (data, _) = indexed(b"generic_seed", 32, 0).unwrap();
+        assert_eq!(data.len(), 32 * 32);
+        assert!(data.iter().all(|class| *class < 3));
+    }
+
+    #[test]
+    fn empty_seed() {
+        assert_eq!(
+            take(unsafe { eth_blockies_png(null(), 0, 8, 16, 16, 0) }).unwrap(),
+            EthBlockies::png_data("", (16, 16))
+        );
+        assert_eq!(
+            take(unsafe { eth_blockies_indexed(null(), 0, 8, 0, null_mut()) }).unwrap(),
+            EthBlockies::indexed_data("")
+                .1
+                .flatten()
+                .into_iter()
+                .map(u8::from)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        // null seed with non-zero length
+        assert_eq!(
+            take(unsafe { eth_blockies_png(null(), 1, 8, 16, 16, 0) }),
+            None
+        );
+        assert_eq!(
+            take(unsafe { eth_blockies_indexed(null(), 1, 8, 0, null_mut()) }),
+            None
+        );
+
+        // blockies size
+        assert_eq!(png(b"seed", 0, (16, 16), 0), None);
+        assert_eq!(png(b"seed", 33, (16, 16), 0), None);
+        assert_eq!(indexed(b"seed", 0, 0), None);
+        assert_eq!(indexed(b"seed", 33, 0), None);
+
+        // dimension
+        assert_eq!(png(b"seed", 8, (0, 16), 0), None);
+        assert_eq!(png(b"seed", 8, (16, 0), 0), None);
+
+        // unknown flags
+        assert_eq!(png(b"seed", 8, (16, 16), 1 << 31), None);
+        assert_eq!(indexed(b"seed", 8, ETH_BLOCKIES_COMPRESSED), None);
+
+        // freeing an empty buffer does nothing
+        unsafe {
+            eth_blockies_free(EthBlockiesBuffer {
+                data: null_mut(),
+                len: 0,
+            })
+        };
+    }
+}
@@ -0,0 +1,32 @@
+// Check if the checked-in C header is the same as the one generated from the source.
+// Regenerate the header with env var ETH_BLOCKIES_FFI_UPDATE_HEADER=1
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod header {
+    use std::path::Path;
+
+    #[test]
+    fn header_up_to_date() {
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let header_path = crate_dir.join("include").join("eth_blockies.h");
+
+        let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+        let mut generated = Vec::new();
+        cbindgen::generate_with_config(crate_dir, config)
+            .expect("failed to generate header")
+            .write(&mut generated);
+
+        if std::env::var_os("ETH_BLOCKIES_FFI_UPDATE_HEADER").is_some() {
+            std::fs::write(&header_path, &generated).unwrap();
+        }
+
+        let checked_in = std::fs::read(&header_path).unwrap_or_default();
+        assert!(
+            checked_in == generated,
+            "'{}' is outdated: \
+             regenerate with 'ETH_BLOCKIES_FFI_UPDATE_HEADER=1 cargo test -p eth-blockies-ffi'",
+            header_path.display()
+        );
+    }
+}
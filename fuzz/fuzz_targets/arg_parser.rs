@@ -0,0 +1,45 @@
+// CLI arg parser for arbitrary arguments (separated by NUL byte):
+//   - never panics, on parsing arguments and on getting options
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[allow(dead_code)]
+#[path = "../../src/bin/arg_parser/mod.rs"]
+mod arg_parser;
+#[allow(dead_code)]
+#[path = "../../src/bin/bin_error/mod.rs"]
+mod bin_error;
+use arg_parser::*;
+
+// options read by the binary
+const OPTS: &[(&str, bool)] = &[
+    ("help", false),
+    ("ascii", false),
+    ("raw", false),
+    ("ethseed", false),
+    ("interlace", false),
+    ("minimize", false),
+    ("labels", false),
+    ("size", true),
+    ("dimension", true),
+    ("format", true),
+    ("outfile", true),
+    ("background", true),
+    ("columns", true),
+    ("gap", true),
+];
+
+fuzz_target!(|data: &[u8]| {
+    let args = data
+        .split(|byte| *byte == 0)
+        .map(|arg| String::from_utf8_lossy(arg).into_owned());
+
+    if let Ok((_, mut opt_list)) = parse_arg_list(args) {
+        OPTS.iter().for_each(|(opt_name, need_opt_value)| {
+            let _ = opt_list.get_opt(opt_name, *need_opt_value);
+        });
+        let _ = opt_list.check_if_empty();
+    }
+});
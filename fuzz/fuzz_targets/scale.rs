@@ -0,0 +1,53 @@
+// BlockiesHelper::scale() for arbitrary dimensions:
+//   - result has exactly the given width and height
+//   - each cell is scaled to floor or ceil of (dimension / size) pixels, in order
+
+#![no_main]
+
+use eth_blockies::{Blockies, BlockiesHelper};
+use libfuzzer_sys::fuzz_target;
+
+// limit each dimension to avoid huge allocation
+const MAX_DIM: usize = 2048;
+
+fuzz_target!(|data: [u8; 4]| {
+    let output_dim = (
+        u16::from_le_bytes([data[0], data[1]]) as usize % (MAX_DIM + 1),
+        u16::from_le_bytes([data[2], data[3]]) as usize % (MAX_DIM + 1),
+    );
+
+    check_scale::<1>(output_dim);
+    check_scale::<7>(output_dim);
+    check_scale::<8>(output_dim);
+    check_scale::<13>(output_dim);
+    check_scale::<32>(output_dim);
+});
+
+fn check_scale<const S: usize>(output_dim: (usize, usize)) {
+    let cells: Blockies<S, (usize, usize)> = Blockies::new(|pos| pos);
+    let scaled = cells.scale(output_dim);
+
+    assert_eq!(scaled.len(), output_dim.1);
+    assert!(scaled.iter().all(|row| row.len() == output_dim.0));
+
+    let is_cell_width_valid =
+        |dim: usize, count: usize| count == dim / S || count == dim.div_ceil(S);
+
+    // columns, in the first row
+    if let Some(row) = scaled.first() {
+        assert!(row.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert!((0..S).all(|x| is_cell_width_valid(
+            output_dim.0,
+            row.iter().filter(|cell| cell.0 == x).count()
+        )));
+    }
+    // rows, in the first column
+    if output_dim.0 > 0 {
+        let column: Vec<usize> = scaled.iter().map(|row| row[0].1).collect();
+        assert!(column.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!((0..S).all(|y| is_cell_width_valid(
+            output_dim.1,
+            column.iter().filter(|cell| **cell == y).count()
+        )));
+    }
+}
@@ -0,0 +1,22 @@
+// SeedInput::to_ethaddr_seed() for arbitrary bytes:
+//   - never panics
+//   - result is idempotent (converting the result again gives the same result)
+//   - 20-byte input always results in a well-formed lowercase address
+
+#![no_main]
+
+use eth_blockies::SeedInput;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let ethaddr = data.to_ethaddr_seed();
+
+    assert_eq!(ethaddr.to_ethaddr_seed(), ethaddr);
+
+    if data.len() == 20 {
+        assert_eq!(&ethaddr[..2], b"0x");
+        assert!(ethaddr[2..]
+            .iter()
+            .all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f')));
+    }
+});
@@ -0,0 +1,34 @@
+from typing import List, Tuple, Union
+
+Seed = Union[str, bytes]
+Rgb = Tuple[int, int, int]
+
+__version__: str
+
+def data(seed: Seed, size: int = 8, ethseed: bool = False) -> List[List[Rgb]]:
+    """Get rgb pixels of blockies, as a list of rows of `(r, g, b)`"""
+
+def indexed_data(
+    seed: Seed, size: int = 8, ethseed: bool = False
+) -> Tuple[List[Rgb], List[List[int]]]:
+    """Get indexed data of blockies, as `(palette, rows)`"""
+
+def png(
+    seed: Seed,
+    dimension: Tuple[int, int] = (128, 128),
+    size: int = 8,
+    ethseed: bool = False,
+    compressed: bool = False,
+) -> bytes:
+    """Get png image data of blockies, as `bytes`"""
+
+def svg(
+    seed: Seed,
+    dimension: Tuple[int, int] = (128, 128),
+    size: int = 8,
+    ethseed: bool = False,
+) -> str:
+    """Get svg image data of blockies, as `str`"""
+
+def to_ethaddr_seed(seed: Seed) -> str:
+    """Canonicalize Ethereum address (to lowercase + set '0x' prefix)"""
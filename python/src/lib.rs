@@ -0,0 +1,232 @@
+//! Python bindings of [`eth_blockies`], to get Ethereum-style blocky identicon from Python
+//!
+//! Build and install the Python module `eth_blockies` with [maturin](https://github.com/PyO3/maturin)
+//! (`pip install ./python`, or `maturin develop -m python/Cargo.toml`).
+//!
+//! # Example (Python)
+//!
+//! ```python
+//! import eth_blockies
+//!
+//! addr = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+//!
+//! # rgb pixels, as list of rows of (r, g, b)
+//! rows = eth_blockies.data(addr, ethseed=True)
+//!
+//! # palette of (r, g, b), and rows of color class indices (0: bg, 1: color, 2: spot)
+//! palette, indices = eth_blockies.indexed_data(addr, ethseed=True)
+//!
+//! # images
+//! png = eth_blockies.png(addr, (128, 128), ethseed=True, compressed=True)  # bytes
+//! svg = eth_blockies.svg(addr, (128, 128), ethseed=True)  # str
+//!
+//! # canonicalized Ethereum address seed
+//! assert eth_blockies.to_ethaddr_seed(addr) == addr.lower()
+//! ```
+
+use eth_blockies::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+const MIN_BLOCKIES_SIZE: usize = 1;
+const MAX_BLOCKIES_SIZE: usize = 32;
+
+// list of generator functions, one for each blockies size
+type FuncList<'a, F> = [&'a F; MAX_BLOCKIES_SIZE - MIN_BLOCKIES_SIZE + 1];
+type DataFn<'a> = dyn Fn(&[u8]) -> Vec<Vec<RgbPixel>> + 'a;
+type IndexedFn<'a> = dyn Fn(&[u8]) -> (RgbPalette, Vec<Vec<u16>>) + 'a;
+type PngFn<'a> = dyn Fn(&[u8], (usize, usize), PngOptions) -> Vec<u8> + 'a;
+type SvgFn<'a> = dyn Fn(&[u8], (usize, usize)) -> String + 'a;
+
+macro_rules! init_const_blockies_arr {
+
+    // wrapper
+    ( $func:ident ) => {
+        init_const_blockies_arr!(@gen_arr
+            $func, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+            22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+        )
+    };
+
+    // gen array of calls of generic fn, with given blockies_sizes (args)
+    (@gen_arr $func:ident, $( $size:literal ), *) => {
+        [
+            $(
+                &$func::<$size>,
+            )*
+        ]
+    };
+
+}
+
+fn gen_data<const S: usize>(seed: &[u8]) -> Vec<Vec<RgbPixel>> {
+    Blockies::<S>::data(seed)
+        .iter()
+        .map(|row| row.to_vec())
+        .collect()
+}
+
+// color classes as u16, as Vec<u8> is converted to bytes (not list of int)
+fn gen_indexed_data<const S: usize>(seed: &[u8]) -> (RgbPalette, Vec<Vec<u16>>) {
+    let (palette, data) = Blockies::<S>::indexed_data(seed);
+    (
+        palette,
+        data.iter()
+            .map(|row| row.iter().map(|class| u16::from(*class)).collect())
+            .collect(),
+    )
+}
+
+fn gen_png<const S: usize>(seed: &[u8], dimension: (usize, usize), options: PngOptions) -> Vec<u8> {
+    Blockies::<S>::png_data_with_options(seed, dimension, options)
+}
+
+fn gen_comp_png<const S: usize>(
+    seed: &[u8],
+    dimension: (usize, usize),
+    options: PngOptions,
+) -> Vec<u8> {
+    Blockies::<S>::compressed_png_data_with_options(seed, dimension, options)
+}
+
+fn gen_svg<const S: usize>(seed: &[u8], dimension: (usize, usize)) -> String {
+    Blockies::<S>::svg_data(seed, dimension)
+}
+
+/// Seed of blockies, given as `str` or `bytes` in Python
+#[derive(FromPyObject)]
+enum SeedArg {
+    Str(String),
+    Bytes(Vec<u8>),
+}
+
+impl SeedArg {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            SeedArg::Str(seed) => seed.as_bytes(),
+            SeedArg::Bytes(seed) => seed,
+        }
+    }
+
+    // get seed bytes, canonicalized if `ethseed` is set
+    fn to_seed(&self, ethseed: bool) -> PyResult<Vec<u8>> {
+        match ethseed {
+            true => ethaddr_seed(self.as_bytes()).map(Vec::from),
+            false => Ok(self.as_bytes().to_vec()),
+        }
+    }
+}
+
+fn ethaddr_seed(seed: &[u8]) -> PyResult<[u8; 42]> {
+    match seed.len() {
+        20 | 40 | 42 => Ok(seed.to_ethaddr_seed()),
+        len => Err(PyValueError::new_err(format!(
+            "Invalid Ethereum address: length must be 20 (bytes), 40 or 42 (hex str), got {}",
+            len
+        ))),
+    }
+}
+
+fn check_size(size: usize) -> PyResult<usize> {
+    match (MIN_BLOCKIES_SIZE..=MAX_BLOCKIES_SIZE).contains(&size) {
+        true => Ok(size),
+        false => Err(PyValueError::new_err(format!(
+            "Invalid blockies size: must be {}-{}, got {}",
+            MIN_BLOCKIES_SIZE, MAX_BLOCKIES_SIZE, size
+        ))),
+    }
+}
+
+fn check_dimension(dimension: (usize, usize)) -> PyResult<(usize, usize)> {
+    match dimension {
+        (0, _) | (_, 0) => Err(PyValueError::new_err(format!(
+            "Invalid dimension: width and height must be positive, got {:?}",
+            dimension
+        ))),
+        _ => Ok(dimension),
+    }
+}
+
+/// Get rgb pixels of blockies, as a list of rows of `(r, g, b)`
+#[pyfunction]
+#[pyo3(signature = (seed, size = 8, ethseed = false))]
+fn data(seed: SeedArg, size: usize, ethseed: bool) -> PyResult<Vec<Vec<RgbPixel>>> {
+    let func_list: FuncList<DataFn<'_>> = init_const_blockies_arr!(gen_data);
+
+    Ok(func_list[check_size(size)? - 1](&seed.to_seed(ethseed)?))
+}
+
+/// Get indexed data of blockies, as `(palette, rows)`
+///
+/// `palette` is a list of `(r, g, b)` of 3 colors (bg color, color, spot color),
+/// and `rows` is a list of rows of color class indices (`0`, `1`, `2`) to the palette.
+#[pyfunction]
+#[pyo3(signature = (seed, size = 8, ethseed = false))]
+fn indexed_data(
+    seed: SeedArg,
+    size: usize,
+    ethseed: bool,
+) -> PyResult<(RgbPalette, Vec<Vec<u16>>)> {
+    let func_list: FuncList<IndexedFn<'_>> = init_const_blockies_arr!(gen_indexed_data);
+
+    Ok(func_list[check_size(size)? - 1](&seed.to_seed(ethseed)?))
+}
+
+/// Get png image data of blockies, as `bytes`
+#[pyfunction]
+#[pyo3(signature = (seed, dimension = (128, 128), size = 8, ethseed = false, compressed = false))]
+fn png<'py>(
+    py: Python<'py>,
+    seed: SeedArg,
+    dimension: (usize, usize),
+    size: usize,
+    ethseed: bool,
+    compressed: bool,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let func_list: FuncList<PngFn<'_>> = match compressed {
+        true => init_const_blockies_arr!(gen_comp_png),
+        false => init_const_blockies_arr!(gen_png),
+    };
+    let data = func_list[check_size(size)? - 1](
+        &seed.to_seed(ethseed)?,
+        check_dimension(dimension)?,
+        PngOptions::default(),
+    );
+
+    Ok(PyBytes::new(py, &data))
+}
+
+/// Get svg image data of blockies, as `str`
+#[pyfunction]
+#[pyo3(signature = (seed, dimension = (128, 128), size = 8, ethseed = false))]
+fn svg(seed: SeedArg, dimension: (usize, usize), size: usize, ethseed: bool) -> PyResult<String> {
+    let func_list: FuncList<SvgFn<'_>> = init_const_blockies_arr!(gen_svg);
+
+    Ok(func_list[check_size(size)? - 1](
+        &seed.to_seed(ethseed)?,
+        check_dimension(dimension)?,
+    ))
+}
+
+/// Canonicalize Ethereum address (to lowercase + set '0x' prefix)
+///
+/// `seed` is an Ethereum address `str` (with or without '0x' prefix), or raw 20-byte address `bytes`.
+#[pyfunction]
+fn to_ethaddr_seed(seed: SeedArg) -> PyResult<String> {
+    String::from_utf8(ethaddr_seed(seed.as_bytes())?.to_vec())
+        .map_err(|_| PyValueError::new_err("Invalid Ethereum address: not a hex string"))
+}
+
+/// Python module `eth_blockies`
+#[pymodule]
+#[pyo3(name = "eth_blockies")]
+pub fn eth_blockies_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_function(wrap_pyfunction!(data, m)?)?;
+    m.add_function(wrap_pyfunction!(indexed_data, m)?)?;
+    m.add_function(wrap_pyfunction!(png, m)?)?;
+    m.add_function(wrap_pyfunction!(svg, m)?)?;
+    m.add_function(wrap_pyfunction!(to_ethaddr_seed, m)?)?;
+    Ok(())
+}
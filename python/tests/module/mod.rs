@@ -0,0 +1,163 @@
+// Call the Python module from an embedded interpreter, and compare the results with the library.
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod module {
+    use eth_blockies::*;
+    use pyo3::exceptions::PyValueError;
+    use pyo3::prelude::*;
+    use pyo3::types::{PyBytes, PyDict};
+    use std::ffi::CStr;
+
+    const ADDR: &str = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+
+    // evaluate python expression, with the module as `eth_blockies`
+    fn eval<'py>(py: Python<'py>, expr: &CStr) -> PyResult<Bound<'py, PyAny>> {
+        let globals = PyDict::new(py);
+        globals.set_item(
+            "eth_blockies",
+            pyo3::wrap_pymodule!(eth_blockies_python::eth_blockies_module)(py),
+        )?;
+        py.eval(expr, Some(&globals), None)
+    }
+
+    #[test]
+    fn data() {
+        Python::attach(|py| {
+            let data: Vec<Vec<RgbPixel>> = eval(py, c"eth_blockies.data('generic_seed')")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(
+                data,
+                EthBlockies::data("generic_seed")
+                    .iter()
+                    .map(|row| row.to_vec())
+                    .collect::<Vec<_>>()
+            );
+
+            // bytes seed, with size
+            let data: Vec<Vec<RgbPixel>> = eval(py, c"eth_blockies.data(b'generic_seed', size=16)")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(
+                data,
+                Blockies::<16>::data("generic_seed")
+                    .iter()
+                    .map(|row| row.to_vec())
+                    .collect::<Vec<_>>()
+            );
+        })
+    }
+
+    #[test]
+    fn indexed_data() {
+        Python::attach(|py| {
+            let (palette, rows): (RgbPalette, Vec<Vec<u8>>) = eval(
+                py,
+                c"eth_blockies.indexed_data('0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC', ethseed=True)",
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+
+            let (expected_palette, expected_data) =
+                EthBlockies::indexed_data(ADDR.to_ethaddr_seed());
+            assert_eq!(palette, expected_palette);
+            assert_eq!(
+                rows,
+                expected_data
+                    .iter()
+                    .map(|row| row.iter().map(u8::from).collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
+            );
+
+            // rows are lists of int (not bytes)
+            assert!(eval(
+                py,
+                c"all(isinstance(i, int) for i in eth_blockies.indexed_data('generic_seed')[1][0])"
+            )
+            .unwrap()
+            .is_truthy()
+            .unwrap());
+        })
+    }
+
+    #[test]
+    fn png() {
+        Python::attach(|py| {
+            let png = eval(py, c"eth_blockies.png('generic_seed')").unwrap();
+            assert_eq!(
+                png.cast::<PyBytes>().unwrap().as_bytes(),
+                EthBlockies::png_data("generic_seed", (128, 128))
+            );
+
+            // raw 20-byte address seed, with options
+            let png = eval(
+                py,
+                c"eth_blockies.png(bytes.fromhex('e686c14ff9c11038f2b1c9ad617f2346cfb817dc'), \
+                  (64, 32), size=16, ethseed=True, compressed=True)",
+            )
+            .unwrap();
+            assert_eq!(
+                png.cast::<PyBytes>().unwrap().as_bytes(),
+                Blockies::<16>::compressed_png_data(ADDR.to_ethaddr_seed(), (64, 32))
+            );
+        })
+    }
+
+    #[test]
+    fn svg() {
+        Python::attach(|py| {
+            let svg: String = eval(
+                py,
+                c"eth_blockies.svg('generic_seed', dimension=(100, 100))",
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+            assert_eq!(svg, EthBlockies::svg_data("generic_seed", (100, 100)));
+        })
+    }
+
+    #[test]
+    fn to_ethaddr_seed() {
+        Python::attach(|py| {
+            let expected = String::from_utf8(ADDR.to_ethaddr_seed().to_vec()).unwrap();
+
+            [
+                c"eth_blockies.to_ethaddr_seed('0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC')",
+                c"eth_blockies.to_ethaddr_seed('E686C14FF9C11038F2B1C9AD617F2346CFB817DC')",
+                c"eth_blockies.to_ethaddr_seed(bytes.fromhex('e686c14ff9c11038f2b1c9ad617f2346cfb817dc'))",
+            ]
+            .iter()
+            .for_each(|expr| {
+                let addr: String = eval(py, expr).unwrap().extract().unwrap();
+                assert_eq!(addr, expected, "{:?}", expr);
+            });
+        })
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        Python::attach(|py| {
+            [
+                c"eth_blockies.to_ethaddr_seed('0x1234')",
+                c"eth_blockies.data('seed', size=0)",
+                c"eth_blockies.data('seed', size=33)",
+                c"eth_blockies.indexed_data('seed', ethseed=True)",
+                c"eth_blockies.png('seed', (0, 16))",
+                c"eth_blockies.svg('seed', (16, 0))",
+            ]
+            .iter()
+            .for_each(|expr| {
+                let err = eval(py, expr).unwrap_err();
+                assert!(err.is_instance_of::<PyValueError>(py), "{:?}", expr);
+            });
+
+            // seed must be str or bytes
+            assert!(eval(py, c"eth_blockies.data(1234)").is_err());
+        })
+    }
+}
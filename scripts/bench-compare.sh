@@ -0,0 +1,80 @@
+#!/bin/sh
+#
+# bench-compare.sh
+#
+# compare throughput of 2 criterion baselines (in 'target/criterion'),
+# and fail if any benchmark regressed more than the threshold
+#
+# usage:
+#   bench-compare.sh <old-baseline> [new-baseline] [threshold-percent]
+#      - <old-baseline>: baseline saved before (e.g. 'cargo bench -- --save-baseline main')
+#      - [new-baseline]: baseline to compare [default: 'new' (the latest run of 'cargo bench')]
+#      - [threshold-percent]: max allowed slowdown of median time in percent [default: 5]
+#
+# example:
+#   git checkout main && cargo bench -- --save-baseline main
+#   git checkout my-branch && cargo bench -- --save-baseline my-branch
+#   scripts/bench-compare.sh main my-branch 3
+#
+
+OLD="$1"
+NEW="${2:-new}"
+THRESHOLD="${3:-5}"
+CRITERION_DIR="${CARGO_TARGET_DIR:-target}/criterion"
+
+if [ -z "$OLD" ]; then
+    sed -n '/^# usage:/,/^#$/p' "$0" | sed 's/^# \{0,1\}//' >&2
+    exit 2
+fi
+
+# print '<median-time-ns> <throughput-kind> <throughput-value>' of a benchmark baseline dir
+bench_result() {
+    # median point estimate
+    sed 's/.*"median":{"confidence_interval":{[^}]*},"point_estimate":\([^,]*\),.*/\1/' \
+        "$1/estimates.json"
+    printf ' '
+    # throughput ('Bytes' or 'Elements', if any)
+    sed -n 's/.*"throughput":{"\([A-Za-z]*\)":\([0-9]*\)}.*/\1 \2/p' "$1/benchmark.json"
+    echo
+}
+
+find "$CRITERION_DIR" -path "*/$OLD/estimates.json" 2>/dev/null | sort | {
+    FOUND=0
+    REGRESSED=0
+
+    printf '%-36s %14s %14s %14s %12s\n' "benchmark" "old icons/s" "new icons/s" "new MiB/s" "time change"
+    while read -r OLD_ESTIMATES; do
+        BENCH_DIR="${OLD_ESTIMATES%/"$OLD"/estimates.json}"
+        [ -f "$BENCH_DIR/$NEW/estimates.json" ] || continue
+        FOUND=1
+
+        RESULT=$(printf '%s %s %s' \
+            "${BENCH_DIR#"$CRITERION_DIR"/}" \
+            "$(bench_result "$BENCH_DIR/$OLD")" \
+            "$(bench_result "$BENCH_DIR/$NEW")" |
+            awk -v threshold="$THRESHOLD" '{
+                # $1: name, $2-$4: old (time, kind, value), $5-$7: new (time, kind, value)
+                name = $1; old_time = $2; new_time = $5
+                if ($3 == "Elements") { old_time /= $4 }
+                if ($6 == "Elements") { new_time /= $7 }
+                mib = ($6 == "Bytes") ? sprintf("%.2f", $7 / (new_time / 1e9) / 1048576) : "-"
+                change = (new_time - old_time) / old_time * 100
+                printf "%-36s %14.1f %14.1f %14s %+11.2f%%%s\n", name, 1e9 / old_time, 1e9 / new_time, \
+                    mib, change, (change > threshold) ? "  REGRESSED" : ""
+            }')
+        echo "$RESULT"
+
+        case "$RESULT" in
+            *REGRESSED) REGRESSED=1 ;;
+        esac
+    done
+
+    if [ "$FOUND" = 0 ]; then
+        echo "No benchmark found in both baselines '$OLD' and '$NEW' ('$CRITERION_DIR')" >&2
+        exit 2
+    fi
+    if [ "$REGRESSED" = 1 ]; then
+        echo "Regression over ${THRESHOLD}% found!" >&2
+        exit 1
+    fi
+}
@@ -34,7 +34,7 @@ impl CmdOpt for OptList {
                 // if opt exists, set exist flag
                 |v| {
                     // check if value exists if needed, or does not exist if not needed
-                    (v.is_some() == need_opt_value).then(|| (true, v)).ok_or(
+                    (v.is_some() == need_opt_value).then_some((true, v)).ok_or(
                         BinError::InvalidInput(format!(
                             "Invalid argument: Argument '{}' {} option value!",
                             opt_name,
@@ -59,108 +59,127 @@ impl CmdOpt for OptList {
     }
 }
 
-// parse args iterator, and return tuple of (option list, normal arg list)
+// parse command line args, and return tuple of (option list, normal arg list)
 pub fn parse_args() -> BinResult<(ArgList, OptList)> {
-    let mut args = env::args();
-    let _bin_name = args.next();
-
-    args.fold(
-        Ok(((ArgList::new(), OptList::new()), None, false)),
-        |list_wrapper, arg| {
-            list_wrapper.and_then(|(mut list, mut last_opt_name, opt_mode)| {
-                // parse current argument
-                // (branch according to: if OPTION/NORMAL ARG)
-                match (
-                    arg.to_string(),
-                    arg.split_once('-')
-                        .filter(|s| s.0.is_empty() && (!s.1.is_empty())),
-                ) {
-                    // OPTION ARG (starting with '-')
-                    (_, Some(optstr)) => {
-                        // check if long or short option argument
-                        // (branch according to: if option argument is long/short form)
-                        match (
-                            optstr.1.to_string(),
-                            optstr.1.split_once('-').filter(|s| {
-                                s.0.is_empty() && {
-                                    // s.1 is not empty && not '='
-                                    s.1.chars().next().filter(|c| *c != '=').is_some()
-                                }
-                            }),
-                        ) {
-                            // long option
-                            (_, Some(("", longopt))) => match longopt.find('=') {
-                                // if opt value exists (embeded with '=')
-                                Some(pos) => {
-                                    longopt
-                                        .get(..pos)
-                                        .zip(longopt.get((pos + 1)..))
-                                        // return (k, v)
-                                        .map(|(k, v)| (k.to_string(), Some(v.to_string())))
-                                }
-
-                                // if opt value does not exist
-                                _ => {
-                                    // set next arg as value of current opt
-                                    last_opt_name = Some(longopt.to_string());
-
-                                    // return (k, v)
-                                    Some((longopt.to_string(), None))
-                                }
-                            },
+    // skip bin name
+    parse_arg_list(env::args().skip(1))
+}
 
-                            // short option
-                            (shortopt, _) => match shortopt.split_at(1) {
-                                // if opt value exists (embedded after option char)
-                                (k, "") => {
-                                    // set next arg as value of current opt
-                                    last_opt_name = Some(shortopt.to_string());
+// parse args iterator (without bin name), and return tuple of (option list, normal arg list)
+//   - '--' ends options: it is kept in normal arg list as a separator,
+//     and every arg after it is a normal arg (even if starting with '-')
+pub fn parse_arg_list<I: IntoIterator<Item = String>>(args: I) -> BinResult<(ArgList, OptList)> {
+    let mut args = args.into_iter();
+
+    args.try_fold(
+        ((ArgList::new(), OptList::new()), None, false, false),
+        |(mut list, mut last_opt_name, opt_mode, args_only), arg| {
+            // parse current argument
+            // (branch according to: if OPTION/NORMAL ARG)
+            match (
+                arg.to_string(),
+                arg.split_once('-')
+                    .filter(|s| s.0.is_empty() && (!s.1.is_empty())),
+            ) {
+                // '--', or any arg after it
+                (fullstr, _) if args_only || fullstr == "--" => {
+                    list.0.push(fullstr);
+
+                    // return next list_wrapper
+                    Ok((list, None, opt_mode, true))
+                }
 
+                // OPTION ARG (starting with '-')
+                (_, Some(optstr)) => {
+                    // check if long or short option argument
+                    // (branch according to: if option argument is long/short form)
+                    match (
+                        optstr.1.to_string(),
+                        optstr.1.split_once('-').filter(|s| {
+                            s.0.is_empty() && {
+                                // s.1 is not empty && not '='
+                                s.1.chars().next().filter(|c| *c != '=').is_some()
+                            }
+                        }),
+                    ) {
+                        // long option
+                        (_, Some(("", longopt))) => match longopt.find('=') {
+                            // if opt value exists (embeded with '=')
+                            Some(pos) => {
+                                longopt
+                                    .get(..pos)
+                                    .zip(longopt.get((pos + 1)..))
                                     // return (k, v)
-                                    Some((k.to_string(), None))
-                                }
+                                    .map(|(k, v)| (k.to_string(), Some(v.to_string())))
+                            }
 
-                                // if opt value does not exist
-                                (k, v) => {
-                                    // return (k, v)
-                                    Some((k.to_string(), Some(v.to_string())))
-                                }
-                            },
-                        }
-                        // insert parsed key & value
-                        .map(|(k, v)| list.1.insert(k, v))
-                        .or_else(|| panic!("Unexpected error on parsing arguments!"));
+                            // if opt value does not exist
+                            _ => {
+                                // set next arg as value of current opt
+                                last_opt_name = Some(longopt.to_string());
 
-                        // return next list_wrapper
-                        Ok((list, last_opt_name, true))
-                    }
+                                // return (k, v)
+                                Some((longopt.to_string(), None))
+                            }
+                        },
+
+                        // short option
+                        (shortopt, _) => match shortopt.split_at(
+                            // split after first char (not byte)
+                            shortopt
+                                .char_indices()
+                                .nth(1)
+                                .map_or(shortopt.len(), |(idx, _)| idx),
+                        ) {
+                            // if opt value exists (embedded after option char)
+                            (k, "") => {
+                                // set next arg as value of current opt
+                                last_opt_name = Some(shortopt.to_string());
 
-                    // NORMAL ARG
-                    (fullstr, _) => {
-                        // set current string as opt value of last_opt_name
-                        last_opt_name.as_ref().and_then(|opt_name| {
-                            list.1.insert(opt_name.clone(), Some(fullstr.clone()))
-                        });
-
-                        // set current string as arg list, only if not in opt_mode
-                        match opt_mode {
-                            true => {
-                                last_opt_name.ok_or(BinError::InvalidInput(format!(
-                                    "Invalid argument: '{}'",
-                                    fullstr
-                                )))?;
+                                // return (k, v)
+                                Some((k.to_string(), None))
                             }
-                            false => {
-                                list.0.push(fullstr.clone());
+
+                            // if opt value does not exist
+                            (k, v) => {
+                                // return (k, v)
+                                Some((k.to_string(), Some(v.to_string())))
                             }
-                        }
+                        },
+                    }
+                    // insert parsed key & value
+                    .map(|(k, v)| list.1.insert(k, v))
+                    .or_else(|| panic!("Unexpected error on parsing arguments!"));
 
-                        // return next list_wrapper
-                        Ok((list, None, opt_mode))
+                    // return next list_wrapper
+                    Ok((list, last_opt_name, true, args_only))
+                }
+
+                // NORMAL ARG
+                (fullstr, _) => {
+                    // set current string as opt value of last_opt_name
+                    last_opt_name.as_ref().and_then(|opt_name| {
+                        list.1.insert(opt_name.clone(), Some(fullstr.clone()))
+                    });
+
+                    // set current string as arg list, only if not in opt_mode
+                    match opt_mode {
+                        true => {
+                            last_opt_name.ok_or(BinError::InvalidInput(format!(
+                                "Invalid argument: '{}'",
+                                fullstr
+                            )))?;
+                        }
+                        false => {
+                            list.0.push(fullstr.clone());
+                        }
                     }
+
+                    // return next list_wrapper
+                    Ok((list, None, opt_mode, args_only))
                 }
-            })
+            }
         },
     )
-    .map(|(list, _, _)| list)
+    .map(|(list, _, _, _)| list)
 }
@@ -3,6 +3,18 @@ use eth_blockies::*;
 pub const MIN_BLOCKIES_SIZE: usize = 1;
 pub const MAX_BLOCKIES_SIZE: usize = 32;
 
+// list of generator functions, one for each blockies size
+type FuncList<'a, F> = [&'a F; MAX_BLOCKIES_SIZE - MIN_BLOCKIES_SIZE + 1];
+type AnsiseqFn<'a, I> = dyn Fn(I, (usize, usize), bool) -> Vec<String> + 'a;
+type ImageFn<'a, I> = dyn Fn(I, (usize, usize)) -> Vec<u8> + 'a;
+type CheckedImageFn<'a, I> = dyn Fn(I, (usize, usize)) -> Option<Vec<u8>> + 'a;
+type PngFn<'a, I> = dyn Fn(I, (usize, usize), PngOptions) -> Vec<u8> + 'a;
+type SheetPngFn<'a, I> = dyn Fn(&[I], SheetOptions) -> Vec<u8> + 'a;
+type SheetSvgFn<'a, I> = dyn Fn(&[I], SheetOptions) -> String + 'a;
+#[cfg(feature = "server")]
+type CachedFn<'a, I> =
+    dyn Fn(&RenderCache, I, (usize, usize), RenderFormat) -> Option<std::sync::Arc<[u8]>> + 'a;
+
 macro_rules! init_const_blockies_arr {
 
     // wrapper
@@ -22,6 +34,23 @@ macro_rules! init_const_blockies_arr {
         ]
     };
 
+    // wrapper of RenderCache::get, with seed type
+    ( @cached $seed_type:ty ) => {
+        init_const_blockies_arr!(@gen_cached_arr
+            $seed_type, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+            22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+        )
+    };
+
+    // gen array of RenderCache::get call, with given blockies_sizes (args)
+    (@gen_cached_arr $seed_type:ty, $( $size:literal ), *) => {
+        [
+            $(
+                &RenderCache::get::<$size, $seed_type>,
+            )*
+        ]
+    };
+
 }
 
 pub fn gen_ansiseq<I: SeedInput>(
@@ -30,8 +59,7 @@ pub fn gen_ansiseq<I: SeedInput>(
     dimension: (usize, usize),
     is_utf8: bool,
 ) -> Vec<String> {
-    let func_list: [&dyn Fn(I, (usize, usize), bool) -> Vec<String>;
-        MAX_BLOCKIES_SIZE - MIN_BLOCKIES_SIZE + 1] = init_const_blockies_arr!(ansiseq_data);
+    let func_list: FuncList<AnsiseqFn<'_, I>> = init_const_blockies_arr!(ansiseq_data);
 
     func_list[blockies_size - 1](seed, dimension, is_utf8)
 }
@@ -40,11 +68,11 @@ pub fn gen_image<I: SeedInput>(
     blockies_size: usize,
     seed: I,
     dimension: (usize, usize),
+    options: PngOptions,
 ) -> Vec<u8> {
-    let func_list: [&dyn Fn(I, (usize, usize)) -> Vec<u8>;
-        MAX_BLOCKIES_SIZE - MIN_BLOCKIES_SIZE + 1] = init_const_blockies_arr!(png_data);
+    let func_list: FuncList<PngFn<'_, I>> = init_const_blockies_arr!(png_data_with_options);
 
-    func_list[blockies_size - 1](seed, dimension)
+    func_list[blockies_size - 1](seed, dimension, options)
 }
 
 #[cfg(feature = "compressed_png")]
@@ -52,9 +80,107 @@ pub fn gen_comp_image<I: SeedInput>(
     blockies_size: usize,
     seed: I,
     dimension: (usize, usize),
+    options: PngOptions,
+) -> Vec<u8> {
+    let func_list: FuncList<PngFn<'_, I>> =
+        init_const_blockies_arr!(compressed_png_data_with_options);
+
+    func_list[blockies_size - 1](seed, dimension, options)
+}
+
+pub fn gen_bmp_image<I: SeedInput>(
+    blockies_size: usize,
+    seed: I,
+    dimension: (usize, usize),
+) -> Vec<u8> {
+    let func_list: FuncList<ImageFn<'_, I>> = init_const_blockies_arr!(bmp_data);
+
+    func_list[blockies_size - 1](seed, dimension)
+}
+
+pub fn gen_ppm_image<I: SeedInput>(
+    blockies_size: usize,
+    seed: I,
+    dimension: (usize, usize),
+) -> Vec<u8> {
+    let func_list: FuncList<ImageFn<'_, I>> = init_const_blockies_arr!(ppm_data);
+
+    func_list[blockies_size - 1](seed, dimension)
+}
+
+pub fn gen_tga_image<I: SeedInput>(
+    blockies_size: usize,
+    seed: I,
+    dimension: (usize, usize),
+) -> Option<Vec<u8>> {
+    let func_list: FuncList<CheckedImageFn<'_, I>> = init_const_blockies_arr!(tga_data);
+
+    func_list[blockies_size - 1](seed, dimension)
+}
+
+#[cfg(feature = "qoi")]
+pub fn gen_qoi_image<I: SeedInput>(
+    blockies_size: usize,
+    seed: I,
+    dimension: (usize, usize),
 ) -> Vec<u8> {
-    let func_list: [&dyn Fn(I, (usize, usize)) -> Vec<u8>;
-        MAX_BLOCKIES_SIZE - MIN_BLOCKIES_SIZE + 1] = init_const_blockies_arr!(compressed_png_data);
+    let func_list: FuncList<ImageFn<'_, I>> = init_const_blockies_arr!(qoi_data);
+
+    func_list[blockies_size - 1](seed, dimension)
+}
+
+#[cfg(feature = "webp_lossless")]
+pub fn gen_webp_image<I: SeedInput>(
+    blockies_size: usize,
+    seed: I,
+    dimension: (usize, usize),
+) -> Option<Vec<u8>> {
+    let func_list: FuncList<CheckedImageFn<'_, I>> = init_const_blockies_arr!(webp_data);
 
     func_list[blockies_size - 1](seed, dimension)
 }
+
+pub fn gen_sheet_image<I: SeedInput>(
+    blockies_size: usize,
+    seeds: &[I],
+    options: SheetOptions,
+) -> Vec<u8> {
+    let func_list: FuncList<SheetPngFn<'_, I>> = init_const_blockies_arr!(sheet_png_data);
+
+    func_list[blockies_size - 1](seeds, options)
+}
+
+#[cfg(feature = "compressed_png")]
+pub fn gen_comp_sheet_image<I: SeedInput>(
+    blockies_size: usize,
+    seeds: &[I],
+    options: SheetOptions,
+) -> Vec<u8> {
+    let func_list: FuncList<SheetPngFn<'_, I>> =
+        init_const_blockies_arr!(compressed_sheet_png_data);
+
+    func_list[blockies_size - 1](seeds, options)
+}
+
+pub fn gen_sheet_svg<I: SeedInput>(
+    blockies_size: usize,
+    seeds: &[I],
+    options: SheetOptions,
+) -> String {
+    let func_list: FuncList<SheetSvgFn<'_, I>> = init_const_blockies_arr!(sheet_svg_data);
+
+    func_list[blockies_size - 1](seeds, options)
+}
+
+#[cfg(feature = "server")]
+pub fn gen_cached<I: SeedInput>(
+    cache: &RenderCache,
+    blockies_size: usize,
+    seed: I,
+    dimension: (usize, usize),
+    format: RenderFormat,
+) -> Option<std::sync::Arc<[u8]>> {
+    let func_list: FuncList<CachedFn<'_, I>> = init_const_blockies_arr!(@cached I);
+
+    func_list[blockies_size - 1](cache, seed, dimension, format)
+}
@@ -1,88 +1,149 @@
 use eth_blockies::SeedInput;
 
-use std::env;
+use std::{
+    env,
+    io::{self, BufRead},
+};
 
 mod arg_parser;
 use arg_parser::*;
 mod bin_error;
 use bin_error::BinError;
-use print_blockies::{print_ansi, print_image};
+use print_blockies::{print_ansi, print_image, print_sheet};
 mod const_generic_call_mapper;
 use const_generic_call_mapper::{MAX_BLOCKIES_SIZE, MIN_BLOCKIES_SIZE};
 mod print_blockies;
+#[cfg(feature = "server")]
+mod serve;
 
 fn main() {
-    match {
-        parse_args()
-            // run main routine
-            .and_then(|(arg_list, mut opt_list)| {
-                let mut arg_list = arg_list.iter();
+    let result = parse_args()
+        // run main routine
+        .and_then(|(mut arg_list, mut opt_list)| {
+            // command is taken only from args before '--'
+            //   (e.g. `-- sheet` for blockies of seed 'sheet')
+            let separator_pos = arg_list.iter().position(|arg| arg == "--");
+            if let Some(pos) = separator_pos {
+                arg_list.remove(pos);
+            }
+            let command = match separator_pos {
+                Some(0) => None,
+                _ => arg_list.first().cloned(),
+            };
+            let mut arg_list = arg_list.iter();
 
-                // help message option
-                match opt_list.get_opt("help", false)?.0 {
+            // serve command
+            #[cfg(feature = "server")]
+            if command.as_deref() == Some("serve") {
+                arg_list.next();
+                return match opt_list.get_opt("help", false)?.0 {
                     true => Err(BinError::HelpArgument),
-                    false => Ok(()),
+                    false => serve::serve(arg_list.as_slice(), opt_list),
+                };
+            }
+
+            // help message option
+            match opt_list.get_opt("help", false)?.0 {
+                true => Err(BinError::HelpArgument),
+                false => Ok(()),
+            }
+            // check if sheet command
+            .map(|()| {
+                let is_sheet = command.as_deref() == Some("sheet");
+                if is_sheet {
+                    arg_list.next();
                 }
-                // initialize seed
-                .and_then(
-                    |()| match (arg_list.next(), opt_list.get_opt("ethseed", false)?.0) {
-                        (Some(seed), false) => Ok(seed.as_bytes().to_vec()),
-                        (Some(seed), true) => Ok(seed.to_ethaddr_seed().to_vec()),
-                        (None, _) => Err(BinError::NoArgument),
+                is_sheet
+            })
+            // initialize seeds
+            .and_then(|is_sheet| {
+                let is_ethseed = opt_list.get_opt("ethseed", false)?.0;
+                let seed_strings: Vec<String> = match is_sheet {
+                    // all remaining args, or each line of stdin if no args
+                    true => match arg_list.len() {
+                        0 => io::stdin()
+                            .lock()
+                            .lines()
+                            .map(|line| line.map(|line| line.trim().to_string()))
+                            .filter(|line| !matches!(line, Ok(line) if line.is_empty()))
+                            .collect::<Result<_, _>>()
+                            .map_err(|e| BinError::InvalidInput(e.to_string()))?,
+                        _ => arg_list.by_ref().cloned().collect(),
                     },
-                )
-                // blockies size
-                .and_then(|seed| {
-                    Ok((
-                        seed,
-                        opt_list
-                            .get_opt("size", true)?
-                            .1
-                            .unwrap_or(format!("{0}", 8)) // default
-                            .parse()
-                            .ok()
-                            .filter(|s| (MIN_BLOCKIES_SIZE..=MAX_BLOCKIES_SIZE).contains(s))
-                            .ok_or(BinError::InvalidInput(format!(
-                                "Invalid argument: Invalid blockies size\n\
+                    false => arg_list.next().cloned().into_iter().collect(),
+                };
+
+                match seed_strings.is_empty() {
+                    true => Err(BinError::NoArgument),
+                    false => Ok((
+                        is_sheet,
+                        seed_strings
+                            .iter()
+                            .map(|seed| match is_ethseed {
+                                false => seed.as_bytes().to_vec(),
+                                true => seed.to_ethaddr_seed().to_vec(),
+                            })
+                            .collect::<Vec<Vec<u8>>>(),
+                    )),
+                }
+            })
+            // blockies size
+            .and_then(|(is_sheet, seeds)| {
+                Ok((
+                    is_sheet,
+                    seeds,
+                    opt_list
+                        .get_opt("size", true)?
+                        .1
+                        .unwrap_or(format!("{0}", 8)) // default
+                        .parse()
+                        .ok()
+                        .filter(|s| (MIN_BLOCKIES_SIZE..=MAX_BLOCKIES_SIZE).contains(s))
+                        .ok_or(BinError::InvalidInput(format!(
+                            "Invalid argument: Invalid blockies size\n\
                                      Enter integer in range {}-{}.",
-                                MIN_BLOCKIES_SIZE, MAX_BLOCKIES_SIZE
-                            )))?,
-                    ))
-                })
-                // select output format
-                .and_then(|(seed, blockies_size)| {
-                    match arg_list.next().map(String::as_str) {
-                        Some("ansi") | Some("a") | None => {
-                            print_ansi(blockies_size, &seed, opt_list)
-                        } // default
-                        Some("image") | Some("i") => print_image(blockies_size, &seed, opt_list),
-                        Some(input) => Err(BinError::InvalidInput(format!(
-                            "Invalid argument: Invalid output format: '{}'\n\
-                                 Use either 'ansi' or 'image' here.",
-                            input
-                        ))),
-                    }
-                })
+                            MIN_BLOCKIES_SIZE, MAX_BLOCKIES_SIZE
+                        )))?,
+                ))
             })
-            // print message if error
-            .map_err(|e| {
-                match e {
-                    BinError::NoArgument => {
-                        eprintln!("{}", bin_usage());
-                    }
-                    BinError::HelpArgument => {
-                        eprintln!("{}", help_msg());
+            // select output format
+            .and_then(|(is_sheet, seeds, blockies_size)| {
+                match (is_sheet, arg_list.next().map(String::as_str)) {
+                    (true, _) => print_sheet(blockies_size, &seeds, opt_list),
+                    (false, Some("ansi")) | (false, Some("a")) | (false, None) => {
+                        print_ansi(blockies_size, &seeds[0], opt_list)
+                    } // default
+                    (false, Some("image")) | (false, Some("i")) => {
+                        print_image(blockies_size, &seeds[0], opt_list)
                     }
-                    BinError::InvalidInput(err_msg) => {
-                        eprintln!(
-                            "{}\n\n* For detailed usages of this binary, \
-                                 run this binary with -h (--help) option.",
-                            err_msg
-                        )
-                    }
-                };
+                    (false, Some(input)) => Err(BinError::InvalidInput(format!(
+                        "Invalid argument: Invalid output type: '{}'\n\
+                                 Use either 'ansi' or 'image' here.",
+                        input
+                    ))),
+                }
             })
-    } {
+        })
+        // print message if error
+        .map_err(|e| {
+            match e {
+                BinError::NoArgument => {
+                    eprintln!("{}", bin_usage());
+                }
+                BinError::HelpArgument => {
+                    eprintln!("{}", help_msg());
+                }
+                BinError::InvalidInput(err_msg) => {
+                    eprintln!(
+                        "{}\n\n* For detailed usages of this binary, \
+                                 run this binary with -h (--help) option.",
+                        err_msg
+                    )
+                }
+            };
+        });
+
+    match result {
         // return exit code
         Ok(_) => std::process::exit(0),
         _ => std::process::exit(1),
@@ -97,24 +158,50 @@ fn help_msg() -> String {
          Written by {pkg_author}\n\
          License: {pkg_license}\n\n\n\
          {bin_usage}\n\n\
-         <seed>                 Seed to generate blockies (e.g. Ethereum wallet address)\n\n\
+         <seed>                 Seed to generate blockies (e.g. Ethereum wallet address)\n\
+         \0                       Put '--' before a seed of command name (e.g. -- sheet),\n\
+         \0                       for blockies of the seed, not the command\n\n\
          [output-fmt]  - ansi   (Default) Generate ansi sequence of blockies,\n\
          \0                       usually for printing to terminal\n\
-         \0              - image  Generate png image data of blockies\n\n\
+         \0              - image  Generate image data of blockies (png, bmp, ppm, tga)\n\n\
+         sheet [seeds...]       Generate a single sheet image (png, svg) of blockies\n\
+         \0                       for multiple seeds, laid out in a grid\n\
+         \0                       If no seed is given, each line of stdin is used\n\n\
+         {serve_msg}\
          [OPTIONS...]:\n\n\
          \t-e --ethseed   Interpret seed string as Ethereum address,\n\
          \t               and canonicalize seed (to lowercase + set '0x' prefix)\n\
          \t               to get Ethereum blockies correctly\n\
          \t-a --ascii     (only for 'ansi' mode)   Get non-compact, big blockies\n\
          \t                                        with ascii (non-unicode)\n\
-         {raw_arg_msg}\n\
+         {raw_arg_msg}\
+         \t-m --minimize  (only for 'image' mode)  Search for the smallest png image\n\
+         \t                                        (slow, especially on large dimension)\n\
+         \t-i --interlace (only for 'image' mode)  Get Adam7-interlaced png image,\n\
+         \t                                        for progressive loading\n\n\
+         \t-f --format=<IMAGE_FORMAT>\n\
+         \t               (only for 'image' mode)  Image file format to generate\n\
+         \t               One of '{image_formats}' (Default: 'png')\n\
+         \t               (only for 'sheet' mode)  One of 'png', 'svg' (Default: 'png')\n\n\
          \t-s --size=<BLOCKIES_SIZE> \n\
          \t               Blockies size: # of elems per side (1-32) (Default: '8')\n\n\
          \t-d --dimension=<WIDTH>x<HEIGHT>\n\
          \t               Dimensions of output in the form of '(width)x(height)'\n\
          \t               If not given, following is used (Default):\n\
          \t               - ('ansi' mode)   '(blockies_size)x(blockies_size)'\n\
-         \t               - ('image' mode)  '128x128'\n\n\
+         \t               - ('image' mode)  '128x128'\n\
+         \t               - ('sheet' mode)  '64x64' (for each blockies)\n\n\
+         \t-c --columns=<COLUMNS>\n\
+         \t               (only for 'sheet' mode)  Max # of blockies per row (Default: '8')\n\n\
+         \t-g --gap=<PIXELS>\n\
+         \t               (only for 'sheet' mode)  Spacing between blockies (Default: '8')\n\n\
+         \t-b --background=<RRGGBB|none>\n\
+         \t               (only for 'sheet' mode)  Background color of sheet,\n\
+         \t                                        or 'none' for transparent (Default: 'ffffff')\n\n\
+         \t-l --labels    (only for 'image', 'sheet' mode)\n\
+         \t                                        Draw seed under blockies\n\
+         \t                                        (abbreviated if Ethereum address seed)\n\
+         \t                                        ('image' mode: png only, inside dimension)\n\n\
          \t-o --outfile=<FILENAME>\n\
          \t               File name to write output\n\
          \t               If the parameter is not given, stdout is used (Default)\n\n\n\n\
@@ -129,7 +216,18 @@ fn help_msg() -> String {
          - Outputs from following commands are all the same:\n\
          $ {bin_name} \"generic_seed\" image > blockies.png\n\
          $ {bin_name} \"generic_seed\" i -d128x128 -oblockies.png\n\
-         $ {bin_name} \"generic_seed\" i -d 128x128 -o blockies.png\n",
+         $ {bin_name} \"generic_seed\" i -d 128x128 -o blockies.png\n\n\
+         - Outputs from following commands are all the same:\n\
+         $ {bin_name} \"generic_seed\" image --format=bmp > blockies.bmp\n\
+         $ {bin_name} \"generic_seed\" i -fbmp -oblockies.bmp\n\n\
+         - Outputs from following commands are all the same:\n\
+         $ {bin_name} {example_addr_without_0x} image -e --labels -d 128x142 > blockies.png\n\
+         $ {bin_name} {example_addr_canonical} i -l -d128x142 -oblockies.png\n\n\
+         - Outputs from following commands are all the same:\n\
+         $ {bin_name} sheet \"seed1\" \"seed2\" \"seed3\" --labels > sheet.png\n\
+         $ printf 'seed1\\nseed2\\nseed3\\n' | {bin_name} sheet -l -osheet.png\n\n\
+         - Blockies of seed 'sheet', with options before '--':\n\
+         $ {bin_name} -s 15 -- sheet image > blockies.png\n",
         pkg_name = env!("CARGO_PKG_NAME"),
         pkg_ver = env!("CARGO_PKG_VERSION"),
         feature_variation = {
@@ -148,6 +246,25 @@ fn help_msg() -> String {
         bin_name = env!("CARGO_BIN_NAME"),
         example_addr_canonical = "0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc",
         example_addr_without_0x = "e686c14FF9C11038F2B1c9aD617F2346CFB817dC",
+        image_formats = print_blockies::image_format_names().join("', '"),
+        serve_msg = {
+            #[cfg(feature = "server")]
+            {
+                "serve                  Serve blockies images over http:\n\
+                 \0                       GET /<seed>.<png|svg|ico>?<PARAMS...>\n\
+                 \0                       - size=<BLOCKIES_SIZE>  (1-32) (Default: '8')\n\
+                 \0                       - dim=<WIDTH>[x<HEIGHT>]  (Default: '128')\n\
+                 \0                       - transparent=1  Round icon, transparent outside\n\
+                 \0                       - ethseed=1  Same as '--ethseed'\n\n\
+                 \0                       -l --listen=<ADDR:PORT>  (Default: '127.0.0.1:8080')\n\
+                 \0                       -t --threads=<THREADS>  (1-256) (Default: # of cpus)\n\
+                 \0                       -c --cache=<MIB>  Size of image cache (Default: '64')\n\n"
+            }
+            #[cfg(not(feature = "server"))]
+            {
+                ""
+            }
+        },
         raw_arg_msg = {
             #[cfg(feature = "compressed_png")]
             {
@@ -163,7 +280,12 @@ fn help_msg() -> String {
 
 fn bin_usage() -> String {
     format!(
-        "usage: {bin_name} <seed> [output-fmt (ansi|image)] [OPTIONS...]",
+        "usage: {bin_name} <seed> [output-fmt (ansi|image)] [OPTIONS...]\n\
+         \0       {bin_name} sheet [seeds...] [OPTIONS...]{serve_usage}",
         bin_name = env!("CARGO_BIN_NAME"),
+        serve_usage = match cfg!(feature = "server") {
+            true => format!("\n\0       {} serve [OPTIONS...]", env!("CARGO_BIN_NAME")),
+            false => String::new(),
+        },
     )
 }
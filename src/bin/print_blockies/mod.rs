@@ -6,7 +6,9 @@ use crate::{
     bin_error::{BinError, BinResult},
     const_generic_call_mapper,
 };
-use eth_blockies::SeedInput;
+use eth_blockies::{
+    Caption, CaptionText, PngFilter, PngOptions, RenderOptions, SeedInput, SheetOptions,
+};
 
 // print as ascii ansi output
 pub fn print_ansi(blockies_size: usize, seed: &[u8], opt_list: OptList) -> BinResult<()> {
@@ -32,7 +34,7 @@ pub fn print_ansi(blockies_size: usize, seed: &[u8], opt_list: OptList) -> BinRe
                     default_blockies_size
                 )))?,
             // name of file out
-            opt_list.get_opt("outfile", true)?.1.map(String::from),
+            opt_list.get_opt("outfile", true)?.1,
         ))
         // check if opt_list is empty. if not, abort parsing and return warning
         .and_then(|ret| opt_list.check_if_empty().map(|_| ret))
@@ -55,73 +57,333 @@ pub fn print_ansi(blockies_size: usize, seed: &[u8], opt_list: OptList) -> BinRe
     Ok(())
 }
 
-// print as png format
+// image file formats
+enum ImageFormat {
+    Png {
+        is_compressed: bool,
+        options: PngOptions,
+    },
+    Bmp,
+    Ppm,
+    Tga,
+    #[cfg(feature = "qoi")]
+    Qoi,
+    #[cfg(feature = "webp_lossless")]
+    Webp,
+}
+
+// names of image formats available in the current build
+pub fn image_format_names() -> Vec<&'static str> {
+    [
+        Some("png"),
+        Some("bmp"),
+        Some("ppm"),
+        Some("tga"),
+        cfg!(feature = "qoi").then_some("qoi"),
+        cfg!(feature = "webp_lossless").then_some("webp"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+// print as image format (png, bmp, ppm, tga, ...)
 pub fn print_image(blockies_size: usize, seed: &[u8], opt_list: OptList) -> BinResult<()> {
-    fn parse_opts(opt_list: OptList) -> BinResult<(bool, (usize, usize), Option<String>)> {
+    fn parse_opts(
+        opt_list: OptList,
+        seed: &[u8],
+    ) -> BinResult<(ImageFormat, (usize, usize), Option<String>)> {
         let mut opt_list = opt_list.clone();
-        // Some( is_uncompressed_png, (dim-width, dim-height), Some(file-out-name) )
+        let dimension: (usize, usize) = opt_list
+            .get_opt("dimension", true)?
+            .1
+            .as_deref()
+            .unwrap_or("128x128") // default
+            .split_once('x')
+            .and_then(|(w, h)| w.parse().ok().zip(h.parse().ok()))
+            .ok_or(BinError::InvalidInput(
+                "Invalid argument: Invalid image dimension\n\
+                 Type input in the form of '(width)x(height)'. (e.g. 128x128)"
+                    .to_owned(),
+            ))?;
+        // Some( image-format, (dim-width, dim-height), Some(file-out-name) )
         Ok((
             // type
+            match opt_list
+                .get_opt("format", true)?
+                .1
+                .as_deref()
+                .unwrap_or("png") // default
             {
-                #[cfg(feature = "compressed_png")]
-                {
-                    !opt_list.get_opt("raw", false)?.0
-                }
-                #[cfg(not(feature = "compressed_png"))]
-                {
-                    false
-                }
+                "png" => ImageFormat::Png {
+                    is_compressed: {
+                        #[cfg(feature = "compressed_png")]
+                        {
+                            !opt_list.get_opt("raw", false)?.0
+                        }
+                        #[cfg(not(feature = "compressed_png"))]
+                        {
+                            false
+                        }
+                    },
+                    options: {
+                        let is_minimized = opt_list.get_opt("minimize", false)?.0;
+                        PngOptions {
+                            filter: match is_minimized {
+                                true => PngFilter::Smallest,
+                                false => PngFilter::None,
+                            },
+                            reduce_bit_depth: is_minimized,
+                            interlaced: opt_list.get_opt("interlace", false)?.0,
+                            render: RenderOptions {
+                                caption: opt_list.get_opt("labels", false)?.0.then(|| Caption {
+                                    // abbreviate only well-formed Ethereum address seed
+                                    text: match seed == seed.to_ethaddr_seed() {
+                                        true => CaptionText::EthAddress,
+                                        false => CaptionText::Seed,
+                                    },
+                                    // abbreviated address is 43 font pixels wide
+                                    scale: (dimension.0 / 48).max(1),
+                                    ..Default::default()
+                                }),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        }
+                    },
+                },
+                "bmp" => ImageFormat::Bmp,
+                "ppm" => ImageFormat::Ppm,
+                "tga" => ImageFormat::Tga,
+                #[cfg(feature = "qoi")]
+                "qoi" => ImageFormat::Qoi,
+                #[cfg(feature = "webp_lossless")]
+                "webp" => ImageFormat::Webp,
+                input => Err(BinError::InvalidInput(format!(
+                    "Invalid argument: Invalid image format: '{}'\n\
+                     Use one of '{}' here.",
+                    input,
+                    image_format_names().join("', '")
+                )))?,
             },
             // dimension (width, height)
-            opt_list
-                .get_opt("dimension", true)?
-                .1
-                .as_deref()
-                .unwrap_or("128x128") // default
-                .split_once('x')
-                .and_then(|(w, h)| w.parse().ok().zip(h.parse().ok()))
-                .ok_or(BinError::InvalidInput(
-                    "Invalid argument: Invalid image dimension\n\
-                     Type input in the form of '(width)x(height)'. (e.g. 128x128)"
-                        .to_owned(),
-                ))?,
+            dimension,
             // name of file out
-            opt_list.get_opt("outfile", true)?.1.map(String::from),
+            opt_list.get_opt("outfile", true)?.1,
         ))
         // check if opt_list is empty. if not, abort parsing and return warning
         .and_then(|ret| opt_list.check_if_empty().map(|_| ret))
     }
 
-    let (is_compressed, dimension, file_out) = parse_opts(opt_list)?;
+    let (img_format, dimension, file_out) = parse_opts(opt_list, seed)?;
 
-    let img_png_data = match is_compressed {
-        true => {
+    let img_data = match img_format {
+        ImageFormat::Png {
+            is_compressed: true,
+            options,
+        } => {
             #[cfg(feature = "compressed_png")]
             fn call_png<I: SeedInput>(
                 blockies_size: usize,
                 seed: I,
                 dimension: (usize, usize),
+                options: PngOptions,
             ) -> Vec<u8> {
-                const_generic_call_mapper::gen_comp_image(blockies_size, seed, dimension)
+                const_generic_call_mapper::gen_comp_image(blockies_size, seed, dimension, options)
             }
             #[cfg(not(feature = "compressed_png"))]
             fn call_png<I: SeedInput>(
                 blockies_size: usize,
                 seed: I,
                 dimension: (usize, usize),
+                options: PngOptions,
             ) -> Vec<u8> {
-                const_generic_call_mapper::gen_image(blockies_size, seed, dimension)
+                const_generic_call_mapper::gen_image(blockies_size, seed, dimension, options)
             }
 
-            call_png(blockies_size, seed, dimension)
+            call_png(blockies_size, seed, dimension, options)
+        }
+        ImageFormat::Png {
+            is_compressed: false,
+            options,
+        } => const_generic_call_mapper::gen_image(blockies_size, seed, dimension, options),
+        ImageFormat::Bmp => {
+            const_generic_call_mapper::gen_bmp_image(blockies_size, seed, dimension)
+        }
+        ImageFormat::Ppm => {
+            const_generic_call_mapper::gen_ppm_image(blockies_size, seed, dimension)
+        }
+        ImageFormat::Tga => {
+            const_generic_call_mapper::gen_tga_image(blockies_size, seed, dimension)
+                .ok_or_else(|| dimension_out_of_range("tga", "0-65535"))?
+        }
+        #[cfg(feature = "qoi")]
+        ImageFormat::Qoi => {
+            const_generic_call_mapper::gen_qoi_image(blockies_size, seed, dimension)
+        }
+        #[cfg(feature = "webp_lossless")]
+        ImageFormat::Webp => {
+            const_generic_call_mapper::gen_webp_image(blockies_size, seed, dimension)
+                .ok_or_else(|| dimension_out_of_range("webp", "1-16384"))?
+        }
+    };
+
+    use io::Write;
+    match file_out {
+        Some(file) => fs::File::create(file).and_then(|mut f| f.write_all(&img_data)),
+        None => io::stdout().write_all(&img_data),
+    }
+    .map_err(|e| BinError::InvalidInput(e.to_string()))?;
+
+    Ok(())
+}
+
+// error of the dimension which the image format cannot encode
+fn dimension_out_of_range(format_name: &str, range: &str) -> BinError {
+    BinError::InvalidInput(format!(
+        "Invalid argument: Invalid image dimension\n\
+         Each of width and height of '{}' image should be in range of {}.",
+        format_name, range
+    ))
+}
+
+// sheet file formats
+enum SheetFormat {
+    Png { is_compressed: bool },
+    Svg,
+}
+
+// print blockies of multiple seeds in a single sheet image (png, svg)
+pub fn print_sheet(blockies_size: usize, seeds: &[Vec<u8>], opt_list: OptList) -> BinResult<()> {
+    fn parse_opts(
+        opt_list: OptList,
+        seeds: &[Vec<u8>],
+    ) -> BinResult<(SheetFormat, SheetOptions, Option<String>)> {
+        let mut opt_list = opt_list.clone();
+        let default_options = SheetOptions::default();
+        // Some( sheet-format, sheet-options, Some(file-out-name) )
+        Ok((
+            // type
+            match opt_list
+                .get_opt("format", true)?
+                .1
+                .as_deref()
+                .unwrap_or("png") // default
+            {
+                "png" => SheetFormat::Png {
+                    is_compressed: {
+                        #[cfg(feature = "compressed_png")]
+                        {
+                            !opt_list.get_opt("raw", false)?.0
+                        }
+                        #[cfg(not(feature = "compressed_png"))]
+                        {
+                            false
+                        }
+                    },
+                },
+                "svg" => SheetFormat::Svg,
+                input => Err(BinError::InvalidInput(format!(
+                    "Invalid argument: Invalid sheet format: '{}'\n\
+                     Use one of 'png', 'svg' here.",
+                    input
+                )))?,
+            },
+            SheetOptions {
+                // columns
+                columns: opt_list
+                    .get_opt("columns", true)?
+                    .1
+                    .map_or(Some(default_options.columns), |columns| {
+                        columns.parse().ok().filter(|columns| *columns > 0)
+                    })
+                    .ok_or(BinError::InvalidInput(
+                        "Invalid argument: Invalid number of columns\n\
+                         Enter positive integer."
+                            .to_owned(),
+                    ))?,
+                // dimension of each blockies (width, height)
+                icon_dimension: opt_list
+                    .get_opt("dimension", true)?
+                    .1
+                    .as_deref()
+                    .unwrap_or("64x64") // default
+                    .split_once('x')
+                    .and_then(|(w, h)| w.parse().ok().zip(h.parse().ok()))
+                    .ok_or(BinError::InvalidInput(
+                        "Invalid argument: Invalid blockies dimension\n\
+                         Type input in the form of '(width)x(height)'. (e.g. 64x64)"
+                            .to_owned(),
+                    ))?,
+                // spacing between blockies
+                spacing: opt_list
+                    .get_opt("gap", true)?
+                    .1
+                    .map_or(Some(default_options.spacing), |gap| gap.parse().ok())
+                    .ok_or(BinError::InvalidInput(
+                        "Invalid argument: Invalid gap\n\
+                         Enter non-negative integer."
+                            .to_owned(),
+                    ))?,
+                // background color
+                background: match opt_list.get_opt("background", true)?.1.as_deref() {
+                    None => default_options.background,
+                    Some("none") => None,
+                    Some(color) => Some(
+                        (color.len() == 6)
+                            .then(|| u32::from_str_radix(color, 16).ok())
+                            .flatten()
+                            .map(|rgb| ((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+                            .ok_or(BinError::InvalidInput(format!(
+                                "Invalid argument: Invalid background color: '{}'\n\
+                                 Type input in the form of 'rrggbb' (e.g. ffffff), or 'none'.",
+                                color
+                            )))?,
+                    ),
+                },
+                // labels
+                labels: opt_list.get_opt("labels", false)?.0,
+                // abbreviate only if all seeds are well-formed Ethereum address seeds
+                label_text: match seeds.iter().all(|seed| *seed == seed.to_ethaddr_seed()) {
+                    true => CaptionText::EthAddress,
+                    false => CaptionText::Seed,
+                },
+                ..default_options
+            },
+            // name of file out
+            opt_list.get_opt("outfile", true)?.1,
+        ))
+        // check if opt_list is empty. if not, abort parsing and return warning
+        .and_then(|ret| opt_list.check_if_empty().map(|_| ret))
+    }
+
+    let (sheet_format, options, file_out) = parse_opts(opt_list, seeds)?;
+
+    let sheet_data = match sheet_format {
+        SheetFormat::Png {
+            is_compressed: true,
+        } => {
+            #[cfg(feature = "compressed_png")]
+            {
+                const_generic_call_mapper::gen_comp_sheet_image(blockies_size, seeds, options)
+            }
+            #[cfg(not(feature = "compressed_png"))]
+            {
+                const_generic_call_mapper::gen_sheet_image(blockies_size, seeds, options)
+            }
+        }
+        SheetFormat::Png {
+            is_compressed: false,
+        } => const_generic_call_mapper::gen_sheet_image(blockies_size, seeds, options),
+        SheetFormat::Svg => {
+            const_generic_call_mapper::gen_sheet_svg(blockies_size, seeds, options).into_bytes()
         }
-        false => const_generic_call_mapper::gen_image(blockies_size, seed, dimension),
     };
 
     use io::Write;
     match file_out {
-        Some(file) => fs::File::create(file).and_then(|mut f| f.write_all(&img_png_data)),
-        None => io::stdout().write_all(&img_png_data),
+        Some(file) => fs::File::create(file).and_then(|mut f| f.write_all(&sheet_data)),
+        None => io::stdout().write_all(&sheet_data),
     }
     .map_err(|e| BinError::InvalidInput(e.to_string()))?;
 
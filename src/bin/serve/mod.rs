@@ -0,0 +1,468 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::arg_parser::CmdOpt;
+use crate::{
+    arg_parser::OptList,
+    bin_error::{BinError, BinResult},
+    const_generic_call_mapper::{self, MAX_BLOCKIES_SIZE, MIN_BLOCKIES_SIZE},
+};
+use eth_blockies::{Mask, PngOptions, RenderCache, RenderFormat, RenderOptions, SeedInput};
+
+// max length of request line and headers, in bytes
+const MAX_REQUEST_BYTES: u64 = 8192;
+// max width/height of output image
+const MAX_DIMENSION: usize = 1024;
+// max width/height of ico (stored as a byte, where 0 is 256)
+const MAX_ICO_DIMENSION: usize = 256;
+// max number of worker threads
+const MAX_THREADS: usize = 256;
+// time limit to receive the whole request line and headers, not for each read.
+// each worker serves one connection at a time, so slow clients (slowloris) can hold
+// all workers for this long at most: put a reverse proxy in front if exposed publicly
+const HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+// output of the same url never changes
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+// avatar image formats
+#[derive(Clone, Copy)]
+enum AvatarFormat {
+    Png,
+    Svg,
+    Ico,
+}
+
+impl AvatarFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            AvatarFormat::Png => "image/png",
+            AvatarFormat::Svg => "image/svg+xml",
+            AvatarFormat::Ico => "image/x-icon",
+        }
+    }
+}
+
+// parsed avatar request: GET /<seed>.<png|svg|ico>?size=8&dim=128&transparent=1&ethseed=1
+struct AvatarRequest {
+    seed: Vec<u8>,
+    format: AvatarFormat,
+    blockies_size: usize,
+    dimension: (usize, usize),
+    is_transparent: bool,
+}
+
+// http response, without body on HEAD request or 304
+struct Response {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Arc<[u8]>,
+}
+
+// serve blockies avatar images over http, until an error occurs on listening
+pub fn serve(args: &[String], opt_list: OptList) -> BinResult<()> {
+    fn parse_opts(opt_list: OptList) -> BinResult<(String, usize, usize)> {
+        let mut opt_list = opt_list.clone();
+        // ( listen-address, # of threads, cache size in MiB )
+        Ok((
+            // listen address
+            opt_list
+                .get_opt("listen", true)?
+                .1
+                .unwrap_or("127.0.0.1:8080".to_owned()), // default
+            // # of threads
+            opt_list
+                .get_opt("threads", true)?
+                .1
+                .map_or(
+                    Some(
+                        thread::available_parallelism()
+                            .map_or(4, usize::from)
+                            .min(MAX_THREADS),
+                    ), // default
+                    |threads| {
+                        threads
+                            .parse()
+                            .ok()
+                            .filter(|threads| (1..=MAX_THREADS).contains(threads))
+                    },
+                )
+                .ok_or(BinError::InvalidInput(format!(
+                    "Invalid argument: Invalid number of threads\n\
+                     Enter positive integer (up to {}).",
+                    MAX_THREADS
+                )))?,
+            // cache size
+            opt_list
+                .get_opt("cache", true)?
+                .1
+                .map_or(Some(64), |cache| cache.parse().ok()) // default
+                .ok_or(BinError::InvalidInput(
+                    "Invalid argument: Invalid cache size\n\
+                     Enter non-negative integer (in MiB)."
+                        .to_owned(),
+                ))?,
+        ))
+        // check if opt_list is empty. if not, abort parsing and return warning
+        .and_then(|ret| opt_list.check_if_empty().map(|_| ret))
+    }
+
+    if let Some(arg) = args.first() {
+        return Err(BinError::InvalidInput(format!(
+            "Invalid argument: '{}'",
+            arg
+        )));
+    }
+    let (listen_addr, threads, cache_mib) = parse_opts(opt_list)?;
+
+    let listener = TcpListener::bind(&listen_addr).map_err(|e| {
+        BinError::InvalidInput(format!("Cannot listen on '{}': {}", listen_addr, e))
+    })?;
+    let cache = Arc::new(RenderCache::new(cache_mib.saturating_mul(1024 * 1024)));
+
+    let local_addr = listener
+        .local_addr()
+        .map_err(|e| BinError::InvalidInput(e.to_string()))?;
+    eprintln!("Listening on http://{}", local_addr);
+
+    // each worker accepts connections from the shared listener
+    let workers = (0..threads)
+        .map(|_| {
+            let listener = listener.try_clone()?;
+            let cache = cache.clone();
+            Ok(thread::spawn(move || -> io::Error {
+                loop {
+                    match listener.accept() {
+                        // errors on a connection only close the connection
+                        Ok((stream, _)) => {
+                            let _ = handle_connection(stream, &cache);
+                        }
+                        Err(e) => return e,
+                    }
+                }
+            }))
+        })
+        .collect::<io::Result<Vec<_>>>()
+        .map_err(|e| BinError::InvalidInput(e.to_string()))?;
+
+    // workers return only on listener error
+    let errors: Vec<String> = workers
+        .into_iter()
+        .map(|worker| match worker.join() {
+            Ok(e) => e.to_string(),
+            Err(_) => "Worker thread panicked".to_owned(),
+        })
+        .collect();
+    Err(BinError::InvalidInput(errors.join("\n")))
+}
+
+// stream which fails to read after the deadline,
+// not to be held by a client sending a request slowly
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self
+            .deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::TimedOut, "Request timed out"))?;
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+// handle a single request on the connection, then close it
+fn handle_connection(stream: TcpStream, cache: &RenderCache) -> io::Result<()> {
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    // read request line and headers, up to the limit, within the time limit
+    let mut reader = BufReader::new(
+        DeadlineReader {
+            stream: stream.try_clone()?,
+            deadline: Instant::now() + HEADER_TIMEOUT,
+        }
+        .take(MAX_REQUEST_BYTES),
+    );
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut if_none_match = None;
+    let mut is_complete = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            is_complete = true;
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("if-none-match") {
+                if_none_match = Some(value.trim().to_owned());
+            }
+        }
+    }
+
+    let mut request_line = request_line.split_whitespace();
+    let (method, target) = (request_line.next(), request_line.next());
+    let response = match (is_complete, method, target) {
+        (true, Some("GET"), Some(target)) | (true, Some("HEAD"), Some(target)) => {
+            match parse_request(target) {
+                Ok(request) => avatar_response(&request, cache, if_none_match.as_deref()),
+                Err((status, msg)) => error_response(status, msg),
+            }
+        }
+        (true, Some(_), Some(_)) => {
+            let mut response = error_response("405 Method Not Allowed", "Use GET or HEAD.".into());
+            response.headers.push(("Allow", "GET, HEAD".to_owned()));
+            response
+        }
+        _ => error_response("400 Bad Request", "Invalid request.".into()),
+    };
+
+    write_response(stream, response, method == Some("HEAD"))
+}
+
+// parse request target: /<seed>.<png|svg|ico>?<query>
+fn parse_request(target: &str) -> Result<AvatarRequest, (&'static str, String)> {
+    const BAD_REQUEST: &str = "400 Bad Request";
+    const NOT_FOUND: &str = "404 Not Found";
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let (seed, ext) = path
+        .strip_prefix('/')
+        .and_then(|path| path.rsplit_once('.'))
+        .filter(|(seed, _)| !seed.is_empty() && !seed.contains('/'))
+        .ok_or((NOT_FOUND, "Request '/<seed>.<png|svg|ico>'.".to_owned()))?;
+    let format = match ext {
+        "png" => AvatarFormat::Png,
+        "svg" => AvatarFormat::Svg,
+        "ico" => AvatarFormat::Ico,
+        ext => Err((NOT_FOUND, format!("Unknown format: '{}'", ext)))?,
+    };
+    let seed = percent_decode(seed).ok_or((BAD_REQUEST, "Invalid seed encoding.".to_owned()))?;
+
+    let mut request = AvatarRequest {
+        seed,
+        format,
+        blockies_size: 8,
+        dimension: (128, 128),
+        is_transparent: false,
+    };
+    let mut is_ethseed = false;
+    for param in query.split('&').filter(|param| !param.is_empty()) {
+        let (key, value) = param.split_once('=').unwrap_or((param, ""));
+        let invalid = || (BAD_REQUEST, format!("Invalid parameter: '{}'", param));
+        let parse_flag = |value: &str| match value {
+            "0" | "false" => Ok(false),
+            "" | "1" | "true" => Ok(true),
+            _ => Err(invalid()),
+        };
+        match key {
+            "size" => {
+                request.blockies_size = value
+                    .parse()
+                    .ok()
+                    .filter(|size| (MIN_BLOCKIES_SIZE..=MAX_BLOCKIES_SIZE).contains(size))
+                    .ok_or_else(invalid)?
+            }
+            // 'dim=128' for 128x128, or 'dim=128x64'
+            "dim" => {
+                request.dimension = value
+                    .split_once('x')
+                    .map_or(Some((value, value)), Some)
+                    .and_then(|(w, h)| w.parse().ok().zip(h.parse().ok()))
+                    .filter(|(w, h)| {
+                        (1..=MAX_DIMENSION).contains(w) && (1..=MAX_DIMENSION).contains(h)
+                    })
+                    .ok_or_else(invalid)?
+            }
+            "transparent" => request.is_transparent = parse_flag(value)?,
+            "ethseed" => is_ethseed = parse_flag(value)?,
+            _ => Err(invalid())?,
+        }
+    }
+
+    if is_ethseed {
+        request.seed = request.seed.to_ethaddr_seed().to_vec();
+    }
+    if let AvatarFormat::Ico = request.format {
+        if request.dimension.0 > MAX_ICO_DIMENSION || request.dimension.1 > MAX_ICO_DIMENSION {
+            Err((
+                BAD_REQUEST,
+                format!(
+                    "Dimension of ico must be {0}x{0} or less.",
+                    MAX_ICO_DIMENSION
+                ),
+            ))?
+        }
+    }
+
+    Ok(request)
+}
+
+fn avatar_response(
+    request: &AvatarRequest,
+    cache: &RenderCache,
+    if_none_match: Option<&str>,
+) -> Response {
+    let render_options = RenderOptions {
+        mask: match request.is_transparent {
+            true => Mask::Circle,
+            false => Mask::None,
+        },
+        ..Default::default()
+    };
+    let png_options = PngOptions {
+        render: render_options,
+        ..Default::default()
+    };
+    #[cfg(feature = "compressed_png")]
+    let png_format = RenderFormat::CompressedPng(png_options);
+    #[cfg(not(feature = "compressed_png"))]
+    let png_format = RenderFormat::Png(png_options);
+
+    let cached_data = |format| {
+        const_generic_call_mapper::gen_cached(
+            cache,
+            request.blockies_size,
+            &request.seed,
+            request.dimension,
+            format,
+        )
+    };
+    let body: Option<Arc<[u8]>> = match request.format {
+        AvatarFormat::Png => cached_data(png_format),
+        AvatarFormat::Svg => cached_data(RenderFormat::Svg(render_options)),
+        AvatarFormat::Ico => {
+            cached_data(png_format).map(|png| png_to_ico(&png, request.dimension).into())
+        }
+    };
+    // not expected, as png and svg are encoded for any dimension
+    let body = match body {
+        Some(body) => body,
+        None => {
+            return error_response(
+                "500 Internal Server Error",
+                "Failed to encode image.".into(),
+            )
+        }
+    };
+
+    let etag = format!("\"{:016x}-{:x}\"", fnv1a_hash(&body), body.len());
+    let headers = vec![
+        ("Content-Type", request.format.content_type().to_owned()),
+        ("Cache-Control", CACHE_CONTROL.to_owned()),
+        ("ETag", etag.clone()),
+    ];
+
+    match if_none_match.map_or(false, |tags| etag_matches(tags, &etag)) {
+        true => Response {
+            status: "304 Not Modified",
+            headers,
+            body: Arc::new([]),
+        },
+        false => Response {
+            status: "200 OK",
+            headers,
+            body,
+        },
+    }
+}
+
+fn error_response(status: &'static str, msg: String) -> Response {
+    Response {
+        status,
+        headers: vec![("Content-Type", "text/plain; charset=utf-8".to_owned())],
+        body: format!("{}\n", msg).into_bytes().into(),
+    }
+}
+
+fn write_response(mut stream: TcpStream, response: Response, is_head: bool) -> io::Result<()> {
+    let mut head = format!("HTTP/1.1 {}\r\n", response.status);
+    response
+        .headers
+        .iter()
+        .for_each(|(name, value)| head.push_str(&format!("{}: {}\r\n", name, value)));
+    // no Content-Length on 304, as it would be of the (omitted) cached body
+    if !response.status.starts_with("304") {
+        head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+    }
+    head.push_str("Connection: close\r\n\r\n");
+
+    stream.write_all(head.as_bytes())?;
+    if !is_head {
+        stream.write_all(&response.body)?;
+    }
+    stream.flush()
+}
+
+// check if any entity tag in If-None-Match header matches (weak comparison)
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+// decode percent-encoded string to bytes
+fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = input.bytes();
+    let mut decoded = Vec::with_capacity(input.len());
+    while let Some(byte) = bytes.next() {
+        decoded.push(match byte {
+            b'%' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?
+            }
+            byte => byte,
+        });
+    }
+    Some(decoded)
+}
+
+// stable hash of data for entity tag (FNV-1a, 64-bit)
+fn fnv1a_hash(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+// wrap png data in ico format (single image, png-compressed)
+fn png_to_ico(png: &[u8], dimension: (usize, usize)) -> Vec<u8> {
+    const HEADER_BYTES: u32 = 6 + 16;
+
+    // width/height of 256 is stored as 0
+    let (width, height) = (dimension.0 as u8, dimension.1 as u8);
+    // bit depth * # of channels (by color type) in IHDR of png, or 0 if unknown
+    let bits_per_pixel = match png.get(24..26) {
+        Some([bit_depth, color_type]) => match color_type {
+            0 | 3 => *bit_depth, // grayscale, indexed
+            2 => bit_depth * 3,  // rgb
+            4 => bit_depth * 2,  // grayscale with alpha
+            6 => bit_depth * 4,  // rgba
+            _ => 0,
+        },
+        _ => 0,
+    };
+    let mut ico = Vec::with_capacity(HEADER_BYTES as usize + png.len());
+    // ICONDIR: reserved, type (1: icon), # of images
+    ico.extend_from_slice(&[0, 0, 1, 0, 1, 0]);
+    // ICONDIRENTRY: width, height, # of palette colors, reserved, color planes, bits per pixel
+    ico.extend_from_slice(&[width, height, 0, 0, 1, 0, bits_per_pixel, 0]);
+    // ICONDIRENTRY: size of image data, offset of image data
+    ico.extend_from_slice(&(png.len() as u32).to_le_bytes());
+    ico.extend_from_slice(&HEADER_BYTES.to_le_bytes());
+    ico.extend_from_slice(png);
+    ico
+}
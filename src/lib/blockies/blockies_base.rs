@@ -59,6 +59,7 @@ pub trait BlockiesHelper<const S: usize, T: Clone> {
     ///     [ (0,7), (1,7), (2,7), (3,7), (4,7), (5,7), (6,7), (7,7) ],
     /// ]);
     /// ```
+    #[allow(clippy::new_ret_no_self)]
     fn new<F>(fn_init: F) -> Blockies<S, T>
     where
         F: FnMut((usize, usize)) -> T;
@@ -0,0 +1,89 @@
+use super::RenderedImage;
+use crate::global_type_helper::RgbaPixel;
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+// Rgba canvas to compose multiple images into a single image
+pub struct Canvas {
+    pixels: Vec<Vec<RgbaPixel>>,
+}
+
+impl Canvas {
+    pub fn new(dimension: (usize, usize), color: RgbaPixel) -> Self {
+        Canvas {
+            pixels: vec![vec![color; dimension.0]; dimension.1],
+        }
+    }
+
+    // fill the rectangle, clipped by the canvas
+    pub fn fill_rect(
+        &mut self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+        color: RgbaPixel,
+    ) {
+        self.pixels.iter_mut().skip(y).take(height).for_each(|row| {
+            row.iter_mut()
+                .skip(x)
+                .take(width)
+                .for_each(|pixel| *pixel = color)
+        });
+    }
+
+    // draw the image with its top-left corner on the offset, clipped by the canvas
+    pub fn draw_image(&mut self, (x, y): (usize, usize), image: &[Vec<RgbaPixel>]) {
+        self.pixels
+            .iter_mut()
+            .skip(y)
+            .zip(image.iter())
+            .for_each(|(row, image_row)| {
+                row.iter_mut()
+                    .skip(x)
+                    .zip(image_row.iter())
+                    .for_each(|(pixel, image_pixel)| *pixel = *image_pixel)
+            });
+    }
+
+    // draw the text with the built-in font, with each font pixel scaled to (scale x scale)
+    pub fn draw_text(
+        &mut self,
+        (x, y): (usize, usize),
+        text_pixels: &[(usize, usize)],
+        scale: usize,
+        color: RgbaPixel,
+    ) {
+        text_pixels.iter().for_each(|(font_x, font_y)| {
+            self.fill_rect(
+                (x + font_x * scale, y + font_y * scale),
+                (scale, scale),
+                color,
+            )
+        });
+    }
+
+    // indexed image if 256 or less colors are used, rgba image otherwise
+    pub fn into_rendered_image(self) -> RenderedImage {
+        const MAX_INDEXED_COLORS: usize = 1 << u8::BITS;
+
+        let mut color_indices: BTreeMap<RgbaPixel, u8> = BTreeMap::new();
+        let mut palette: Vec<RgbaPixel> = Vec::new();
+        for pixel in self.pixels.iter().flatten() {
+            if !color_indices.contains_key(pixel) {
+                if palette.len() == MAX_INDEXED_COLORS {
+                    return RenderedImage::Rgba(self.pixels);
+                }
+                color_indices.insert(*pixel, palette.len() as u8);
+                palette.push(*pixel);
+            }
+        }
+
+        let bitmap = self
+            .pixels
+            .iter()
+            .map(|row| row.iter().map(|pixel| color_indices[pixel]).collect())
+            .collect();
+        RenderedImage::Indexed(palette, bitmap)
+    }
+}
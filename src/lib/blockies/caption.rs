@@ -0,0 +1,170 @@
+use super::{canvas::Canvas, font, render, render::rendered_svg_element, Blockies, RenderedImage};
+use crate::global_type_helper::{
+    Caption, CaptionText, ColorClass, RenderOptions, RgbPalette, SeedInput,
+};
+use crate::indexed_svg;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Position of each element in a captioned image
+struct CaptionLayout {
+    // dimension of blockies above the caption
+    icon_dim: (usize, usize),
+    // offset of the caption band
+    band_offset: (usize, usize),
+    // offset of the caption text
+    text_offset: (usize, usize),
+    // foreground pixels of the caption text, in font pixels
+    text_pixels: Vec<(usize, usize)>,
+    scale: usize,
+}
+
+// caption band: text with 1 font pixel margin above and below
+fn caption_layout(caption: Caption, seed: &[u8], output_dim: (usize, usize)) -> CaptionLayout {
+    let scale = caption.scale.max(1);
+    let band_height = (font::GLYPH_HEIGHT + 2) * scale;
+    let icon_dim = (output_dim.0, output_dim.1.saturating_sub(band_height));
+
+    let (text_pixels, text_width) =
+        font::text_pixels(&caption_text(caption.text, seed), output_dim.0 / scale);
+
+    CaptionLayout {
+        icon_dim,
+        band_offset: (0, icon_dim.1),
+        text_offset: (
+            output_dim.0.saturating_sub(text_width * scale) / 2,
+            icon_dim.1 + scale,
+        ),
+        text_pixels,
+        scale,
+    }
+}
+
+// text of caption or label for the seed.
+// seeds other than an Ethereum address are not abbreviated, but drawn as is
+pub fn caption_text(text: CaptionText, seed: &[u8]) -> String {
+    match text {
+        CaptionText::EthAddress => seed
+            .to_abbreviated_ethaddr()
+            .unwrap_or_else(|| seed_text(seed)),
+        CaptionText::Seed => seed_text(seed),
+    }
+}
+
+// text of the seed: seed itself if utf-8, hex string otherwise
+fn seed_text(seed: &[u8]) -> String {
+    const HEX_TABLE: &[u8; 16] = b"0123456789abcdef";
+
+    match core::str::from_utf8(seed) {
+        Ok(seed_str) => String::from(seed_str),
+        Err(_) => seed
+            .iter()
+            .flat_map(|byte| {
+                [
+                    HEX_TABLE[(byte >> 4) as usize] as char,
+                    HEX_TABLE[(byte & 0xf) as usize] as char,
+                ]
+            })
+            .collect(),
+    }
+}
+
+// Render blockies with the caption under it.
+// blockies is rendered with the options except the caption, above the caption band
+pub fn render_captioned<const S: usize>(
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    output_dim: (usize, usize),
+    options: RenderOptions,
+    caption: Caption,
+    seed: &[u8],
+) -> RenderedImage {
+    const TRANSPARENT: (u8, u8, u8, u8) = (0, 0, 0, 0);
+    const OPAQUE: u8 = 255;
+
+    let layout = caption_layout(caption, seed, output_dim);
+    let (text_r, text_g, text_b) = caption.color;
+
+    let mut canvas = Canvas::new(output_dim, TRANSPARENT);
+    canvas.draw_image(
+        (0, 0),
+        &render(
+            palette,
+            bitmap,
+            layout.icon_dim,
+            RenderOptions {
+                caption: None,
+                ..options
+            },
+            seed,
+        )
+        .into_rgba(),
+    );
+    if let Some((r, g, b)) = caption.background {
+        canvas.fill_rect(
+            layout.band_offset,
+            (output_dim.0, output_dim.1 - layout.band_offset.1),
+            (r, g, b, OPAQUE),
+        );
+    }
+    canvas.draw_text(
+        layout.text_offset,
+        &layout.text_pixels,
+        layout.scale,
+        (text_r, text_g, text_b, OPAQUE),
+    );
+
+    canvas.into_rendered_image()
+}
+
+// Generate svg data of blockies with the caption under it.
+// blockies is rendered with the options except the caption, above the caption band
+pub fn captioned_svg<const S: usize>(
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    output_dim: (usize, usize),
+    options: RenderOptions,
+    caption: Caption,
+    seed: &[u8],
+) -> String {
+    let layout = caption_layout(caption, seed, output_dim);
+
+    let text_runs: Vec<(usize, usize, usize)> = layout
+        .text_pixels
+        .iter()
+        .map(|(x, y)| (*y, *x, 1))
+        .collect();
+
+    let elements: String = [
+        rendered_svg_element(
+            palette,
+            bitmap,
+            layout.icon_dim,
+            RenderOptions {
+                caption: None,
+                ..options
+            },
+            seed,
+        ),
+        caption.background.map_or(String::new(), |color| {
+            indexed_svg::rect_element(
+                layout.band_offset,
+                (output_dim.0, output_dim.1 - layout.band_offset.1),
+                color,
+            )
+        }),
+        match text_runs.is_empty() {
+            true => String::new(),
+            false => indexed_svg::path_element(
+                &text_runs,
+                layout.text_offset,
+                layout.scale,
+                caption.color,
+            ),
+        },
+    ]
+    .concat();
+
+    indexed_svg::svg_document(output_dim, &elements)
+}
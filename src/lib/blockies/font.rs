@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+
+// Tiny built-in bitmap font, for drawing labels without any font files.
+// each glyph is 3x5 pixels, and each row is 3 bits (MSB on the left)
+pub const GLYPH_WIDTH: usize = 3;
+pub const GLYPH_HEIGHT: usize = 5;
+// glyph width + 1 pixel spacing
+pub const GLYPH_ADVANCE: usize = GLYPH_WIDTH + 1;
+
+const ELLIPSIS: char = '\u{2026}';
+const ELLIPSIS_GLYPH: [u8; GLYPH_HEIGHT] = [0b000, 0b000, 0b000, 0b000, 0b101];
+
+// glyphs of printable ascii characters (' ' - '~')
+const ASCII_GLYPHS: [[u8; GLYPH_HEIGHT]; 95] = [
+    [0b000, 0b000, 0b000, 0b000, 0b000], // ' '
+    [0b010, 0b010, 0b010, 0b000, 0b010], // '!'
+    [0b101, 0b101, 0b000, 0b000, 0b000], // '"'
+    [0b101, 0b111, 0b101, 0b111, 0b101], // '#'
+    [0b011, 0b110, 0b010, 0b011, 0b110], // '$'
+    [0b101, 0b001, 0b010, 0b100, 0b101], // '%'
+    [0b010, 0b101, 0b010, 0b101, 0b011], // '&'
+    [0b010, 0b010, 0b000, 0b000, 0b000], // '''
+    [0b001, 0b010, 0b010, 0b010, 0b001], // '('
+    [0b100, 0b010, 0b010, 0b010, 0b100], // ')'
+    [0b000, 0b101, 0b010, 0b101, 0b000], // '*'
+    [0b000, 0b010, 0b111, 0b010, 0b000], // '+'
+    [0b000, 0b000, 0b000, 0b010, 0b100], // ','
+    [0b000, 0b000, 0b111, 0b000, 0b000], // '-'
+    [0b000, 0b000, 0b000, 0b000, 0b010], // '.'
+    [0b001, 0b001, 0b010, 0b100, 0b100], // '/'
+    [0b111, 0b101, 0b101, 0b101, 0b111], // '0'
+    [0b010, 0b110, 0b010, 0b010, 0b111], // '1'
+    [0b110, 0b001, 0b010, 0b100, 0b111], // '2'
+    [0b110, 0b001, 0b010, 0b001, 0b110], // '3'
+    [0b101, 0b101, 0b111, 0b001, 0b001], // '4'
+    [0b111, 0b100, 0b110, 0b001, 0b110], // '5'
+    [0b011, 0b100, 0b111, 0b101, 0b111], // '6'
+    [0b111, 0b001, 0b010, 0b010, 0b010], // '7'
+    [0b111, 0b101, 0b111, 0b101, 0b111], // '8'
+    [0b111, 0b101, 0b111, 0b001, 0b110], // '9'
+    [0b000, 0b010, 0b000, 0b010, 0b000], // ':'
+    [0b000, 0b010, 0b000, 0b010, 0b100], // ';'
+    [0b001, 0b010, 0b100, 0b010, 0b001], // '<'
+    [0b000, 0b111, 0b000, 0b111, 0b000], // '='
+    [0b100, 0b010, 0b001, 0b010, 0b100], // '>'
+    [0b110, 0b001, 0b010, 0b000, 0b010], // '?'
+    [0b010, 0b101, 0b111, 0b100, 0b011], // '@'
+    [0b010, 0b101, 0b111, 0b101, 0b101], // 'A'
+    [0b110, 0b101, 0b110, 0b101, 0b110], // 'B'
+    [0b011, 0b100, 0b100, 0b100, 0b011], // 'C'
+    [0b110, 0b101, 0b101, 0b101, 0b110], // 'D'
+    [0b111, 0b100, 0b110, 0b100, 0b111], // 'E'
+    [0b111, 0b100, 0b110, 0b100, 0b100], // 'F'
+    [0b011, 0b100, 0b101, 0b101, 0b011], // 'G'
+    [0b101, 0b101, 0b111, 0b101, 0b101], // 'H'
+    [0b111, 0b010, 0b010, 0b010, 0b111], // 'I'
+    [0b001, 0b001, 0b001, 0b101, 0b010], // 'J'
+    [0b101, 0b101, 0b110, 0b101, 0b101], // 'K'
+    [0b100, 0b100, 0b100, 0b100, 0b111], // 'L'
+    [0b101, 0b111, 0b111, 0b101, 0b101], // 'M'
+    [0b111, 0b101, 0b101, 0b101, 0b101], // 'N'
+    [0b010, 0b101, 0b101, 0b101, 0b010], // 'O'
+    [0b110, 0b101, 0b110, 0b100, 0b100], // 'P'
+    [0b010, 0b101, 0b101, 0b110, 0b011], // 'Q'
+    [0b110, 0b101, 0b110, 0b101, 0b101], // 'R'
+    [0b011, 0b100, 0b010, 0b001, 0b110], // 'S'
+    [0b111, 0b010, 0b010, 0b010, 0b010], // 'T'
+    [0b101, 0b101, 0b101, 0b101, 0b111], // 'U'
+    [0b101, 0b101, 0b101, 0b101, 0b010], // 'V'
+    [0b101, 0b101, 0b111, 0b111, 0b101], // 'W'
+    [0b101, 0b101, 0b010, 0b101, 0b101], // 'X'
+    [0b101, 0b101, 0b010, 0b010, 0b010], // 'Y'
+    [0b111, 0b001, 0b010, 0b100, 0b111], // 'Z'
+    [0b011, 0b010, 0b010, 0b010, 0b011], // '['
+    [0b100, 0b100, 0b010, 0b001, 0b001], // '\'
+    [0b110, 0b010, 0b010, 0b010, 0b110], // ']'
+    [0b010, 0b101, 0b000, 0b000, 0b000], // '^'
+    [0b000, 0b000, 0b000, 0b000, 0b111], // '_'
+    [0b100, 0b010, 0b000, 0b000, 0b000], // '`'
+    [0b000, 0b011, 0b101, 0b101, 0b011], // 'a'
+    [0b100, 0b110, 0b101, 0b101, 0b110], // 'b'
+    [0b000, 0b011, 0b100, 0b100, 0b011], // 'c'
+    [0b001, 0b011, 0b101, 0b101, 0b011], // 'd'
+    [0b000, 0b010, 0b101, 0b110, 0b011], // 'e'
+    [0b001, 0b010, 0b111, 0b010, 0b010], // 'f'
+    [0b000, 0b011, 0b101, 0b011, 0b110], // 'g'
+    [0b100, 0b110, 0b101, 0b101, 0b101], // 'h'
+    [0b010, 0b000, 0b010, 0b010, 0b010], // 'i'
+    [0b001, 0b000, 0b001, 0b101, 0b010], // 'j'
+    [0b100, 0b101, 0b110, 0b110, 0b101], // 'k'
+    [0b110, 0b010, 0b010, 0b010, 0b111], // 'l'
+    [0b000, 0b110, 0b111, 0b101, 0b101], // 'm'
+    [0b000, 0b110, 0b101, 0b101, 0b101], // 'n'
+    [0b000, 0b010, 0b101, 0b101, 0b010], // 'o'
+    [0b000, 0b110, 0b101, 0b110, 0b100], // 'p'
+    [0b000, 0b011, 0b101, 0b011, 0b001], // 'q'
+    [0b000, 0b011, 0b100, 0b100, 0b100], // 'r'
+    [0b000, 0b011, 0b110, 0b011, 0b110], // 's'
+    [0b010, 0b111, 0b010, 0b010, 0b011], // 't'
+    [0b000, 0b101, 0b101, 0b101, 0b011], // 'u'
+    [0b000, 0b101, 0b101, 0b101, 0b010], // 'v'
+    [0b000, 0b101, 0b101, 0b111, 0b101], // 'w'
+    [0b000, 0b101, 0b010, 0b010, 0b101], // 'x'
+    [0b000, 0b101, 0b011, 0b001, 0b110], // 'y'
+    [0b000, 0b111, 0b011, 0b110, 0b111], // 'z'
+    [0b011, 0b010, 0b110, 0b010, 0b011], // '{'
+    [0b010, 0b010, 0b010, 0b010, 0b010], // '|'
+    [0b110, 0b010, 0b011, 0b010, 0b110], // '}'
+    [0b000, 0b011, 0b110, 0b000, 0b000], // '~'
+];
+
+// glyph of the character ('?' if not supported)
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c {
+        ' '..='~' => ASCII_GLYPHS[c as usize - ' ' as usize],
+        ELLIPSIS => ELLIPSIS_GLYPH,
+        _ => ASCII_GLYPHS['?' as usize - ' ' as usize],
+    }
+}
+
+// width of the text in font pixels
+pub fn text_width(char_count: usize) -> usize {
+    (char_count * GLYPH_ADVANCE).saturating_sub(1)
+}
+
+// Get coordinates of foreground pixels of the text, and the text width, in font pixels.
+// if the text is wider than max_width, the end of the text is replaced with an ellipsis
+pub fn text_pixels(text: &str, max_width: usize) -> (Vec<(usize, usize)>, usize) {
+    let max_chars = (max_width + 1) / GLYPH_ADVANCE;
+    let chars: Vec<char> = match text.chars().count() > max_chars {
+        true => text
+            .chars()
+            .take(max_chars.saturating_sub(1))
+            .chain(core::iter::once(ELLIPSIS))
+            .take(max_chars)
+            .collect(),
+        false => text.chars().collect(),
+    };
+
+    let pixels = chars
+        .iter()
+        .enumerate()
+        .flat_map(|(char_idx, c)| {
+            glyph(*c).into_iter().enumerate().flat_map(move |(y, row)| {
+                (0..GLYPH_WIDTH)
+                    .filter(move |x| row & (1 << (GLYPH_WIDTH - 1 - x)) != 0)
+                    .map(move |x| (char_idx * GLYPH_ADVANCE + x, y))
+            })
+        })
+        .collect();
+
+    (pixels, text_width(chars.len()))
+}
@@ -1,6 +1,45 @@
-use crate::global_type_helper::{ColorClass, RgbPixel};
+use crate::global_type_helper::{ColorClass, RgbPixel, SeedInput};
 
-/// Ethereum blockies generator, which stores necessary seeds for creating blockies
+/// Deterministic pseudo-random generator of ethereum blockies
+///
+/// This is the exact equivalent of `seedrand()` and `rand()`
+/// in the JS implementation ([`ethereum-blockies`](https://github.com/ethereum/blockies)),
+/// from which the palette and the bitmap of blockies are generated.  
+/// Use it to derive extra deterministic attributes, consistent with the blockies of the same seed.
+///
+/// # Example
+///
+/// * Keys, the same as `rand()` of the JS implementation
+///   ```
+///   use eth_blockies::*;
+///
+///   let mut keygen = KeyGenerator::new("0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc");
+///   assert_eq!(keygen.next_key(), 0.27121658017858863);
+///   assert_eq!(keygen.next_key(), 0.47638651076704264);
+///
+///   // as an iterator
+///   let keys: Vec<f64> = keygen.take(2).collect();
+///   assert_eq!(keys, [0.9251977484673262, 0.5804053316824138]);
+///   ```
+///
+/// * Colors and color classes, the same as the palette and the bitmap of the blockies
+///   ```
+///   use eth_blockies::*;
+///
+///   let seed = "0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc";
+///   let mut keygen = KeyGenerator::new(seed);
+///   let (palette, bitmap) = EthBlockies::indexed_data(seed);
+///
+///   let (color, bgcolor, spotcolor) = (keygen.next_rgb(), keygen.next_rgb(), keygen.next_rgb());
+///   assert_eq!(palette, [bgcolor, color, spotcolor]);
+///
+///   let first_row: Vec<ColorClass> = (0..4).map(|_| keygen.next_colorclass()).collect();
+///   assert_eq!(first_row, bitmap[0][..4]);
+///
+///   // extra attribute derived after the blockies: accent color
+///   let accent: RgbPixel = keygen.next_rgb();
+///   ```
+#[derive(Clone, Debug)]
 pub struct KeyGenerator {
     /// Seeds for generating ethereum blockies  
     /// (Named as "randseed" in original implementation)
@@ -12,23 +51,12 @@ pub struct KeyGenerator {
 impl KeyGenerator {
     const KEY_SEEDS_LEN: usize = 4;
 
-    /// Initialize new ethereum blockies generator using a given seed byte sequences
-    pub fn new(seed: &[u8]) -> Self {
-        Self {
-            key_seeds: (seed.chunks(KeyGenerator::KEY_SEEDS_LEN).fold(
-                [0_i32; KeyGenerator::KEY_SEEDS_LEN],
-                |mut key_seeds_acc, seed_chunks| {
-                    key_seeds_acc.iter_mut().zip(seed_chunks.iter()).for_each(
-                        |(key_seed_cur, seed_char_cur)| {
-                            *key_seed_cur = Self::key_seed_init(*key_seed_cur, *seed_char_cur)
-                        },
-                    );
-
-                    key_seeds_acc
-                },
-            )),
-            key_seed_curidx: 0,
-        }
+    /// Initialize new ethereum blockies generator using a given seed
+    pub fn new<I: SeedInput>(seed: I) -> Self {
+        let mut hasher = SeedHasher::new();
+        hasher.update(&seed.to_seed_bytes());
+
+        Self::from(&hasher)
     }
 
     /// Get next RGB pixel for palette using key_seeds
@@ -40,12 +68,12 @@ impl KeyGenerator {
                     _ => t % 360,
                 };
 
-                return match t {
+                match t {
                     0..=60 => p + (q - p) * t as f64 / 60_f64,
-                    0..=180 => q,
-                    0..=240 => p + (q - p) * (4_f64 - t as f64 / 60_f64),
+                    61..=180 => q,
+                    181..=240 => p + (q - p) * (4_f64 - t as f64 / 60_f64),
                     _ => p,
-                };
+                }
             }
 
             let rgb_frac = match saturation == 0_f64 {
@@ -83,15 +111,6 @@ impl KeyGenerator {
         ((self.next_key() * 2.3_f64) as u8).try_into().unwrap()
     }
 
-    // Update single element in key_seeds for initialization
-    fn key_seed_init(key_seed_cur: i32, seed_char_cur: u8) -> i32 {
-        (key_seed_cur << 5)
-            .overflowing_sub(key_seed_cur)
-            .0
-            .overflowing_add(seed_char_cur as i32)
-            .0
-    }
-
     // Get previous index of key_seeds
     fn idx_prev(idx: usize) -> usize {
         idx.overflowing_sub(1).0 % KeyGenerator::KEY_SEEDS_LEN
@@ -102,9 +121,10 @@ impl KeyGenerator {
         idx.overflowing_add(1).0 % KeyGenerator::KEY_SEEDS_LEN
     }
 
-    // Get next computed key using key_seeds, which is used for blockies generation
-    // Returns f64 in range: [0, 1]
-    fn next_key(&mut self) -> f64 {
+    /// Get next computed key using key_seeds, which is used for blockies generation
+    ///
+    /// Returns f64 in range: [0, 1)
+    pub fn next_key(&mut self) -> f64 {
         self.key_seeds
             .get(Self::idx_prev(self.key_seed_curidx))
             .zip(self.key_seeds.get(self.key_seed_curidx))
@@ -131,3 +151,129 @@ impl KeyGenerator {
             .expect("next_key")
     }
 }
+
+impl Iterator for KeyGenerator {
+    type Item = f64;
+
+    /// Get next key, the same as [`next_key()`](KeyGenerator::next_key) (never returns `None`)
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_key())
+    }
+}
+
+impl From<&SeedHasher> for KeyGenerator {
+    /// Initialize new ethereum blockies generator using all data absorbed in the hasher
+    fn from(hasher: &SeedHasher) -> Self {
+        Self {
+            key_seeds: hasher.key_seeds,
+            key_seed_curidx: 0,
+        }
+    }
+}
+
+/// Incremental hasher of a seed, to absorb long or non-contiguous seeds without buffering them
+///
+/// Absorbing data with [`update()`](SeedHasher::update) in any number of chunks
+/// is the same as using the concatenation of all chunks as a seed.
+/// [`finish()`](SeedHasher::finish) returns a compact seed of 28 bytes,
+/// which generates the same blockies as the whole absorbed data.
+///
+/// # Example
+///
+/// * Absorb a stream in chunks
+///   ```
+///   use eth_blockies::*;
+///   use std::io::Read;
+///
+///   // e.g. std::fs::File::open("document.pdf")
+///   let mut reader: &[u8] = b"long document contents...";
+///
+///   let mut hasher = SeedHasher::new();
+///   let mut buf = [0_u8; 4];
+///   loop {
+///       match reader.read(&mut buf).unwrap() {
+///           0 => break,
+///           len => hasher.update(&buf[..len]),
+///       }
+///   }
+///
+///   assert_eq!(
+///       Blockies::<8>::data(hasher.finish()),
+///       Blockies::<8>::data("long document contents...")
+///   );
+///   ```
+///
+/// * Absorb formatted fields
+///   ```
+///   use eth_blockies::*;
+///   use std::fmt::Write;
+///
+///   let mut hasher = SeedHasher::new();
+///   write!(hasher, "{}:{}", "user", 42).unwrap();
+///
+///   assert_eq!(Blockies::<8>::data(hasher.finish()), Blockies::<8>::data("user:42"));
+///   ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub struct SeedHasher {
+    key_seeds: [i32; KeyGenerator::KEY_SEEDS_LEN],
+    /// Total length of absorbed data
+    len: usize,
+}
+
+impl SeedHasher {
+    /// Length of the compact seed from [`finish()`](SeedHasher::finish)
+    pub const COMPACT_SEED_LEN: usize = KeyGenerator::KEY_SEEDS_LEN * Self::DIGITS_PER_KEY_SEED;
+
+    // each key seed is absorbed as 7 digits in base 31 (31^7 > 2^32)
+    const DIGITS_PER_KEY_SEED: usize = 7;
+    const BASE: u32 = 31;
+
+    /// Create a new hasher with nothing absorbed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Absorb the data, following the data absorbed so far
+    pub fn update(&mut self, data: &[u8]) {
+        data.iter().for_each(|byte| {
+            let key_seed = &mut self.key_seeds[self.len % KeyGenerator::KEY_SEEDS_LEN];
+            *key_seed = Self::key_seed_init(*key_seed, *byte);
+            self.len = self.len.wrapping_add(1);
+        });
+    }
+
+    /// Get a compact seed, which generates the same blockies as all data absorbed so far
+    pub fn finish(&self) -> [u8; Self::COMPACT_SEED_LEN] {
+        // base-31 digits of each key seed, from the most significant one
+        let digits: [[u8; Self::DIGITS_PER_KEY_SEED]; KeyGenerator::KEY_SEEDS_LEN] =
+            self.key_seeds.map(|key_seed| {
+                let mut digits = [0_u8; Self::DIGITS_PER_KEY_SEED];
+                digits.iter_mut().rev().fold(key_seed as u32, |rest, digit| {
+                    *digit = (rest % Self::BASE) as u8;
+                    rest / Self::BASE
+                });
+                digits
+            });
+
+        // seed bytes are absorbed into key seeds in turn
+        core::array::from_fn(|idx| {
+            digits[idx % KeyGenerator::KEY_SEEDS_LEN][idx / KeyGenerator::KEY_SEEDS_LEN]
+        })
+    }
+
+    // Update single element in key_seeds for initialization
+    fn key_seed_init(key_seed_cur: i32, seed_char_cur: u8) -> i32 {
+        (key_seed_cur << 5)
+            .overflowing_sub(key_seed_cur)
+            .0
+            .overflowing_add(seed_char_cur as i32)
+            .0
+    }
+}
+
+impl core::fmt::Write for SeedHasher {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.update(s.as_bytes());
+        Ok(())
+    }
+}
@@ -1,11 +1,23 @@
 mod blockies_base;
+mod canvas;
+mod caption;
+mod font;
 mod key_generator;
+#[cfg(feature = "qr")]
+mod qr_badge;
+mod render;
+mod sheet;
 
 use crate::global_type_helper::{ColorClass, RgbPalette};
 pub use blockies_base::{Blockies, BlockiesHelper};
+pub use key_generator::{KeyGenerator, SeedHasher};
+#[cfg(feature = "qr")]
+pub use qr_badge::{qr_badge_svg, render_qr_badge};
+pub use render::{render, render_svg, RenderedImage};
+pub use sheet::{render_sheet, sheet_to_svg};
 
 pub fn new_blockies<const R: usize>(seed: &[u8]) -> (RgbPalette, Blockies<R, ColorClass>) {
-    let mut keygen = key_generator::KeyGenerator::new(seed);
+    let mut keygen = KeyGenerator::new(seed);
 
     // initialize palette
     let palette = {
@@ -23,9 +35,9 @@ pub fn new_blockies<const R: usize>(seed: &[u8]) -> (RgbPalette, Blockies<R, Col
         // class
         match x < (R + 1) / 2 {
             // left half: use itself
-            true => src_arr[y][x].clone(),
+            true => src_arr[y][x],
             // right half: use corresponding flipped left half
-            false => src_arr[y][R - 1 - x].clone(),
+            false => src_arr[y][R - 1 - x],
         }
     });
 
@@ -0,0 +1,189 @@
+use super::{canvas::Canvas, new_blockies, render, Blockies, RenderedImage};
+use crate::global_type_helper::{ColorClass, QrOptions, RenderOptions, RgbPalette, SeedInput};
+use crate::{indexed_svg, qr_code};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const EIP681_SCHEME: &str = "ethereum:";
+
+// Position of each element in a QR code badge
+struct QrBadgeLayout {
+    dimension: (usize, usize),
+    module_size: usize,
+    // offset of the QR code symbol (inside the quiet zone)
+    qr_offset: (usize, usize),
+    // offset and dimension of the light area in the centre, with blockies in it
+    center_offset: (usize, usize),
+    center_dim: (usize, usize),
+    // offset and dimension of blockies, with 1 module margin in the light area
+    icon_offset: (usize, usize),
+    icon_dim: (usize, usize),
+}
+
+// QR code badge of the address: QR code modules, and blockies of the address
+struct QrBadge<const S: usize> {
+    modules: qr_code::QrModules,
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    ethaddr: [u8; 42],
+}
+
+// QR code badge of the address, or None if the seed is not an Ethereum address
+// (to_ethaddr_seed() falls back to zeroes for other seeds, which must not be encoded)
+fn qr_badge<const S: usize>(seed: &[u8], options: QrOptions) -> Option<QrBadge<S>> {
+    let ethaddr = seed.to_ethaddr_seed();
+    let is_ethaddr = ethaddr.starts_with(b"0x")
+        && ethaddr[2..]
+            .iter()
+            .all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f'));
+    if !is_ethaddr {
+        return None;
+    }
+
+    let payload: Vec<u8> = match options.eip681_uri {
+        true => EIP681_SCHEME.bytes().chain(ethaddr).collect(),
+        false => ethaddr.to_vec(),
+    };
+    let (palette, bitmap) = new_blockies::<S>(&ethaddr);
+
+    Some(QrBadge {
+        modules: qr_code::qr_code_modules(&payload),
+        palette,
+        bitmap,
+        ethaddr,
+    })
+}
+
+// the light area in the centre covers a quarter of the QR code on each axis (odd, to be centered),
+// which is far less than the error correction level H recovers
+fn qr_badge_layout(qr_size: usize, options: QrOptions) -> QrBadgeLayout {
+    let module_size = options.module_size.max(1);
+    let quiet_zone = options.quiet_zone * module_size;
+    let center_modules = match options.blockies {
+        true => (qr_size / 4).saturating_sub(1) | 1,
+        false => 0,
+    };
+    let center_offset = quiet_zone + (qr_size - center_modules) / 2 * module_size;
+    let icon_modules = center_modules.saturating_sub(2);
+
+    let dimension = qr_size * module_size + quiet_zone * 2;
+    QrBadgeLayout {
+        dimension: (dimension, dimension),
+        module_size,
+        qr_offset: (quiet_zone, quiet_zone),
+        center_offset: (center_offset, center_offset),
+        center_dim: (center_modules * module_size, center_modules * module_size),
+        icon_offset: (center_offset + module_size, center_offset + module_size),
+        icon_dim: (icon_modules * module_size, icon_modules * module_size),
+    }
+}
+
+// Render QR code of the address, with blockies in the centre.
+// return: (QR code image, dimension of QR code image), or None if the seed is not an Ethereum address
+pub fn render_qr_badge<const S: usize>(
+    seed: &[u8],
+    options: QrOptions,
+) -> Option<(RenderedImage, (usize, usize))> {
+    const OPAQUE: u8 = 255;
+
+    let badge = qr_badge::<S>(seed, options)?;
+    let layout = qr_badge_layout(badge.modules.len(), options);
+    let (light_r, light_g, light_b) = options.light_color;
+    let (dark_r, dark_g, dark_b) = options.dark_color;
+
+    let mut canvas = Canvas::new(layout.dimension, (light_r, light_g, light_b, OPAQUE));
+    badge.modules.iter().enumerate().for_each(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .filter(|(_, is_dark)| **is_dark)
+            .for_each(|(x, _)| {
+                canvas.fill_rect(
+                    (
+                        layout.qr_offset.0 + x * layout.module_size,
+                        layout.qr_offset.1 + y * layout.module_size,
+                    ),
+                    (layout.module_size, layout.module_size),
+                    (dark_r, dark_g, dark_b, OPAQUE),
+                )
+            })
+    });
+    if options.blockies {
+        canvas.fill_rect(
+            layout.center_offset,
+            layout.center_dim,
+            (light_r, light_g, light_b, OPAQUE),
+        );
+        canvas.draw_image(
+            layout.icon_offset,
+            &render(
+                badge.palette,
+                badge.bitmap,
+                layout.icon_dim,
+                RenderOptions::default(),
+                &badge.ethaddr,
+            )
+            .into_rgba(),
+        );
+    }
+
+    Some((canvas.into_rendered_image(), layout.dimension))
+}
+
+// Generate svg data of QR code of the address, with blockies in the centre,
+// or None if the seed is not an Ethereum address
+pub fn qr_badge_svg<const S: usize>(seed: &[u8], options: QrOptions) -> Option<String> {
+    let badge = qr_badge::<S>(seed, options)?;
+    let layout = qr_badge_layout(badge.modules.len(), options);
+
+    // (row, start, length) of each run of dark modules
+    let runs: Vec<(usize, usize, usize)> = badge
+        .modules
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter().enumerate().fold(
+                Vec::new(),
+                |mut runs: Vec<(usize, usize, usize)>, (x, is_dark)| {
+                    match (*is_dark, runs.last_mut()) {
+                        (true, Some((_, start, len))) if *start + *len == x => *len += 1,
+                        (true, _) => runs.push((y, x, 1)),
+                        (false, _) => (),
+                    };
+                    runs
+                },
+            )
+        })
+        .collect();
+
+    let bitmap: Vec<Vec<ColorClass>> = badge.bitmap.iter().map(|row| row.to_vec()).collect();
+    let elements: String = [
+        indexed_svg::rect_element((0, 0), layout.dimension, options.light_color),
+        indexed_svg::path_element(
+            &runs,
+            layout.qr_offset,
+            layout.module_size,
+            options.dark_color,
+        ),
+        match options.blockies {
+            true => [
+                indexed_svg::rect_element(
+                    layout.center_offset,
+                    layout.center_dim,
+                    options.light_color,
+                ),
+                indexed_svg::blockies_element(
+                    badge.palette,
+                    &bitmap,
+                    layout.icon_offset,
+                    layout.icon_dim,
+                ),
+            ]
+            .concat(),
+            false => String::new(),
+        },
+    ]
+    .concat();
+
+    Some(indexed_svg::svg_document(layout.dimension, &elements))
+}
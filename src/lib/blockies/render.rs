@@ -0,0 +1,364 @@
+use super::{caption, Blockies, BlockiesHelper};
+use crate::global_type_helper::{
+    ColorClass, Mask, Padding, RenderOptions, RgbPalette, RgbaPixel, ScaleMode,
+};
+use crate::indexed_svg;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Rendered blockies image
+pub enum RenderedImage {
+    // (palette, palette index of each pixel)
+    Indexed(Vec<RgbaPixel>, Vec<Vec<u8>>),
+    // color of each pixel
+    Rgba(Vec<Vec<RgbaPixel>>),
+}
+
+impl RenderedImage {
+    pub fn into_rgba(self) -> Vec<Vec<RgbaPixel>> {
+        match self {
+            RenderedImage::Indexed(palette, bitmap) => bitmap
+                .iter()
+                .map(|row| row.iter().map(|index| palette[*index as usize]).collect())
+                .collect(),
+            RenderedImage::Rgba(bitmap) => bitmap,
+        }
+    }
+}
+
+// what to draw on a pixel
+enum Layer {
+    // palette index of a solid color
+    Index(u8),
+    // coordinates inside the scaled blockies
+    Blockies(usize, usize),
+}
+
+// Render blockies into image of the given dimension, with render options.
+// the palette of indexed image is [bgcolor, color, spotcolor, (border color), (transparent)],
+// so that each class is indexed as is (except for captioned image)
+pub fn render<const S: usize>(
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    output_dim: (usize, usize),
+    options: RenderOptions,
+    seed: &[u8],
+) -> RenderedImage {
+    if let Some(caption) = options.caption {
+        return caption::render_captioned(palette, bitmap, output_dim, options, caption, seed);
+    }
+
+    const OPAQUE: u8 = 255;
+    const TRANSPARENT: RgbaPixel = (0, 0, 0, 0);
+
+    let mut rgba_palette: Vec<RgbaPixel> = palette
+        .iter()
+        .map(|(r, g, b)| (*r, *g, *b, OPAQUE))
+        .collect();
+    let mut push_color = |color: RgbaPixel| -> u8 {
+        rgba_palette.push(color);
+        (rgba_palette.len() - 1) as u8
+    };
+    let border_index: Option<u8> = (options.border_width > 0).then(|| {
+        let (r, g, b) = options.border_color;
+        push_color((r, g, b, OPAQUE))
+    });
+    let transparent_index: Option<u8> =
+        (options.mask != Mask::None).then(|| push_color(TRANSPARENT));
+
+    let border_width = options.border_width;
+    let (icon_offset, icon_dim) = icon_area::<S>(output_dim, options);
+
+    // masks are tested only for the layers in use
+    let layer_at = |(x, y): (usize, usize)| -> Layer {
+        match (
+            transparent_index.filter(|_| !is_in_mask(options.mask, output_dim, (x, y), 0)),
+            border_index.filter(|_| !is_in_mask(options.mask, output_dim, (x, y), border_width)),
+        ) {
+            (Some(transparent_index), _) => Layer::Index(transparent_index),
+            (_, Some(border_index)) => Layer::Index(border_index),
+            _ => match (
+                x.checked_sub(icon_offset.0).filter(|x| *x < icon_dim.0),
+                y.checked_sub(icon_offset.1).filter(|y| *y < icon_dim.1),
+            ) {
+                (Some(icon_x), Some(icon_y)) => Layer::Blockies(icon_x, icon_y),
+                _ => Layer::Index(ColorClass::BgColor as u8),
+            },
+        }
+    };
+
+    // without border, padding or mask, the scaled blockies is the whole image
+    let is_icon_only =
+        icon_dim == output_dim && transparent_index.is_none() && border_index.is_none();
+
+    match options.scale_mode {
+        ScaleMode::AreaAveraged => {
+            let icon = scale_area_averaged(palette, bitmap, icon_dim);
+            RenderedImage::Rgba(match is_icon_only {
+                true => icon,
+                false => map_layers(output_dim, layer_at, |layer| match layer {
+                    Layer::Index(index) => rgba_palette[index as usize],
+                    Layer::Blockies(icon_x, icon_y) => icon[icon_y][icon_x],
+                }),
+            })
+        }
+        scale_mode => {
+            let icon = scale_nearest(bitmap, icon_dim, scale_mode);
+            let indexed_bitmap = match is_icon_only {
+                true => icon
+                    .iter()
+                    .map(|row| row.iter().map(|class| *class as u8).collect())
+                    .collect(),
+                false => map_layers(output_dim, layer_at, |layer| match layer {
+                    Layer::Index(index) => index,
+                    Layer::Blockies(icon_x, icon_y) => icon[icon_y][icon_x] as u8,
+                }),
+            };
+            RenderedImage::Indexed(rgba_palette, indexed_bitmap)
+        }
+    }
+}
+
+// (offset, dimension) of the area of scaled blockies: inside of border and padding
+fn icon_area<const S: usize>(
+    output_dim: (usize, usize),
+    options: RenderOptions,
+) -> ((usize, usize), (usize, usize)) {
+    let border_width = options.border_width;
+    let padding = match options.padding {
+        Padding::Pixels(pixels) => (pixels, pixels),
+        Padding::Cells(cells) => {
+            let cells_per_side = S + 2 * cells;
+            (
+                output_dim.0.saturating_sub(2 * border_width) * cells / cells_per_side,
+                output_dim.1.saturating_sub(2 * border_width) * cells / cells_per_side,
+            )
+        }
+    };
+    let icon_offset = (border_width + padding.0, border_width + padding.1);
+    let icon_dim = (
+        output_dim.0.saturating_sub(2 * icon_offset.0),
+        output_dim.1.saturating_sub(2 * icon_offset.1),
+    );
+
+    (icon_offset, icon_dim)
+}
+
+// Generate svg data of blockies, rendered with render options.
+// scaling is left to the svg renderer: only square cells of ScaleMode::Exact make a difference,
+// and ScaleMode::AreaAveraged is the same as ScaleMode::Distributed
+pub fn render_svg<const S: usize>(
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    output_dim: (usize, usize),
+    options: RenderOptions,
+    seed: &[u8],
+) -> String {
+    match options.caption {
+        Some(caption) => {
+            caption::captioned_svg(palette, bitmap, output_dim, options, caption, seed)
+        }
+        None => indexed_svg::svg_document(
+            output_dim,
+            &rendered_svg_element(palette, bitmap, output_dim, options, seed),
+        ),
+    }
+}
+
+// blockies rendered with render options (except the caption), as svg elements
+pub fn rendered_svg_element<const S: usize>(
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    output_dim: (usize, usize),
+    options: RenderOptions,
+    seed: &[u8],
+) -> String {
+    let (icon_offset, icon_dim) = icon_area::<S>(output_dim, options);
+    let icon_area = match (options.scale_mode, (icon_dim.0 / S).min(icon_dim.1 / S)) {
+        (ScaleMode::Exact, cell_size @ 1..) => {
+            let scaled_len = cell_size * S;
+            (
+                (
+                    icon_offset.0 + (icon_dim.0 - scaled_len) / 2,
+                    icon_offset.1 + (icon_dim.1 - scaled_len) / 2,
+                ),
+                (scaled_len, scaled_len),
+            )
+        }
+        _ => (icon_offset, icon_dim),
+    };
+
+    // unique id of the mask for the seed and the options,
+    // not to conflict when embedded in the same html (FNV-1a, 64-bit)
+    let mask_id = format!(
+        "blockies-mask-{:016x}",
+        seed.iter()
+            .chain(format!("{:?}{:?}", output_dim, options).as_bytes())
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+            })
+    );
+
+    indexed_svg::rendered_element(
+        palette,
+        &bitmap.iter().map(|row| row.to_vec()).collect::<Vec<_>>(),
+        output_dim,
+        icon_area,
+        options,
+        &mask_id,
+    )
+}
+
+// pixels mapped from the layer at each of them (layers are not kept for the whole image)
+fn map_layers<T, L: Fn((usize, usize)) -> Layer, F: Fn(Layer) -> T>(
+    output_dim: (usize, usize),
+    layer_at: L,
+    fn_layer: F,
+) -> Vec<Vec<T>> {
+    (0..output_dim.1)
+        .map(|y| {
+            (0..output_dim.0)
+                .map(|x| fn_layer(layer_at((x, y))))
+                .collect()
+        })
+        .collect()
+}
+
+// nearest-neighbour scaling, with distributed leftover pixels or uniform cells
+fn scale_nearest<const S: usize>(
+    bitmap: Blockies<S, ColorClass>,
+    dimension: (usize, usize),
+    scale_mode: ScaleMode,
+) -> Vec<Vec<ColorClass>> {
+    let cell_size = (dimension.0 / S).min(dimension.1 / S);
+
+    match (scale_mode, cell_size) {
+        (ScaleMode::Exact, 1..) => {
+            let scaled_len = cell_size * S;
+            let offset = (
+                (dimension.0 - scaled_len) / 2,
+                (dimension.1 - scaled_len) / 2,
+            );
+            let scaled = bitmap.scale((scaled_len, scaled_len));
+
+            (0..dimension.1)
+                .map(|y| {
+                    (0..dimension.0)
+                        .map(|x| {
+                            match (
+                                x.checked_sub(offset.0).filter(|x| *x < scaled_len),
+                                y.checked_sub(offset.1).filter(|y| *y < scaled_len),
+                            ) {
+                                (Some(x), Some(y)) => scaled[y][x],
+                                _ => ColorClass::BgColor,
+                            }
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+        _ => bitmap.scale(dimension),
+    }
+}
+
+// area-averaged scaling: each pixel is the average color of cells,
+// weighted by the area of each cell covering the pixel
+fn scale_area_averaged<const S: usize>(
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    dimension: (usize, usize),
+) -> Vec<Vec<RgbaPixel>> {
+    const OPAQUE: u8 = 255;
+
+    // (cell index, covering length) of each pixel on an axis of the given length.
+    // in units of 1 / S pixels: a pixel is S long, and a cell is (length) long
+    let axis_weights = |len: usize| -> Vec<Vec<(usize, u64)>> {
+        (0..len)
+            .map(|pixel| {
+                let (pixel_start, pixel_end) = (pixel * S, (pixel + 1) * S);
+                (pixel_start / len..S.min((pixel_end + len - 1) / len))
+                    .map(|cell| {
+                        let (cell_start, cell_end) = (cell * len, (cell + 1) * len);
+                        (
+                            cell,
+                            (pixel_end.min(cell_end) - pixel_start.max(cell_start)) as u64,
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    let (weights_x, weights_y) = (axis_weights(dimension.0), axis_weights(dimension.1));
+    let area = (S * S) as u64;
+
+    weights_y
+        .iter()
+        .map(|row_weights| {
+            weights_x
+                .iter()
+                .map(|col_weights| {
+                    let (r, g, b) = row_weights
+                        .iter()
+                        .flat_map(|(cell_y, weight_y)| {
+                            col_weights.iter().map(move |(cell_x, weight_x)| {
+                                (*cell_x, *cell_y, weight_x * weight_y)
+                            })
+                        })
+                        .fold(
+                            (0_u64, 0_u64, 0_u64),
+                            |(r, g, b), (cell_x, cell_y, weight)| {
+                                let (cell_r, cell_g, cell_b) = palette[bitmap[cell_y][cell_x]];
+                                (
+                                    r + cell_r as u64 * weight,
+                                    g + cell_g as u64 * weight,
+                                    b + cell_b as u64 * weight,
+                                )
+                            },
+                        );
+                    // rounded average
+                    let average = |sum: u64| ((sum + area / 2) / area) as u8;
+                    (average(r), average(g), average(b), OPAQUE)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// check if the center of the pixel is inside the mask shape, shrunk by inset pixels.
+// calculated in units of half pixels, to use integers only
+fn is_in_mask(mask: Mask, dimension: (usize, usize), (x, y): (usize, usize), inset: usize) -> bool {
+    let (width, height) = (2 * dimension.0 as i128, 2 * dimension.1 as i128);
+    let (px, py) = (2 * x as i128 + 1, 2 * y as i128 + 1);
+    let inset = 2 * inset as i128;
+
+    let (left, top, right, bottom) = (inset, inset, width - inset, height - inset);
+    if px < left || px > right || py < top || py > bottom {
+        return false;
+    }
+
+    match mask {
+        Mask::None => true,
+        Mask::Circle => {
+            let (radius_x, radius_y) = ((right - left) / 2, (bottom - top) / 2);
+            let (dx, dy) = (px - width / 2, py - height / 2);
+            radius_x > 0
+                && radius_y > 0
+                && dx * dx * radius_y * radius_y + dy * dy * radius_x * radius_x
+                    <= radius_x * radius_x * radius_y * radius_y
+        }
+        Mask::RoundedSquare(radius) => {
+            let radius = (2 * radius as i128 - inset)
+                .min((right - left) / 2)
+                .min((bottom - top) / 2)
+                .max(0);
+            // nearest center of corner arcs
+            let (cx, cy) = (
+                px.clamp(left + radius, right - radius),
+                py.clamp(top + radius, bottom - radius),
+            );
+            (px - cx) * (px - cx) + (py - cy) * (py - cy) <= radius * radius
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use super::{
+    canvas::Canvas, caption::caption_text, font, new_blockies, render, Blockies, RenderedImage,
+};
+use crate::global_type_helper::{ColorClass, RenderOptions, RgbPalette, SheetOptions};
+use crate::indexed_svg;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Position of each element in a sheet
+struct SheetLayout {
+    dimension: (usize, usize),
+    // offset of each blockies
+    icon_offsets: Vec<(usize, usize)>,
+    // offset of the label under each blockies
+    label_offsets: Vec<(usize, usize)>,
+}
+
+// blockies and label of each seed in a sheet
+struct SheetItem<'a, const S: usize> {
+    seed: &'a [u8],
+    palette: RgbPalette,
+    bitmap: Blockies<S, ColorClass>,
+    label_pixels: Vec<(usize, usize)>,
+    label_width: usize,
+}
+
+fn sheet_layout(item_count: usize, options: SheetOptions) -> SheetLayout {
+    let columns = options.columns.max(1).min(item_count.max(1));
+    let rows = ((item_count + columns - 1) / columns).max(1);
+    let (icon_width, icon_height) = options.icon_dimension;
+    let label_height = match options.labels {
+        // 1 font pixel gap between the blockies and the label
+        true => (1 + font::GLYPH_HEIGHT) * label_scale(options),
+        false => 0,
+    };
+
+    let offset_of = |idx: usize| -> (usize, usize) {
+        (
+            options.spacing + (idx % columns) * (icon_width + options.spacing),
+            options.spacing + (idx / columns) * (icon_height + label_height + options.spacing),
+        )
+    };
+    let icon_offsets: Vec<(usize, usize)> = (0..item_count).map(offset_of).collect();
+    let label_offsets = icon_offsets
+        .iter()
+        .map(|(x, y)| (*x, y + icon_height + label_scale(options)))
+        .collect();
+
+    SheetLayout {
+        dimension: (
+            options.spacing + columns * (icon_width + options.spacing),
+            options.spacing + rows * (icon_height + label_height + options.spacing),
+        ),
+        icon_offsets,
+        label_offsets,
+    }
+}
+
+fn label_scale(options: SheetOptions) -> usize {
+    options.label_scale.max(1)
+}
+
+fn sheet_items<'a, const S: usize>(
+    seeds: &[&'a [u8]],
+    options: SheetOptions,
+) -> Vec<SheetItem<'a, S>> {
+    seeds
+        .iter()
+        .map(|seed| {
+            let (palette, bitmap) = new_blockies::<S>(seed);
+            let (label_pixels, label_width) = match options.labels {
+                true => font::text_pixels(
+                    &caption_text(options.label_text, seed),
+                    options.icon_dimension.0 / label_scale(options),
+                ),
+                false => (Vec::new(), 0),
+            };
+            SheetItem {
+                seed,
+                palette,
+                bitmap,
+                label_pixels,
+                label_width,
+            }
+        })
+        .collect()
+}
+
+// horizontal offset to center the label under the blockies
+fn label_x(options: SheetOptions, label_width: usize) -> usize {
+    options
+        .icon_dimension
+        .0
+        .saturating_sub(label_width * label_scale(options))
+        / 2
+}
+
+// Render blockies of all seeds into a single sheet image.
+// return: (sheet image, dimension of sheet image)
+pub fn render_sheet<const S: usize>(
+    seeds: &[&[u8]],
+    options: SheetOptions,
+) -> (RenderedImage, (usize, usize)) {
+    const TRANSPARENT: (u8, u8, u8, u8) = (0, 0, 0, 0);
+    const OPAQUE: u8 = 255;
+
+    let layout = sheet_layout(seeds.len(), options);
+    let (label_r, label_g, label_b) = options.label_color;
+
+    let mut canvas = Canvas::new(
+        layout.dimension,
+        options
+            .background
+            .map_or(TRANSPARENT, |(r, g, b)| (r, g, b, OPAQUE)),
+    );
+    sheet_items::<S>(seeds, options)
+        .into_iter()
+        .zip(layout.icon_offsets.iter())
+        .zip(layout.label_offsets.iter())
+        .for_each(|((item, icon_offset), (label_x_base, label_y))| {
+            canvas.draw_image(
+                *icon_offset,
+                &render(
+                    item.palette,
+                    item.bitmap,
+                    options.icon_dimension,
+                    RenderOptions::default(),
+                    item.seed,
+                )
+                .into_rgba(),
+            );
+            canvas.draw_text(
+                (label_x_base + label_x(options, item.label_width), *label_y),
+                &item.label_pixels,
+                label_scale(options),
+                (label_r, label_g, label_b, OPAQUE),
+            );
+        });
+
+    (canvas.into_rendered_image(), layout.dimension)
+}
+
+// Generate svg data of a sheet with blockies of all seeds
+pub fn sheet_to_svg<const S: usize>(seeds: &[&[u8]], options: SheetOptions) -> String {
+    let layout = sheet_layout(seeds.len(), options);
+
+    let background = options.background.map_or(String::new(), |color| {
+        indexed_svg::rect_element((0, 0), layout.dimension, color)
+    });
+    let elements: String = sheet_items::<S>(seeds, options)
+        .into_iter()
+        .zip(layout.icon_offsets.iter())
+        .zip(layout.label_offsets.iter())
+        .flat_map(|((item, icon_offset), (label_x_base, label_y))| {
+            let bitmap: Vec<Vec<ColorClass>> = item.bitmap.iter().map(|row| row.to_vec()).collect();
+            let label_runs: Vec<(usize, usize, usize)> =
+                item.label_pixels.iter().map(|(x, y)| (*y, *x, 1)).collect();
+            [
+                indexed_svg::blockies_element(
+                    item.palette,
+                    &bitmap,
+                    *icon_offset,
+                    options.icon_dimension,
+                ),
+                match label_runs.is_empty() {
+                    true => String::new(),
+                    false => indexed_svg::path_element(
+                        &label_runs,
+                        (label_x_base + label_x(options, item.label_width), *label_y),
+                        label_scale(options),
+                        options.label_color,
+                    ),
+                },
+            ]
+        })
+        .collect();
+
+    indexed_svg::svg_document(layout.dimension, &(background + &elements))
+}
@@ -4,7 +4,7 @@
 use alloc::{string::String, vec::Vec};
 
 #[cfg(feature = "compressed_png")]
-use crate::indexed_png;
+use crate::{blockies, indexed_png, PngOptions, RenderOptions};
 
 use crate::{
     ansi_seq, BlockiesGenerator, BlockiesHelper, ColorClass, EthBlockies, Palette, RgbPalette,
@@ -153,7 +153,7 @@ pub fn eth_blockies_data_mapped<S: SeedInput, T: Clone, F: Fn(RgbPixel) -> T>(
 #[doc(hidden)]
 #[deprecated(since = "1.1.0", note = "Use `EthBlockies::indexed_data` instead")]
 pub fn eth_blockies_indexed_data<S: SeedInput>(seed: S) -> (RgbPalette, EthBlockies<ColorClass>) {
-    EthBlockies::indexed_data(seed.as_seed_bytes())
+    EthBlockies::indexed_data(seed)
 }
 
 /// Get Ethereum blockies data in indexed image format with mapped palette (`map_fn`)
@@ -217,7 +217,7 @@ pub fn eth_blockies_indexed_data_mapped<S: SeedInput, T: Clone, F: Fn(RgbPixel)
     seed: S,
     map_fn: F,
 ) -> (Palette<T>, EthBlockies<ColorClass>) {
-    EthBlockies::indexed_data_mapped(seed.as_seed_bytes(), map_fn)
+    EthBlockies::indexed_data_mapped(seed, map_fn)
 }
 
 /// Get Ethereum-style blockies data in ANSI sequence format
@@ -331,12 +331,14 @@ pub fn eth_blockies_png_data<S: SeedInput>(
     dimension: (usize, usize),
     compressed_output: bool,
 ) -> Vec<u8> {
-    let (palette, bitmap) = EthBlockies::indexed_data(seed);
-    indexed_png::indexed_data_to_png(
-        palette,
-        bitmap.scale(dimension),
+    let seed = seed.to_seed_bytes();
+    let (palette, bitmap) = EthBlockies::indexed_data(&*seed);
+    indexed_png::rendered_data_to_png(
+        blockies::render(palette, bitmap, dimension, RenderOptions::default(), &seed),
         dimension,
         compressed_output,
+        PngOptions::default(),
+        (&*seed, EthBlockies::<ColorClass>::SIZE),
     )
 }
 
@@ -472,7 +474,7 @@ pub trait SeedString {
 #[allow(deprecated)]
 impl SeedString for &str {
     fn as_seed_str(&self) -> &str {
-        *self
+        self
     }
 }
 
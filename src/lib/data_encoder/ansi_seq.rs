@@ -113,6 +113,11 @@ pub fn indexed_data_to_ansiseq_utf8(
 
             row_arr_reset.copy_from_slice(&color_reset());
 
+            // remove unused bgcolor part (zero bytes) of each block, if only upper line exists
+            if row_chunks.len() == 1 {
+                row_arr.retain(|byte| *byte != 0);
+            }
+
             // using unchecked ver, as built string is always valid utf8
             unsafe { String::from_utf8_unchecked(row_arr) }
             // String::from_utf8(row_arr).expect("unexpected internal error")
@@ -129,9 +134,9 @@ fn color_block_ascii(
 
     let (bg_part, block_part) = ret_arr.split_at_mut(19);
 
-    bgcolor.map(|bg| {
+    if let Some(bg) = bgcolor {
         write_color_ansiseq_to_bytebuf(bg_part, bg, false);
-    });
+    }
     block_part.copy_from_slice(block);
 
     ret_arr
@@ -148,12 +153,12 @@ fn color_block_unicode(
     let (fg_part, rem) = ret_arr.split_at_mut(19);
     let (bg_part, block_part) = rem.split_at_mut(19);
 
-    fgcolor.map(|fg| {
+    if let Some(fg) = fgcolor {
         write_color_ansiseq_to_bytebuf(fg_part, fg, true);
-    });
-    bgcolor.map(|bg| {
+    }
+    if let Some(bg) = bgcolor {
         write_color_ansiseq_to_bytebuf(bg_part, bg, false);
-    });
+    }
     block_part.copy_from_slice(block);
 
     ret_arr
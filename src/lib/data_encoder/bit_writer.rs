@@ -0,0 +1,85 @@
+use alloc::vec::Vec;
+
+// LSB-first bit writer
+pub struct BitWriter {
+    data: Vec<u8>,
+    bits: u64,
+    bits_len: u8,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            bits: 0,
+            bits_len: 0,
+        }
+    }
+
+    pub fn write_bits(&mut self, value: u32, len: u8) -> &mut Self {
+        self.bits |= (value as u64 & ((1_u64 << len) - 1)) << self.bits_len;
+        self.bits_len += len;
+        while self.bits_len >= 8 {
+            self.data.push(self.bits as u8);
+            self.bits >>= 8;
+            self.bits_len -= 8;
+        }
+        self
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.bits_len > 0 {
+            self.data.push(self.bits as u8);
+        }
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert!(BitWriter::new().finish().is_empty());
+    }
+
+    #[test]
+    fn lsb_first() {
+        let mut writer = BitWriter::new();
+        writer
+            .write_bits(0b1, 1)
+            .write_bits(0b10, 2)
+            .write_bits(0b11111, 5);
+        assert_eq!(writer.finish(), [0b1111_1101]);
+    }
+
+    #[test]
+    fn partial_byte_padded_with_zeros() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(0b101, 3);
+        assert_eq!(writer.finish(), [0b0000_0101]);
+
+        let mut writer = BitWriter::new();
+        writer.write_bits(0xff, 8).write_bits(0b1, 1);
+        assert_eq!(writer.finish(), [0xff, 0b1]);
+    }
+
+    #[test]
+    fn values_across_bytes() {
+        let mut writer = BitWriter::new();
+        writer
+            .write_bits(0b1, 1)
+            .write_bits(0x1234, 16)
+            .write_bits(0xffff_ffff, 32)
+            .write_bits(0, 7);
+        assert_eq!(writer.finish(), [0x69, 0x24, 0xfe, 0xff, 0xff, 0xff, 0x01]);
+    }
+
+    #[test]
+    fn upper_bits_of_value_ignored() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(0xffff_fff0, 4).write_bits(0xffff_fff5, 4);
+        assert_eq!(writer.finish(), [0x50]);
+    }
+}
@@ -0,0 +1,235 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+// (symbol, extra bits count, extra bits value) of code lengths, run-length encoded
+// with the code length alphabet shared by deflate and webp lossless:
+// 0 - 15: code length as is, 17: 3 - 10 zeros, 18: 11 - 138 zeros
+pub fn code_length_tokens(code_lengths: &[u8]) -> Vec<(u8, u8, u32)> {
+    const REPEAT_ZEROS_SHORT: (u8, usize, u8) = (17, 3, 3); // (symbol, min repeat, extra bits)
+    const REPEAT_ZEROS_LONG: (u8, usize, u8) = (18, 11, 7);
+
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < code_lengths.len() {
+        let zeros = code_lengths[idx..]
+            .iter()
+            .take_while(|len| **len == 0)
+            .count();
+        let (symbol, min_repeat, extra_bits) = match zeros {
+            0..=2 => (code_lengths[idx], 1, 0),
+            3..=10 => REPEAT_ZEROS_SHORT,
+            _ => REPEAT_ZEROS_LONG,
+        };
+        let repeat = match extra_bits {
+            0 => 1,
+            _ => zeros.min(min_repeat + (1 << extra_bits) - 1),
+        };
+        tokens.push((symbol, extra_bits, (repeat - min_repeat) as u32));
+        idx += repeat;
+    }
+
+    tokens
+}
+
+// build length-limited huffman code lengths for symbols of the histogram.
+// a complete tree is always built: a dummy symbol is added if only one symbol is used
+pub fn huffman_code_lengths(histogram: &[u32], max_len: u8) -> Vec<u8> {
+    let mut counts: Vec<u32> = histogram.to_vec();
+    match counts.iter().filter(|count| **count > 0).count() {
+        0 => counts[0..2].iter_mut().for_each(|count| *count = 1),
+        1 => {
+            let dummy_symbol = (counts[0] > 0) as usize;
+            counts[dummy_symbol] = 1;
+        }
+        _ => (),
+    }
+
+    let mut count_min = 1;
+    loop {
+        // build huffman tree using a min-heap of (count, node index)
+        let mut parents: Vec<usize> = vec![usize::MAX; counts.len()];
+        let mut heap: BinaryHeap<Reverse<(u32, usize)>> = counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(symbol, count)| Reverse(((*count).max(count_min), symbol)))
+            .collect();
+
+        while heap.len() > 1 {
+            let (Reverse((count_a, node_a)), Reverse((count_b, node_b))) =
+                (heap.pop().expect("huffman"), heap.pop().expect("huffman"));
+            let node_new = parents.len();
+            parents.push(usize::MAX);
+            parents[node_a] = node_new;
+            parents[node_b] = node_new;
+            heap.push(Reverse((count_a + count_b, node_new)));
+        }
+
+        // depth of each leaf
+        let code_lengths: Vec<u8> = (0..counts.len())
+            .map(|symbol| match counts[symbol] > 0 {
+                true => {
+                    let mut depth = 0;
+                    let mut node = symbol;
+                    while parents[node] != usize::MAX {
+                        node = parents[node];
+                        depth += 1;
+                    }
+                    depth
+                }
+                false => 0,
+            })
+            .collect();
+
+        match code_lengths.iter().all(|len| *len <= max_len) {
+            true => return code_lengths,
+            // flatten the distribution, then retry
+            false => count_min *= 2,
+        }
+    }
+}
+
+// canonical (code, length) of each symbol, with the code bits reversed for LSB-first writing
+pub fn canonical_codes(code_lengths: &[u8]) -> Vec<(u32, u8)> {
+    let max_len = code_lengths.iter().copied().max().unwrap_or(0) as usize;
+
+    let mut len_count = vec![0_u32; max_len + 1];
+    code_lengths
+        .iter()
+        .filter(|len| **len > 0)
+        .for_each(|len| len_count[*len as usize] += 1);
+
+    let mut next_code = vec![0_u32; max_len + 1];
+    (1..=max_len).fold(0_u32, |code, len| {
+        let code = (code + len_count[len - 1]) << 1;
+        next_code[len] = code;
+        code
+    });
+
+    code_lengths
+        .iter()
+        .map(|len| match *len {
+            0 => (0, 0),
+            len => {
+                let code = next_code[len as usize];
+                next_code[len as usize] += 1;
+                (code.reverse_bits() >> (u32::BITS - len as u32), len)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // sum of 2^-len of used codes, scaled by 2^max_len: a complete prefix code sums to 2^max_len
+    fn kraft_sum(code_lengths: &[u8], max_len: u8) -> u32 {
+        code_lengths
+            .iter()
+            .filter(|len| **len > 0)
+            .map(|len| 1 << (max_len - len))
+            .sum()
+    }
+
+    #[test]
+    fn code_lengths_complete() {
+        [
+            vec![1, 1],
+            vec![5, 9, 12, 13, 16, 45],
+            vec![1, 0, 0, 3, 0, 7, 7, 0],
+            (1..=286).collect(),
+        ]
+        .iter()
+        .for_each(|histogram| {
+            let code_lengths = huffman_code_lengths(histogram, 15);
+            assert_eq!(code_lengths.len(), histogram.len());
+            assert!(histogram
+                .iter()
+                .zip(code_lengths.iter())
+                .all(|(count, len)| (*count > 0) == (*len > 0)));
+            assert_eq!(kraft_sum(&code_lengths, 15), 1 << 15, "{:?}", histogram);
+        });
+    }
+
+    #[test]
+    fn code_lengths_optimal() {
+        assert_eq!(
+            huffman_code_lengths(&[5, 9, 12, 13, 16, 45], 15),
+            [4, 4, 3, 3, 3, 1]
+        );
+    }
+
+    #[test]
+    fn code_lengths_limited() {
+        // fibonacci counts make the deepest unlimited tree
+        let histogram: Vec<u32> = (0..30)
+            .scan((1, 1), |(a, b), _| {
+                let count = *a;
+                *a = *b;
+                *b += count;
+                Some(count)
+            })
+            .collect();
+
+        [7, 9, 15].iter().for_each(|max_len| {
+            let code_lengths = huffman_code_lengths(&histogram, *max_len);
+            assert!(code_lengths.iter().all(|len| (1..=*max_len).contains(len)));
+            assert_eq!(kraft_sum(&code_lengths, *max_len), 1 << max_len);
+        });
+    }
+
+    #[test]
+    fn code_lengths_fewer_than_two_symbols() {
+        // a dummy symbol completes the tree
+        assert_eq!(huffman_code_lengths(&[0, 0, 3, 0], 15), [1, 0, 1, 0]);
+        assert_eq!(huffman_code_lengths(&[3, 0, 0], 15), [1, 1, 0]);
+        assert_eq!(huffman_code_lengths(&[0, 0, 0], 15), [1, 1, 0]);
+    }
+
+    #[test]
+    fn canonical_codes_rfc1951_example() {
+        // https://www.rfc-editor.org/rfc/rfc1951#section-3.2.2
+        let codes = canonical_codes(&[3, 3, 3, 3, 3, 2, 4, 4]);
+        let expected: [(u32, u8); 8] = [
+            (0b010, 3),
+            (0b011, 3),
+            (0b100, 3),
+            (0b101, 3),
+            (0b110, 3),
+            (0b00, 2),
+            (0b1110, 4),
+            (0b1111, 4),
+        ];
+
+        // written LSB-first, so the code bits are reversed
+        assert_eq!(
+            codes,
+            expected.map(|(code, len)| (code.reverse_bits() >> (u32::BITS - len as u32), len))
+        );
+        assert_eq!(
+            canonical_codes(&[0, 1, 0, 1]),
+            [(0, 0), (0, 1), (0, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn code_length_tokens_zero_runs() {
+        assert_eq!(code_length_tokens(&[]), []);
+        assert_eq!(
+            code_length_tokens(&[3, 0, 0, 3]),
+            [(3, 0, 0), (0, 0, 0), (0, 0, 0), (3, 0, 0)]
+        );
+        assert_eq!(code_length_tokens(&[0; 3]), [(17, 3, 0)]);
+        assert_eq!(code_length_tokens(&[0; 10]), [(17, 3, 7)]);
+        assert_eq!(code_length_tokens(&[0; 11]), [(18, 7, 0)]);
+        assert_eq!(code_length_tokens(&[0; 138]), [(18, 7, 127)]);
+        assert_eq!(code_length_tokens(&[0; 139]), [(18, 7, 127), (0, 0, 0)]);
+        assert_eq!(code_length_tokens(&[0; 141]), [(18, 7, 127), (17, 3, 0)]);
+
+        // non-zero lengths are never repeated
+        assert_eq!(code_length_tokens(&[5; 3]), [(5, 0, 0); 3]);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::global_type_helper::{ColorClass, RgbPalette};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+// Convert indexed raw data to indexed bmp data
+pub fn indexed_data_to_bmp(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: Vec<Vec<ColorClass>>,
+    dimension: (usize, usize),
+) -> Vec<u8> {
+    const BIT_DEPTH: u8 = 4;
+
+    let mut color_table = color_table(indexed_palette);
+    let mut pixel_array = pixel_array(indexed_bitmap, dimension, BIT_DEPTH);
+    let colors_used = color_table.len() / COLOR_TABLE_ENTRY_BYTES;
+
+    // build bmp
+    let pixel_offset = FILE_HEADER_BYTES + INFO_HEADER_BYTES + color_table.len();
+    let mut ret_data: Vec<u8> = file_header(pixel_offset + pixel_array.len(), pixel_offset);
+    ret_data.append(&mut info_header(
+        dimension,
+        BIT_DEPTH,
+        pixel_array.len(),
+        colors_used,
+    ));
+    ret_data.append(&mut color_table);
+    ret_data.append(&mut pixel_array);
+
+    ret_data
+}
+
+const FILE_HEADER_BYTES: usize = 14;
+const INFO_HEADER_BYTES: usize = 40;
+const COLOR_TABLE_ENTRY_BYTES: usize = 4;
+
+// BITMAPFILEHEADER
+fn file_header(file_size: usize, pixel_offset: usize) -> Vec<u8> {
+    const SIGNATURE: &[u8] = b"BM";
+    const RESERVED: u32 = 0;
+
+    let mut header: Vec<u8> = SIGNATURE.to_vec();
+
+    header.extend_from_slice(&(file_size as u32).to_le_bytes());
+    header.extend_from_slice(&RESERVED.to_le_bytes());
+    header.extend_from_slice(&(pixel_offset as u32).to_le_bytes());
+
+    header
+}
+
+// BITMAPINFOHEADER
+fn info_header(
+    dimension: (usize, usize),
+    bit_depth: u8,
+    image_size: usize,
+    colors_used: usize,
+) -> Vec<u8> {
+    const PLANES: u16 = 1;
+    const COMPRESSION: u32 = 0; // BI_RGB
+    const PIXELS_PER_METER: i32 = 2835; // 72 DPI
+
+    let mut header: Vec<u8> = Vec::with_capacity(INFO_HEADER_BYTES);
+
+    header.extend_from_slice(&(INFO_HEADER_BYTES as u32).to_le_bytes());
+    // positive height: bottom-up pixel array
+    header.extend_from_slice(&(dimension.0 as i32).to_le_bytes());
+    header.extend_from_slice(&(dimension.1 as i32).to_le_bytes());
+    header.extend_from_slice(&PLANES.to_le_bytes());
+    header.extend_from_slice(&(bit_depth as u16).to_le_bytes());
+    header.extend_from_slice(&COMPRESSION.to_le_bytes());
+    header.extend_from_slice(&(image_size as u32).to_le_bytes());
+    header.extend_from_slice(&PIXELS_PER_METER.to_le_bytes());
+    header.extend_from_slice(&PIXELS_PER_METER.to_le_bytes());
+    header.extend_from_slice(&(colors_used as u32).to_le_bytes());
+    header.extend_from_slice(&(colors_used as u32).to_le_bytes());
+
+    header
+}
+
+// palette in (blue, green, red, reserved) order
+fn color_table(palette: RgbPalette) -> Vec<u8> {
+    palette
+        .iter()
+        .flat_map(|(r, g, b)| [*b, *g, *r, 0])
+        .collect()
+}
+
+fn pixel_array(data: Vec<Vec<ColorClass>>, dimension: (usize, usize), bit_depth: u8) -> Vec<u8> {
+    const ROW_ALIGN: usize = 4;
+
+    let classes_per_byte: usize = (u8::BITS as u8 / bit_depth) as usize;
+    let bytes_per_row = {
+        let packed_bytes = (dimension.0 + classes_per_byte - 1) / classes_per_byte;
+        (packed_bytes + ROW_ALIGN - 1) / ROW_ALIGN * ROW_ALIGN
+    };
+
+    // no pixel to write if width is 0 (also, chunks_mut() does not accept 0)
+    if bytes_per_row == 0 {
+        return Vec::new();
+    }
+
+    let mut img_data = vec![0_u8; bytes_per_row * dimension.1];
+    img_data
+        .chunks_mut(bytes_per_row)
+        // bmp rows are stored from bottom to top
+        .zip(data.iter().rev())
+        .for_each(|(row_dest, row_src)| {
+            row_dest
+                .iter_mut()
+                .zip(row_src.chunks(classes_per_byte))
+                // build 1 byte: pack each n pixels into 1 byte
+                .for_each(|(row_dest_elem, row_src_chunk)| {
+                    *row_dest_elem = row_src_chunk
+                        .iter()
+                        .map(|colorclass| *colorclass as u8)
+                        .enumerate()
+                        .fold(0_u8, |byte, (idx, class): (_, u8)| {
+                            (class << (u8::BITS as u8 - ((idx + 1) as u8 * bit_depth))) | byte
+                        });
+                });
+        });
+
+    img_data
+}
@@ -1,41 +1,223 @@
-use crate::global_type_helper::{ColorClass, RgbPalette, RgbPaletteHelper};
+use super::zlib;
+use crate::blockies::RenderedImage;
+use crate::global_type_helper::{PngFilter, PngOptions, RgbaPixel};
 
+use alloc::format;
 use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 
+// Convert rendered data to indexed or truecolor png data, according to the type of rendered data
+pub fn rendered_data_to_png(
+    rendered_image: RenderedImage,
+    dimension: (usize, usize),
+    is_compressed: bool,
+    options: PngOptions,
+    seed_info: (&[u8], usize),
+) -> Vec<u8> {
+    match rendered_image {
+        RenderedImage::Indexed(palette, bitmap) => indexed_data_to_png(
+            palette,
+            bitmap,
+            dimension,
+            is_compressed,
+            options,
+            seed_info,
+        ),
+        RenderedImage::Rgba(bitmap) => {
+            rgba_data_to_png(bitmap, dimension, is_compressed, options, seed_info)
+        }
+    }
+}
+
 // Convert indexed raw data to indexed png data
-pub fn indexed_data_to_png(
-    indexed_palette: RgbPalette,
-    indexed_bitmap: Vec<Vec<ColorClass>>,
+fn indexed_data_to_png(
+    indexed_palette: Vec<RgbaPixel>,
+    indexed_bitmap: Vec<Vec<u8>>,
+    dimension: (usize, usize),
+    is_compressed: bool,
+    options: PngOptions,
+    (seed, blockies_size): (&[u8], usize),
+) -> Vec<u8> {
+    const BIT_DEPTH_REDUCED: u8 = 1;
+    const BIT_DEPTH_FULL: u8 = 2;
+    const BIT_DEPTH_EXTENDED: u8 = 4;
+    const BIT_DEPTH_MAX: u8 = 8;
+    const MAX_COLORS_FULL: usize = 1 << BIT_DEPTH_FULL;
+    const MAX_COLORS_EXTENDED: usize = 1 << BIT_DEPTH_EXTENDED;
+
+    // new palette indices of each color, and palette colors to be written
+    let (bit_depth, palette_indices, palette_colors): (u8, Vec<u8>, Vec<RgbaPixel>) = {
+        let mut color_used: Vec<bool> = vec![false; indexed_palette.len()];
+        indexed_bitmap
+            .iter()
+            .flatten()
+            .for_each(|index| color_used[*index as usize] = true);
+
+        match (
+            options.reduce_bit_depth,
+            color_used.iter().filter(|used| **used).count(),
+        ) {
+            (true, 0..=2) => {
+                let mut palette_indices: Vec<u8> = vec![0; indexed_palette.len()];
+                let mut palette_colors: Vec<RgbaPixel> = Vec::new();
+                color_used
+                    .iter()
+                    .zip(indexed_palette.iter())
+                    .zip(palette_indices.iter_mut())
+                    .filter(|((used, _), _)| **used)
+                    .for_each(|((_, color), palette_index)| {
+                        *palette_index = palette_colors.len() as u8;
+                        palette_colors.push(*color);
+                    });
+                (BIT_DEPTH_REDUCED, palette_indices, palette_colors)
+            }
+            _ => (
+                match indexed_palette.len() {
+                    0..=MAX_COLORS_FULL => BIT_DEPTH_FULL,
+                    len if len <= MAX_COLORS_EXTENDED => BIT_DEPTH_EXTENDED,
+                    _ => BIT_DEPTH_MAX,
+                },
+                (0..=u8::MAX).take(indexed_palette.len()).collect(),
+                indexed_palette,
+            ),
+        }
+    };
+
+    let color_type = ColorType::Indexed;
+    let has_alpha = palette_colors
+        .iter()
+        .any(|(_, _, _, alpha)| *alpha != u8::MAX);
+
+    let mut palette_chunks: Vec<u8> = plte_chunk(&palette_colors);
+    if has_alpha {
+        palette_chunks.append(&mut trns_chunk(&palette_colors));
+    }
+
+    assemble_png(
+        ihdr_chunk(dimension, bit_depth, color_type, options.interlaced),
+        palette_chunks,
+        idat_chunk(
+            &indexed_bitmap,
+            dimension,
+            |indices: &[u8]| pack_indices(indices, bit_depth, &palette_indices),
+            color_type.bytes_per_pixel(),
+            is_compressed,
+            options.filter,
+            options.interlaced,
+        ),
+        options,
+        (seed, blockies_size),
+    )
+}
+
+// Convert rgba raw data to truecolor png data (with alpha channel only if needed)
+fn rgba_data_to_png(
+    rgba_bitmap: Vec<Vec<RgbaPixel>>,
     dimension: (usize, usize),
     is_compressed: bool,
+    options: PngOptions,
+    (seed, blockies_size): (&[u8], usize),
+) -> Vec<u8> {
+    const BIT_DEPTH: u8 = 8;
+
+    let color_type = match rgba_bitmap
+        .iter()
+        .flatten()
+        .any(|(_, _, _, alpha)| *alpha != u8::MAX)
+    {
+        true => ColorType::TruecolorAlpha,
+        false => ColorType::Truecolor,
+    };
+
+    assemble_png(
+        ihdr_chunk(dimension, BIT_DEPTH, color_type, options.interlaced),
+        Vec::new(),
+        idat_chunk(
+            &rgba_bitmap,
+            dimension,
+            |pixels: &[RgbaPixel]| {
+                pixels
+                    .iter()
+                    .flat_map(|(r, g, b, a)| [*r, *g, *b, *a])
+                    .enumerate()
+                    .filter(|(idx, _)| color_type == ColorType::TruecolorAlpha || idx % 4 != 3)
+                    .map(|(_, byte)| byte)
+                    .collect()
+            },
+            color_type.bytes_per_pixel(),
+            is_compressed,
+            options.filter,
+            options.interlaced,
+        ),
+        options,
+        (seed, blockies_size),
+    )
+}
+
+#[derive(PartialEq, Eq, Copy, Clone)]
+enum ColorType {
+    Truecolor = 2,
+    Indexed = 3,
+    TruecolorAlpha = 6,
+}
+
+impl ColorType {
+    // bytes per complete pixel, rounding up to one byte (for filters)
+    fn bytes_per_pixel(self) -> usize {
+        match self {
+            ColorType::Truecolor => 3,
+            ColorType::Indexed => 1,
+            ColorType::TruecolorAlpha => 4,
+        }
+    }
+}
+
+// build png from chunks, with additional chunks according to the options
+fn assemble_png(
+    mut ihdr_chunk: Vec<u8>,
+    mut palette_chunks: Vec<u8>,
+    mut idat_chunk: Vec<u8>,
+    options: PngOptions,
+    (seed, blockies_size): (&[u8], usize),
 ) -> Vec<u8> {
     const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n";
-    const BIT_DEPTH: u8 = 2;
 
-    // build png
     let mut ret_data: Vec<u8> = Vec::from(PNG_HEADER);
-    ret_data.append(&mut ihdr_chunk(dimension, BIT_DEPTH));
-    ret_data.append(&mut plte_chunk(indexed_palette));
-    ret_data.append(&mut idat_chunk(
-        indexed_bitmap,
-        dimension,
-        BIT_DEPTH,
-        is_compressed,
-    ));
+    ret_data.append(&mut ihdr_chunk);
+    if options.srgb {
+        ret_data.append(&mut srgb_chunk());
+        ret_data.append(&mut gama_chunk());
+    }
+    ret_data.append(&mut palette_chunks);
+    if let Some(dpi) = options.dpi {
+        ret_data.append(&mut phys_chunk(dpi));
+    }
+    if options.embed_seed {
+        ret_data.append(&mut seed_text_chunk(seed));
+        ret_data.append(&mut text_chunk(
+            b"Blockies Size",
+            format!("{}", blockies_size).as_bytes(),
+        ));
+    }
+    ret_data.append(&mut idat_chunk);
     ret_data.append(&mut iend_chunk());
 
     ret_data
 }
 
-fn ihdr_chunk(dimension: (usize, usize), bit_depth: u8) -> Vec<u8> {
+fn ihdr_chunk(
+    dimension: (usize, usize),
+    bit_depth: u8,
+    color_type: ColorType,
+    is_interlaced: bool,
+) -> Vec<u8> {
     const CHUNK_TYPE: &[u8] = b"IHDR";
 
-    const COLOR_TYPE: u8 = 3;
     const COMPRESSION_METHOD: u8 = 0;
     const FILTER_METHOD: u8 = 0;
-    const INTERLACE_METHOD: u8 = 0;
+    const INTERLACE_METHOD_NONE: u8 = 0;
+    const INTERLACE_METHOD_ADAM7: u8 = 1;
 
     let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
 
@@ -44,111 +226,349 @@ fn ihdr_chunk(dimension: (usize, usize), bit_depth: u8) -> Vec<u8> {
 
     chunk_data.extend_from_slice(&bit_depth.to_be_bytes());
     chunk_data.extend_from_slice(&[
-        COLOR_TYPE,
+        color_type as u8,
         COMPRESSION_METHOD,
         FILTER_METHOD,
-        INTERLACE_METHOD,
+        match is_interlaced {
+            true => INTERLACE_METHOD_ADAM7,
+            false => INTERLACE_METHOD_NONE,
+        },
     ]);
 
     pack_png_chunk(&mut chunk_data)
 }
 
-fn plte_chunk(palette: RgbPalette) -> Vec<u8> {
+fn plte_chunk(palette_colors: &[RgbaPixel]) -> Vec<u8> {
     const CHUNK_TYPE: &[u8] = b"PLTE";
 
     let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
 
-    chunk_data.extend_from_slice(&palette.serialize());
+    palette_colors
+        .iter()
+        .for_each(|(r, g, b, _)| chunk_data.extend_from_slice(&[*r, *g, *b]));
+
+    pack_png_chunk(&mut chunk_data)
+}
+
+// alpha of palette colors, omitting opaque colors at the end
+fn trns_chunk(palette_colors: &[RgbaPixel]) -> Vec<u8> {
+    const CHUNK_TYPE: &[u8] = b"tRNS";
+
+    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
+
+    let alpha_count = palette_colors
+        .iter()
+        .rposition(|(_, _, _, alpha)| *alpha != u8::MAX)
+        .map_or(0, |pos| pos + 1);
+    palette_colors[..alpha_count]
+        .iter()
+        .for_each(|(_, _, _, alpha)| chunk_data.push(*alpha));
+
+    pack_png_chunk(&mut chunk_data)
+}
+
+fn srgb_chunk() -> Vec<u8> {
+    const CHUNK_TYPE: &[u8] = b"sRGB";
+    const RENDERING_INTENT_PERCEPTUAL: u8 = 0;
+
+    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
+
+    chunk_data.push(RENDERING_INTENT_PERCEPTUAL);
+
+    pack_png_chunk(&mut chunk_data)
+}
+
+// gamma value for sRGB, for decoders which do not support sRGB chunk
+fn gama_chunk() -> Vec<u8> {
+    const CHUNK_TYPE: &[u8] = b"gAMA";
+    const SRGB_GAMMA: u32 = 45455; // 1 / 2.2, in units of 1 / 100000
+
+    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
+
+    chunk_data.extend_from_slice(&SRGB_GAMMA.to_be_bytes());
+
+    pack_png_chunk(&mut chunk_data)
+}
+
+fn phys_chunk(dpi: u32) -> Vec<u8> {
+    const CHUNK_TYPE: &[u8] = b"pHYs";
+    const UNIT_METER: u8 = 1;
+    const INCH_PER_METER_X10000: u64 = 393_701; // 1 m = 39.3701 inch
+
+    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
+
+    // pixels per meter, rounded
+    let ppm: u32 = ((dpi as u64 * INCH_PER_METER_X10000 + 5000) / 10000) as u32;
+    chunk_data.extend_from_slice(&ppm.to_be_bytes()); // x axis
+    chunk_data.extend_from_slice(&ppm.to_be_bytes()); // y axis
+    chunk_data.push(UNIT_METER);
+
+    pack_png_chunk(&mut chunk_data)
+}
+
+// text chunk of the seed: tEXt for ascii, iTXt for other utf-8, tEXt of hex string for binary
+fn seed_text_chunk(seed: &[u8]) -> Vec<u8> {
+    const KEYWORD: &[u8] = b"Blockies Seed";
+    const KEYWORD_HEX: &[u8] = b"Blockies Seed Hex";
+
+    match core::str::from_utf8(seed) {
+        Ok(seed_str) if seed_str.bytes().all(|byte| (b' '..=b'~').contains(&byte)) => {
+            text_chunk(KEYWORD, seed)
+        }
+        Ok(seed_str) if !seed_str.contains('\0') => itxt_chunk(KEYWORD, seed),
+        _ => {
+            const HEX_TABLE: &[u8; 16] = b"0123456789abcdef";
+            let seed_hex: Vec<u8> = seed
+                .iter()
+                .flat_map(|byte| {
+                    [
+                        HEX_TABLE[(byte >> 4) as usize],
+                        HEX_TABLE[(byte & 0xf) as usize],
+                    ]
+                })
+                .collect();
+            text_chunk(KEYWORD_HEX, &seed_hex)
+        }
+    }
+}
+
+// latin-1 text
+fn text_chunk(keyword: &[u8], text: &[u8]) -> Vec<u8> {
+    const CHUNK_TYPE: &[u8] = b"tEXt";
+    const NULL_SEPARATOR: u8 = 0;
+
+    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
+
+    chunk_data.extend_from_slice(keyword);
+    chunk_data.push(NULL_SEPARATOR);
+    chunk_data.extend_from_slice(text);
 
     pack_png_chunk(&mut chunk_data)
 }
 
-fn idat_chunk(
-    data: Vec<Vec<ColorClass>>,
+// uncompressed utf-8 text, without language tag
+fn itxt_chunk(keyword: &[u8], text: &[u8]) -> Vec<u8> {
+    const CHUNK_TYPE: &[u8] = b"iTXt";
+    const NULL_SEPARATOR: u8 = 0;
+    const COMPRESSION_FLAG: u8 = 0;
+    const COMPRESSION_METHOD: u8 = 0;
+
+    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
+
+    chunk_data.extend_from_slice(keyword);
+    chunk_data.extend_from_slice(&[
+        NULL_SEPARATOR,
+        COMPRESSION_FLAG,
+        COMPRESSION_METHOD,
+        NULL_SEPARATOR, // empty language tag
+        NULL_SEPARATOR, // empty translated keyword
+    ]);
+    chunk_data.extend_from_slice(text);
+
+    pack_png_chunk(&mut chunk_data)
+}
+
+fn idat_chunk<T: Copy, F: Fn(&[T]) -> Vec<u8>>(
+    data: &[Vec<T>],
     dimension: (usize, usize),
-    bit_depth: u8,
+    pack_scanline: F,
+    bytes_per_pixel: usize,
     is_compressed: bool,
+    filter: PngFilter,
+    is_interlaced: bool,
 ) -> Vec<u8> {
     const CHUNK_TYPE: &[u8] = b"IDAT";
-    const FILTER_TYPE: &[u8] = &[0];
+    const CANDIDATE_FILTERS: [PngFilter; 6] = [
+        PngFilter::None,
+        PngFilter::Sub,
+        PngFilter::Up,
+        PngFilter::Average,
+        PngFilter::Paeth,
+        PngFilter::Adaptive,
+    ];
 
-    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
+    // (x start, y start, x step, y step) of each pass
+    const ADAM7_PASSES: [(usize, usize, usize, usize); 7] = [
+        (0, 0, 8, 8),
+        (4, 0, 8, 8),
+        (0, 4, 4, 8),
+        (2, 0, 4, 4),
+        (0, 2, 2, 4),
+        (1, 0, 2, 2),
+        (0, 1, 1, 2),
+    ];
+    const NO_INTERLACE_PASS: [(usize, usize, usize, usize); 1] = [(0, 0, 1, 1)];
 
-    // build image data
-    let classes_per_byte: usize = (u8::BITS as u8 / bit_depth) as usize;
-    let bytes_per_scanline =
-        FILTER_TYPE.len() + ((dimension.0 + classes_per_byte - 1) / classes_per_byte);
-
-    let mut img_data = vec![0_u8; bytes_per_scanline * dimension.1];
-    img_data
-        .chunks_mut(bytes_per_scanline)
-        .zip(data.iter())
-        // build each scanline: filter type + packed pixel data
-        .for_each(|(scanline_dest, scanline_src)| {
-            let (scanline_dest_filter, scanline_dest_data) =
-                scanline_dest.split_at_mut(FILTER_TYPE.len());
-
-            // filter
-            scanline_dest_filter.copy_from_slice(FILTER_TYPE);
-
-            // packed data
-            scanline_dest_data
-                .iter_mut()
-                .zip(scanline_src.chunks(classes_per_byte))
-                // build 1 byte: pack each n pixels into 1 byte
-                .for_each(|(scanline_dest_elem, scanline_src_chunk)| {
-                    *scanline_dest_elem = scanline_src_chunk
-                        .iter()
-                        .map(|colorclass| *colorclass as u8)
-                        //.map(|colorclass| (*colorclass).into())
-                        .enumerate()
-                        .fold(0_u8, |byte, (idx, class): (_, u8)| {
-                            (class << (u8::BITS as u8 - ((idx + 1) as u8 * bit_depth))) | byte
-                        });
-                });
-        });
+    let mut chunk_data: Vec<u8> = CHUNK_TYPE.to_vec();
 
-    // form idat body from img_data, according to compression style
-    match is_compressed {
+    // build packed scanlines (without filter type) of each pass, picked from the pixel grid.
+    // a pass without any pixel does not have any scanline
+    let passes: &[(usize, usize, usize, usize)] = match is_interlaced {
+        true => &ADAM7_PASSES,
+        false => &NO_INTERLACE_PASS,
+    };
+    let pass_scanlines: Vec<Vec<Vec<u8>>> = passes
+        .iter()
+        .map(
+            |(x_start, y_start, x_step, y_step)| match dimension.0 > *x_start {
+                true => data
+                    .iter()
+                    .take(dimension.1)
+                    .skip(*y_start)
+                    .step_by(*y_step)
+                    .map(|row| {
+                        pack_scanline(
+                            &row.iter()
+                                .take(dimension.0)
+                                .skip(*x_start)
+                                .step_by(*x_step)
+                                .copied()
+                                .collect::<Vec<T>>(),
+                        )
+                    })
+                    .collect(),
+                false => Vec::new(),
+            },
+        )
+        .collect();
+    let filter_passes = |filter: PngFilter| -> Vec<u8> {
+        pass_scanlines
+            .iter()
+            .flat_map(|scanlines| filter_scanlines(scanlines, filter, bytes_per_pixel))
+            .collect()
+    };
+
+    // form idat body from scanlines, according to compression style
+    let mut zlib_data = match is_compressed {
         true => {
             #[cfg(feature = "compressed_png")]
             fn compress_png(input: &[u8]) -> Vec<u8> {
-                deflate::deflate_bytes_zlib(&input)
+                zlib::zlib_compressed(input)
             }
             #[cfg(not(feature = "compressed_png"))]
             fn compress_png(_input: &[u8]) -> Vec<u8> {
                 panic!("eth_blockies: Unexpected error: compressed_png is called when its feature is not enabled!");
             }
 
-            img_data = compress_png(&img_data);
-        }
-        false => {
-            const CM: u8 = 8;
-            const CINFO: u8 = 7;
-            const COMPRESSION_METHOD: &[u8] = &[CINFO << 4 | CM];
-            const FCHECK: u8 = 1;
-            const FDICT: u8 = 0;
-            const FLEVEL: u8 = 0;
-            const FLG: &[u8] = &[FLEVEL << 6 | FDICT << 5 | FCHECK];
-            const IS_LAST_BLOCK: &[u8] = &[1];
-
-            chunk_data.extend_from_slice(COMPRESSION_METHOD);
-            chunk_data.extend_from_slice(FLG);
-            chunk_data.extend_from_slice(IS_LAST_BLOCK);
-
-            let img_data_len: u16 = (img_data.len()) as u16;
-            chunk_data.extend_from_slice(&img_data_len.to_le_bytes());
-            chunk_data.extend_from_slice(&(!img_data_len).to_le_bytes());
-
-            img_data.extend_from_slice(&adler32(&img_data).to_be_bytes());
+            match filter {
+                PngFilter::Smallest => CANDIDATE_FILTERS
+                    .iter()
+                    .map(|filter| compress_png(&filter_passes(*filter)))
+                    .min_by_key(|zlib_data| zlib_data.len())
+                    .expect("png filter"),
+                filter => compress_png(&filter_passes(filter)),
+            }
         }
-    }
+        // filters do not change the size of uncompressed data
+        false => zlib::zlib_stored(&filter_passes(match filter {
+            PngFilter::Smallest => PngFilter::None,
+            filter => filter,
+        })),
+    };
 
-    chunk_data.append(&mut img_data);
+    chunk_data.append(&mut zlib_data);
 
     pack_png_chunk(&mut chunk_data)
 }
 
+// pack palette indices of a scanline into bytes: each n pixels into 1 byte
+fn pack_indices(indices: &[u8], bit_depth: u8, palette_indices: &[u8]) -> Vec<u8> {
+    let pixels_per_byte: usize = (u8::BITS as u8 / bit_depth) as usize;
+
+    let mut scanline: Vec<u8> = Vec::new();
+    indices
+        .iter()
+        .map(|index| palette_indices[*index as usize])
+        .enumerate()
+        .for_each(|(idx, index)| {
+            let idx_in_byte = idx % pixels_per_byte;
+            if idx_in_byte == 0 {
+                scanline.push(0);
+            }
+            if let Some(byte) = scanline.last_mut() {
+                *byte |= index << (u8::BITS as u8 - ((idx_in_byte + 1) as u8 * bit_depth));
+            }
+        });
+
+    scanline
+}
+
+// apply filter on each scanline, and prepend filter type to each one.
+// the corresponding byte on the left is the byte of the previous pixel
+// (the previous byte, if bit depth is less than 8)
+fn filter_scanlines(scanlines: &[Vec<u8>], filter: PngFilter, bytes_per_pixel: usize) -> Vec<u8> {
+    const SINGLE_FILTERS: [(PngFilter, u8); 5] = [
+        (PngFilter::None, 0),
+        (PngFilter::Sub, 1),
+        (PngFilter::Up, 2),
+        (PngFilter::Average, 3),
+        (PngFilter::Paeth, 4),
+    ];
+
+    let filter_scanline = |filter_type: u8, cur: &[u8], prev: Option<&Vec<u8>>| -> Vec<u8> {
+        core::iter::once(filter_type)
+            .chain(cur.iter().enumerate().map(|(idx, x)| {
+                let idx_left = idx.checked_sub(bytes_per_pixel);
+                let a = idx_left.map_or(0, |idx_left| cur[idx_left]);
+                let b = prev.map_or(0, |prev| prev[idx]);
+                let c = idx_left
+                    .zip(prev)
+                    .map_or(0, |(idx_left, prev)| prev[idx_left]);
+                let predictor = match filter_type {
+                    1 => a,
+                    2 => b,
+                    3 => ((a as u16 + b as u16) / 2) as u8,
+                    4 => paeth_predictor(a, b, c),
+                    _ => 0,
+                };
+                x.wrapping_sub(predictor)
+            }))
+            .collect()
+    };
+
+    scanlines
+        .iter()
+        .enumerate()
+        .flat_map(|(row, cur)| {
+            let prev = row.checked_sub(1).map(|row| &scanlines[row]);
+            match filter {
+                // minimum sum of absolute differences (as signed bytes)
+                PngFilter::Adaptive => SINGLE_FILTERS
+                    .iter()
+                    .map(|(_, filter_type)| filter_scanline(*filter_type, cur, prev))
+                    .min_by_key(|filtered| {
+                        filtered[1..]
+                            .iter()
+                            .map(|byte| (*byte as i8).unsigned_abs() as u32)
+                            .sum::<u32>()
+                    })
+                    .expect("png filter"),
+                filter => {
+                    let filter_type = SINGLE_FILTERS
+                        .iter()
+                        .find(|(single_filter, _)| *single_filter == filter)
+                        .map_or(0, |(_, filter_type)| *filter_type);
+                    filter_scanline(filter_type, cur, prev)
+                }
+            }
+        })
+        .collect()
+}
+
+fn paeth_predictor(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    match (pa <= pb && pa <= pc, pb <= pc) {
+        (true, _) => a,
+        (false, true) => b,
+        (false, false) => c,
+    }
+}
+
 fn iend_chunk() -> Vec<u8> {
     const CHUNK_TYPE: &[u8] = b"IEND";
 
@@ -158,7 +578,7 @@ fn iend_chunk() -> Vec<u8> {
 }
 
 fn pack_png_chunk(chunk_data: &mut Vec<u8>) -> Vec<u8> {
-    let crc_val = crc32(&chunk_data).to_be_bytes();
+    let crc_val = crc32(chunk_data).to_be_bytes();
     let mut chunk: Vec<u8> = Vec::new();
 
     chunk.extend_from_slice(&(chunk_data.len() as u32 - 4).to_be_bytes());
@@ -215,25 +635,6 @@ fn crc32(buf: &[u8]) -> u32 {
     }) ^ 0xffffffff_u32
 }
 
-fn adler32(buf: &[u8]) -> u32 {
-    const MOD_ADLER: u32 = 65521;
-    const MAX_CHUNK: usize = 5552;
-
-    let (a, b) = buf
-        .chunks(MAX_CHUNK)
-        .fold((1_u32, 0_u32), |(a, b), idx_data_tuple| {
-            let (a_new, b_new) = idx_data_tuple
-                .iter()
-                .fold((a, b), |(a_inner, b_inner), elem| {
-                    (a_inner + *elem as u32, a_inner + b_inner + *elem as u32)
-                });
-
-            (a_new % MOD_ADLER, b_new % MOD_ADLER)
-        });
-
-    b << 16 | a
-}
-
 fn base64(buf: &[u8]) -> Vec<u8> {
     const BASE64_TABLE: &[u8; 64] =
         b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
@@ -251,7 +652,7 @@ fn base64(buf: &[u8]) -> Vec<u8> {
         .zip(buf.chunks(SRC_BYTES_PER_UNIT))
         .for_each(|(bytes_dest, bytes_src)| {
             // read bytes_src
-            let bytes_src_opt = [bytes_src.get(0), bytes_src.get(1), bytes_src.get(2)];
+            let bytes_src_opt = [bytes_src.first(), bytes_src.get(1), bytes_src.get(2)];
 
             // ready source bits
             let bits = u32::from_be_bytes([
@@ -0,0 +1,209 @@
+use crate::global_type_helper::{ColorClass, Mask, RenderOptions, RgbPalette, RgbPixel};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Convert indexed raw data (not scaled) to svg data
+pub fn indexed_data_to_svg(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: Vec<Vec<ColorClass>>,
+    dimension: (usize, usize),
+) -> String {
+    svg_document(
+        dimension,
+        &blockies_element(indexed_palette, &indexed_bitmap, (0, 0), dimension),
+    )
+}
+
+// svg document of the given dimension, containing the elements
+pub fn svg_document(dimension: (usize, usize), elements: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" shape-rendering=\"crispEdges\">{elements}</svg>",
+        w = dimension.0,
+        h = dimension.1,
+        elements = elements,
+    )
+}
+
+// blockies scaled to the dimension, as a nested svg element.
+// each row of cells with the same color is merged into a single rectangle
+pub fn blockies_element(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: &[Vec<ColorClass>],
+    (x, y): (usize, usize),
+    dimension: (usize, usize),
+) -> String {
+    let size = indexed_bitmap.len();
+
+    let color_path = |class: ColorClass| -> String {
+        let runs: Vec<(usize, usize, usize)> = indexed_bitmap
+            .iter()
+            .enumerate()
+            .flat_map(|(row_idx, row)| {
+                // (row, start, length) of each run
+                row.iter().enumerate().fold(
+                    Vec::new(),
+                    |mut runs: Vec<(usize, usize, usize)>, (col, cell)| {
+                        match (*cell == class, runs.last_mut()) {
+                            (true, Some((_, start, len))) if *start + *len == col => *len += 1,
+                            (true, _) => runs.push((row_idx, col, 1)),
+                            (false, _) => (),
+                        };
+                        runs
+                    },
+                )
+            })
+            .collect();
+
+        match runs.is_empty() {
+            true => String::new(),
+            false => path_element(&runs, (0, 0), 1, indexed_palette[class]),
+        }
+    };
+
+    format!(
+        "<svg x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {s} {s}\" \
+         preserveAspectRatio=\"none\">{bg}{color}{spotcolor}</svg>",
+        x = x,
+        y = y,
+        w = dimension.0,
+        h = dimension.1,
+        s = size,
+        bg = rect_element((0, 0), (size, size), indexed_palette[ColorClass::BgColor]),
+        color = color_path(ColorClass::Color),
+        spotcolor = color_path(ColorClass::SpotColor),
+    )
+}
+
+// blockies with border and padding, as svg elements clipped by the mask.
+// blockies is placed at the icon area, inside the border and padding filled with the bgcolor,
+// and the clip path of the mask is referred by the id (which should be unique in a html)
+pub fn rendered_element(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: &[Vec<ColorClass>],
+    dimension: (usize, usize),
+    (icon_offset, icon_dim): ((usize, usize), (usize, usize)),
+    options: RenderOptions,
+    mask_id: &str,
+) -> String {
+    let border_width = options.border_width as f64;
+
+    let elements: String = [
+        // padding
+        match (icon_offset, icon_dim) == ((0, 0), dimension) {
+            true => String::new(),
+            false => shape_element(
+                options.mask,
+                dimension,
+                border_width,
+                &format!(
+                    " fill=\"{}\"",
+                    hex_color(indexed_palette[ColorClass::BgColor])
+                ),
+            ),
+        },
+        blockies_element(indexed_palette, indexed_bitmap, icon_offset, icon_dim),
+        // border: stroke centered on the shape shrunk by the half of its width
+        match options.border_width {
+            0 => String::new(),
+            _ => shape_element(
+                options.mask,
+                dimension,
+                border_width / 2.0,
+                &format!(
+                    " fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"",
+                    hex_color(options.border_color),
+                    options.border_width
+                ),
+            ),
+        },
+    ]
+    .concat();
+
+    match options.mask {
+        Mask::None => elements,
+        mask => format!(
+            "<defs><clipPath id=\"{id}\">{shape}</clipPath></defs>\
+             <g clip-path=\"url(#{id})\">{elements}</g>",
+            id = mask_id,
+            shape = shape_element(mask, dimension, 0.0, ""),
+            elements = elements,
+        ),
+    }
+}
+
+// shape of the mask filling the dimension, shrunk by inset pixels, with the attributes
+fn shape_element(mask: Mask, dimension: (usize, usize), inset: f64, attributes: &str) -> String {
+    let (width, height) = (
+        (dimension.0 as f64 - 2.0 * inset).max(0.0),
+        (dimension.1 as f64 - 2.0 * inset).max(0.0),
+    );
+
+    match mask {
+        Mask::Circle => format!(
+            "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"{}/>",
+            dimension.0 as f64 / 2.0,
+            dimension.1 as f64 / 2.0,
+            width / 2.0,
+            height / 2.0,
+            attributes
+        ),
+        Mask::None | Mask::RoundedSquare(_) => format!(
+            "<rect x=\"{x}\" y=\"{x}\" width=\"{}\" height=\"{}\"{}{}/>",
+            width,
+            height,
+            match mask {
+                Mask::RoundedSquare(radius) if radius as f64 > inset => {
+                    format!(" rx=\"{r}\" ry=\"{r}\"", r = radius as f64 - inset)
+                }
+                _ => String::new(),
+            },
+            attributes,
+            x = inset,
+        ),
+    }
+}
+
+// filled rectangle element
+pub fn rect_element((x, y): (usize, usize), dimension: (usize, usize), color: RgbPixel) -> String {
+    format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        x,
+        y,
+        dimension.0,
+        dimension.1,
+        hex_color(color)
+    )
+}
+
+// filled path element of horizontal runs (y, x, length) from the offset,
+// with each cell scaled to (scale x scale)
+pub fn path_element(
+    runs: &[(usize, usize, usize)],
+    (offset_x, offset_y): (usize, usize),
+    scale: usize,
+    color: RgbPixel,
+) -> String {
+    let path_data: String = runs
+        .iter()
+        .map(|(y, x, len)| {
+            format!(
+                "M{} {}h{}v{}h-{}z",
+                offset_x + x * scale,
+                offset_y + y * scale,
+                len * scale,
+                scale,
+                len * scale
+            )
+        })
+        .collect();
+
+    format!("<path d=\"{}\" fill=\"{}\"/>", path_data, hex_color(color))
+}
+
+// color in the form of '#rrggbb'
+pub fn hex_color((r, g, b): RgbPixel) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
@@ -0,0 +1,81 @@
+use crate::global_type_helper::{ColorClass, RgbPalette};
+
+use alloc::vec::Vec;
+
+// Get (width, height) in the 16-bit fields of tga header, or None if it does not fit
+pub fn tga_dimension(dimension: (usize, usize)) -> Option<(u16, u16)> {
+    Some((
+        u16::try_from(dimension.0).ok()?,
+        u16::try_from(dimension.1).ok()?,
+    ))
+}
+
+// Convert indexed raw data to (uncompressed) color-mapped tga data
+pub fn indexed_data_to_tga(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: Vec<Vec<ColorClass>>,
+    dimension: (u16, u16),
+) -> Vec<u8> {
+    // build tga
+    let mut ret_data: Vec<u8> = tga_header(dimension, indexed_palette.len());
+    ret_data.append(&mut color_map(indexed_palette));
+    ret_data.append(&mut image_data(indexed_bitmap, dimension));
+    ret_data.append(&mut tga_footer());
+
+    ret_data
+}
+
+fn tga_header(dimension: (u16, u16), color_map_len: usize) -> Vec<u8> {
+    const ID_LENGTH: u8 = 0;
+    const COLOR_MAP_TYPE: u8 = 1;
+    const IMAGE_TYPE: u8 = 1; // uncompressed, color-mapped
+    const COLOR_MAP_FIRST_ENTRY: u16 = 0;
+    const COLOR_MAP_ENTRY_SIZE: u8 = 24;
+    const ORIGIN: (u16, u16) = (0, 0);
+    const PIXEL_DEPTH: u8 = 8;
+    const IMAGE_DESCRIPTOR: u8 = 0b0010_0000; // top-left origin, no alpha bits
+
+    let mut header: Vec<u8> = [ID_LENGTH, COLOR_MAP_TYPE, IMAGE_TYPE].to_vec();
+
+    header.extend_from_slice(&COLOR_MAP_FIRST_ENTRY.to_le_bytes());
+    header.extend_from_slice(&(color_map_len as u16).to_le_bytes());
+    header.extend_from_slice(&COLOR_MAP_ENTRY_SIZE.to_le_bytes());
+
+    header.extend_from_slice(&ORIGIN.0.to_le_bytes());
+    header.extend_from_slice(&ORIGIN.1.to_le_bytes());
+    header.extend_from_slice(&dimension.0.to_le_bytes());
+    header.extend_from_slice(&dimension.1.to_le_bytes());
+    header.extend_from_slice(&[PIXEL_DEPTH, IMAGE_DESCRIPTOR]);
+
+    header
+}
+
+// palette in (blue, green, red) order
+fn color_map(palette: RgbPalette) -> Vec<u8> {
+    palette.iter().flat_map(|(r, g, b)| [*b, *g, *r]).collect()
+}
+
+fn image_data(data: Vec<Vec<ColorClass>>, dimension: (u16, u16)) -> Vec<u8> {
+    let mut img_data: Vec<u8> = Vec::with_capacity(dimension.0 as usize * dimension.1 as usize);
+
+    data.iter().for_each(|row| {
+        img_data.extend(row.iter().map(|colorclass| *colorclass as u8));
+    });
+
+    img_data
+}
+
+// TGA 2.0 footer, without extension and developer area
+fn tga_footer() -> Vec<u8> {
+    const EXTENSION_AREA_OFFSET: u32 = 0;
+    const DEVELOPER_DIRECTORY_OFFSET: u32 = 0;
+    const SIGNATURE: &[u8] = b"TRUEVISION-XFILE.\0";
+
+    let mut footer: Vec<u8> = Vec::new();
+
+    footer.extend_from_slice(&EXTENSION_AREA_OFFSET.to_le_bytes());
+    footer.extend_from_slice(&DEVELOPER_DIRECTORY_OFFSET.to_le_bytes());
+    footer.extend_from_slice(SIGNATURE);
+
+    footer
+}
@@ -0,0 +1,362 @@
+use super::bit_writer::BitWriter;
+use super::huffman::{canonical_codes, code_length_tokens, huffman_code_lengths};
+use crate::global_type_helper::{ColorClass, RgbPalette};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+// Get (width, height) fitting in the 14-bit fields (of size - 1) of VP8L header, or None if not
+pub fn webp_dimension(dimension: (usize, usize)) -> Option<(u16, u16)> {
+    const MAX_SIZE: usize = 1 << 14;
+
+    let fits = |size: usize| (1..=MAX_SIZE).contains(&size).then_some(size as u16);
+    Some((fits(dimension.0)?, fits(dimension.1)?))
+}
+
+// Convert indexed raw data to lossless webp (VP8L) data
+// https://developers.google.com/speed/webp/docs/webp_lossless_bitstream_specification
+pub fn indexed_data_to_webp(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: Vec<Vec<ColorClass>>,
+    dimension: (u16, u16),
+) -> Vec<u8> {
+    let mut vp8l_data = vp8l_bitstream(indexed_palette, indexed_bitmap, dimension);
+
+    // build webp
+    let mut ret_data: Vec<u8> = riff_header(vp8l_data.len());
+    ret_data.append(&mut vp8l_chunk(&mut vp8l_data));
+
+    ret_data
+}
+
+const CHUNK_HEADER_BYTES: usize = 8;
+
+fn riff_header(vp8l_data_len: usize) -> Vec<u8> {
+    const RIFF: &[u8] = b"RIFF";
+    const WEBP: &[u8] = b"WEBP";
+
+    let mut header: Vec<u8> = RIFF.to_vec();
+
+    // 'WEBP' + VP8L chunk (with padding)
+    let riff_size = WEBP.len() + CHUNK_HEADER_BYTES + vp8l_data_len + vp8l_data_len % 2;
+    header.extend_from_slice(&(riff_size as u32).to_le_bytes());
+    header.extend_from_slice(WEBP);
+
+    header
+}
+
+fn vp8l_chunk(vp8l_data: &mut Vec<u8>) -> Vec<u8> {
+    const CHUNK_TYPE: &[u8] = b"VP8L";
+
+    let mut chunk: Vec<u8> = CHUNK_TYPE.to_vec();
+
+    chunk.extend_from_slice(&(vp8l_data.len() as u32).to_le_bytes());
+    if vp8l_data.len() % 2 == 1 {
+        vp8l_data.push(0);
+    }
+    chunk.append(vp8l_data);
+
+    chunk
+}
+
+fn vp8l_bitstream(
+    palette: RgbPalette,
+    data: Vec<Vec<ColorClass>>,
+    dimension: (u16, u16),
+) -> Vec<u8> {
+    const SIGNATURE: u8 = 0x2f;
+    const ALPHA_IS_USED: u32 = 0;
+    const VERSION: u32 = 0;
+    const TRANSFORM_PRESENT: u32 = 1;
+    const COLOR_INDEXING_TRANSFORM: u32 = 3;
+    const NO_MORE_TRANSFORM: u32 = 0;
+    const NO_COLOR_CACHE: u32 = 0;
+    const NO_META_PREFIX_CODES: u32 = 0;
+
+    let mut writer = BitWriter::new();
+    writer.write_bits(SIGNATURE as u32, 8);
+
+    // image header
+    writer.write_bits(dimension.0 as u32 - 1, 14);
+    writer.write_bits(dimension.1 as u32 - 1, 14);
+    writer.write_bits(ALPHA_IS_USED, 1);
+    writer.write_bits(VERSION, 3);
+
+    // color indexing transform, with the palette as its color table
+    writer.write_bits(TRANSFORM_PRESENT, 1);
+    writer.write_bits(COLOR_INDEXING_TRANSFORM, 2);
+    writer.write_bits(palette.len() as u32 - 1, 8);
+    writer.write_bits(NO_COLOR_CACHE, 1);
+    write_entropy_coded_image(&mut writer, &color_table_pixels(palette), 0);
+    writer.write_bits(NO_MORE_TRANSFORM, 1);
+
+    // main image: palette indices bundled into green of each packed pixel
+    let (packed_width, packed_pixels) = bundle_pixels(data, dimension.0 as usize);
+    writer.write_bits(NO_COLOR_CACHE, 1);
+    writer.write_bits(NO_META_PREFIX_CODES, 1);
+    write_entropy_coded_image(&mut writer, &packed_pixels, packed_width);
+
+    writer.finish()
+}
+
+// (alpha, red, green, blue)
+type Argb = [u8; 4];
+const OPAQUE: u8 = u8::MAX;
+
+// palette, with each color subtracted from the previous one
+fn color_table_pixels(palette: RgbPalette) -> Vec<Argb> {
+    palette
+        .iter()
+        .fold(
+            (Vec::with_capacity(palette.len()), [0_u8; 4]),
+            |(mut pixels, prev), (r, g, b)| {
+                let color = [OPAQUE, *r, *g, *b];
+                pixels.push(core::array::from_fn(|idx| {
+                    color[idx].wrapping_sub(prev[idx])
+                }));
+                (pixels, color)
+            },
+        )
+        .0
+}
+
+// pack 4 palette indices (2 bits each) into green of one pixel
+fn bundle_pixels(data: Vec<Vec<ColorClass>>, width: usize) -> (usize, Vec<Argb>) {
+    const BITS_PER_INDEX: usize = 2;
+    const INDICES_PER_PIXEL: usize = u8::BITS as usize / BITS_PER_INDEX;
+
+    let packed_width = (width + INDICES_PER_PIXEL - 1) / INDICES_PER_PIXEL;
+
+    let packed_pixels = data
+        .iter()
+        .flat_map(|row| {
+            row.chunks(INDICES_PER_PIXEL).map(|chunk| {
+                let green = chunk.iter().enumerate().fold(0_u8, |green, (idx, class)| {
+                    green | (*class as u8) << (idx * BITS_PER_INDEX)
+                });
+                [OPAQUE, 0, green, 0]
+            })
+        })
+        .collect();
+
+    (packed_width, packed_pixels)
+}
+
+// a literal pixel, or a backward reference of (length, distance code)
+enum Token {
+    Literal(Argb),
+    Copy(usize, usize),
+}
+
+const NUM_LITERAL_CODES: usize = 256;
+const NUM_LENGTH_CODES: usize = 24;
+const NUM_DISTANCE_CODES: usize = 40;
+const MAX_COPY_LENGTH: usize = 4096;
+
+// encode pixels with LZ77 backward references (if width is given) + prefix codes
+fn write_entropy_coded_image(writer: &mut BitWriter, pixels: &[Argb], width: usize) {
+    let tokens = lz77_tokens(pixels, width);
+
+    // histograms of: green (+ length), red, blue, alpha, distance
+    let mut histograms: [Vec<u32>; 5] = [
+        vec![0; NUM_LITERAL_CODES + NUM_LENGTH_CODES],
+        vec![0; NUM_LITERAL_CODES],
+        vec![0; NUM_LITERAL_CODES],
+        vec![0; NUM_LITERAL_CODES],
+        vec![0; NUM_DISTANCE_CODES],
+    ];
+    tokens.iter().for_each(|token| match token {
+        Token::Literal([a, r, g, b]) => {
+            histograms[0][*g as usize] += 1;
+            histograms[1][*r as usize] += 1;
+            histograms[2][*b as usize] += 1;
+            histograms[3][*a as usize] += 1;
+        }
+        Token::Copy(length, dist_code) => {
+            histograms[0][NUM_LITERAL_CODES + prefix_encode(*length).0 as usize] += 1;
+            histograms[4][prefix_encode(*dist_code).0 as usize] += 1;
+        }
+    });
+
+    let codes: Vec<Vec<(u32, u8)>> = histograms
+        .iter()
+        .map(|histogram| write_prefix_code(writer, histogram))
+        .collect();
+
+    tokens.iter().for_each(|token| match token {
+        Token::Literal([a, r, g, b]) => {
+            [(0, g), (1, r), (2, b), (3, a)]
+                .iter()
+                .for_each(|(code_idx, value)| {
+                    let (code, len) = codes[*code_idx][**value as usize];
+                    writer.write_bits(code, len);
+                });
+        }
+        Token::Copy(length, dist_code) => {
+            [(0, NUM_LITERAL_CODES, length), (4, 0, dist_code)]
+                .iter()
+                .for_each(|(code_idx, symbol_offset, value)| {
+                    let (prefix, extra_bits, extra_bits_value) = prefix_encode(**value);
+                    let (code, len) = codes[*code_idx][symbol_offset + prefix as usize];
+                    writer.write_bits(code, len);
+                    writer.write_bits(extra_bits_value, extra_bits);
+                });
+        }
+    });
+}
+
+// greedy matching against the upper pixel or the left pixel only,
+// which covers the long runs in scaled blockies
+fn lz77_tokens(pixels: &[Argb], width: usize) -> Vec<Token> {
+    const MIN_COPY_LENGTH: usize = 3;
+    // distance codes of the neighborhood: (0, 1) -> upper, (1, 0) -> left
+    const UPPER_DIST_CODE: usize = 1;
+    const LEFT_DIST_CODE: usize = 2;
+
+    let match_length = |pos: usize, distance: usize| -> usize {
+        match distance > 0 && distance <= pos {
+            true => pixels[pos..]
+                .iter()
+                .zip(pixels[(pos - distance)..].iter())
+                .take(MAX_COPY_LENGTH)
+                .take_while(|(cur, ref_pixel)| cur == ref_pixel)
+                .count(),
+            false => 0,
+        }
+    };
+
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut pos = 0;
+    while pos < pixels.len() {
+        let best_match = match width {
+            0 => None,
+            _ => [(UPPER_DIST_CODE, width), (LEFT_DIST_CODE, 1)]
+                .iter()
+                .map(|(dist_code, distance)| (match_length(pos, *distance), *dist_code))
+                .max_by_key(|(length, _)| *length)
+                .filter(|(length, _)| *length >= MIN_COPY_LENGTH),
+        };
+
+        match best_match {
+            Some((length, dist_code)) => {
+                tokens.push(Token::Copy(length, dist_code));
+                pos += length;
+            }
+            None => {
+                tokens.push(Token::Literal(pixels[pos]));
+                pos += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+// (prefix symbol, extra bits count, extra bits value) of length or distance
+fn prefix_encode(value: usize) -> (u32, u8, u32) {
+    let value = value as u32 - 1;
+    match value < 4 {
+        true => (value, 0, 0),
+        false => {
+            let highest_bit = u32::BITS - 1 - value.leading_zeros();
+            let second_highest_bit = (value >> (highest_bit - 1)) & 1;
+            let extra_bits = highest_bit - 1;
+            (
+                2 * highest_bit + second_highest_bit,
+                extra_bits as u8,
+                value & ((1 << extra_bits) - 1),
+            )
+        }
+    }
+}
+
+// write a prefix code from the histogram, then return (code, length) of each symbol
+fn write_prefix_code(writer: &mut BitWriter, histogram: &[u32]) -> Vec<(u32, u8)> {
+    const MAX_CODE_LENGTH: u8 = 15;
+
+    let used_symbols: Vec<usize> = (0..histogram.len())
+        .filter(|symbol| histogram[*symbol] > 0)
+        .collect();
+
+    match used_symbols.as_slice() {
+        // simple code: 1 or 2 symbols, of 8-bit range
+        [] | [_] | [_, _] if used_symbols.iter().all(|s| *s < NUM_LITERAL_CODES) => {
+            const SIMPLE_CODE: u32 = 1;
+
+            let symbols: &[usize] = match used_symbols.is_empty() {
+                true => &[0],
+                false => &used_symbols,
+            };
+
+            writer.write_bits(SIMPLE_CODE, 1);
+            writer.write_bits(symbols.len() as u32 - 1, 1);
+            match symbols[0] < 2 {
+                true => writer.write_bits(0, 1).write_bits(symbols[0] as u32, 1),
+                false => writer.write_bits(1, 1).write_bits(symbols[0] as u32, 8),
+            };
+            if let Some(symbol) = symbols.get(1) {
+                writer.write_bits(*symbol as u32, 8);
+            }
+
+            // one symbol: zero-length code, two symbols: 1-bit codes
+            let mut code_lengths = vec![0_u8; histogram.len()];
+            if symbols.len() == 2 {
+                symbols.iter().for_each(|s| code_lengths[*s] = 1);
+            }
+            canonical_codes(&code_lengths)
+        }
+
+        // normal code
+        _ => {
+            const NORMAL_CODE: u32 = 0;
+
+            let code_lengths = huffman_code_lengths(histogram, MAX_CODE_LENGTH);
+
+            writer.write_bits(NORMAL_CODE, 1);
+            write_code_lengths(writer, &code_lengths);
+
+            canonical_codes(&code_lengths)
+        }
+    }
+}
+
+// write code lengths of a normal prefix code, using the code length code
+fn write_code_lengths(writer: &mut BitWriter, code_lengths: &[u8]) {
+    const MAX_CODE_LENGTH_CODE_LENGTH: u8 = 7;
+    const CODE_LENGTH_CODE_ORDER: [usize; 19] = [
+        17, 18, 0, 1, 2, 3, 4, 5, 16, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    ];
+    const USE_FULL_ALPHABET: u32 = 0;
+
+    let tokens = code_length_tokens(code_lengths);
+
+    let code_length_code_lengths = {
+        let mut histogram = [0_u32; CODE_LENGTH_CODE_ORDER.len()];
+        tokens
+            .iter()
+            .for_each(|(symbol, _, _)| histogram[*symbol as usize] += 1);
+        huffman_code_lengths(&histogram, MAX_CODE_LENGTH_CODE_LENGTH)
+    };
+    let code_length_codes = canonical_codes(&code_length_code_lengths);
+
+    let num_code_lengths = CODE_LENGTH_CODE_ORDER
+        .iter()
+        .rposition(|symbol| code_length_code_lengths[*symbol] != 0)
+        .map_or(0, |pos| pos + 1)
+        .max(4);
+    writer.write_bits(num_code_lengths as u32 - 4, 4);
+    CODE_LENGTH_CODE_ORDER[..num_code_lengths]
+        .iter()
+        .for_each(|symbol| {
+            writer.write_bits(code_length_code_lengths[*symbol] as u32, 3);
+        });
+
+    writer.write_bits(USE_FULL_ALPHABET, 1);
+    tokens
+        .iter()
+        .for_each(|(symbol, extra_bits, extra_bits_value)| {
+            let (code, len) = code_length_codes[*symbol as usize];
+            writer.write_bits(code, len);
+            writer.write_bits(*extra_bits_value, *extra_bits);
+        });
+}
@@ -1,2 +1,18 @@
+#[cfg(any(feature = "compressed_png", feature = "webp_lossless"))]
+mod bit_writer;
+#[cfg(any(feature = "compressed_png", feature = "webp_lossless"))]
+mod huffman;
+mod zlib;
+
 pub mod ansi_seq;
+pub mod indexed_bmp;
 pub mod indexed_png;
+pub mod indexed_svg;
+pub mod indexed_tga;
+#[cfg(feature = "webp_lossless")]
+pub mod indexed_webp;
+#[cfg(feature = "qr")]
+pub mod qr_code;
+pub mod rgb_ppm;
+#[cfg(feature = "qoi")]
+pub mod rgb_qoi;
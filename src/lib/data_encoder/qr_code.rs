@@ -0,0 +1,619 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+// QR code symbol, as dark (true) / light (false) modules
+pub type QrModules = Vec<Vec<bool>>;
+
+const MIN_VERSION: usize = 1;
+const MAX_VERSION: usize = 40;
+
+// error correction level H (recovers ~30% of codewords)
+const ECC_LEVEL_H_BITS: u32 = 0b10;
+
+// (ecc codewords per block, number of blocks) of each version, on error correction level H
+const ECC_BLOCKS_LEVEL_H: [(usize, usize); MAX_VERSION + 1] = [
+    (0, 0), // (unused)
+    (17, 1),
+    (28, 1),
+    (22, 2),
+    (16, 4),
+    (22, 4),
+    (28, 4),
+    (26, 5),
+    (26, 6),
+    (24, 8),
+    (28, 8),
+    (24, 11),
+    (28, 11),
+    (22, 16),
+    (24, 16),
+    (24, 18),
+    (30, 16),
+    (28, 19),
+    (28, 21),
+    (26, 25),
+    (28, 25),
+    (30, 25),
+    (24, 34),
+    (30, 30),
+    (30, 32),
+    (30, 35),
+    (30, 37),
+    (30, 40),
+    (30, 42),
+    (30, 45),
+    (30, 48),
+    (30, 51),
+    (30, 54),
+    (30, 57),
+    (30, 60),
+    (30, 63),
+    (30, 66),
+    (30, 70),
+    (30, 74),
+    (30, 77),
+    (30, 81),
+];
+
+// Encode data as QR code in byte mode, on error correction level H.
+// the smallest version (1-40) for the data is used, with the mask of the lowest penalty
+pub fn qr_code_modules(data: &[u8]) -> QrModules {
+    let version = (MIN_VERSION..=MAX_VERSION)
+        .find(|version| data_bits(data.len(), *version) <= num_data_codewords(*version) * 8)
+        .expect("qr_code_modules()");
+
+    let codewords = add_ecc_and_interleave(&data_codewords(data, version), version);
+
+    let (function_modules, is_function) = function_patterns(version);
+    let data_modules = place_codewords(function_modules, &is_function, &codewords);
+
+    // apply each mask, and keep the one with the lowest penalty
+    (0..8)
+        .map(|mask| {
+            let mut modules = apply_mask(data_modules.clone(), &is_function, mask);
+            draw_format_bits(&mut modules, mask);
+            modules
+        })
+        .min_by_key(penalty_score)
+        .expect("qr_code_modules()")
+}
+
+fn size_of(version: usize) -> usize {
+    version * 4 + 17
+}
+
+// mode indicator + character count + data
+fn data_bits(data_len: usize, version: usize) -> usize {
+    4 + char_count_bits(version) + data_len * 8
+}
+
+fn char_count_bits(version: usize) -> usize {
+    match version {
+        1..=9 => 8,
+        _ => 16,
+    }
+}
+
+// number of modules for codewords (data + ecc) of each version, excluding function patterns
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            // version information
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn num_data_codewords(version: usize) -> usize {
+    let (ecc_len, num_blocks) = ECC_BLOCKS_LEVEL_H[version];
+    num_raw_data_modules(version) / 8 - ecc_len * num_blocks
+}
+
+// data codewords in byte mode, with terminator and pad bytes
+fn data_codewords(data: &[u8], version: usize) -> Vec<u8> {
+    const MODE_BYTE: u32 = 0b0100;
+    const PAD_BYTES: [u8; 2] = [0xec, 0x11];
+
+    let capacity_bits = num_data_codewords(version) * 8;
+
+    let mut bits: Vec<bool> = Vec::with_capacity(capacity_bits);
+    let mut push_bits = |value: u32, len: usize| {
+        (0..len)
+            .rev()
+            .for_each(|idx| bits.push((value >> idx) & 1 != 0));
+    };
+    push_bits(MODE_BYTE, 4);
+    push_bits(data.len() as u32, char_count_bits(version));
+    data.iter().for_each(|byte| push_bits(*byte as u32, 8));
+    // terminator (up to 4 bits), then pad to a byte boundary
+    let terminator_len = (capacity_bits - bits.len()).min(4);
+    bits.resize(bits.len() + terminator_len, false);
+    bits.resize((bits.len() + 7) / 8 * 8, false);
+
+    let mut codewords: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte_bits| {
+            byte_bits
+                .iter()
+                .fold(0, |byte, bit| (byte << 1) | (*bit as u8))
+        })
+        .collect();
+    codewords.extend(
+        PAD_BYTES
+            .iter()
+            .cycle()
+            .take(capacity_bits / 8 - codewords.len()),
+    );
+
+    codewords
+}
+
+// split data into blocks, append ecc to each block, and interleave all blocks
+fn add_ecc_and_interleave(data: &[u8], version: usize) -> Vec<u8> {
+    let (ecc_len, num_blocks) = ECC_BLOCKS_LEVEL_H[version];
+    let raw_codewords = num_raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+    let divisor = reed_solomon_divisor(ecc_len);
+
+    // (data, ecc) of each block. long blocks have one more data codeword
+    let mut data_offset = 0;
+    let blocks: Vec<(&[u8], Vec<u8>)> = (0..num_blocks)
+        .map(|block_idx| {
+            let data_len = short_block_len - ecc_len + (block_idx >= num_short_blocks) as usize;
+            let block_data = &data[data_offset..data_offset + data_len];
+            data_offset += data_len;
+            (block_data, reed_solomon_remainder(block_data, &divisor))
+        })
+        .collect();
+
+    let max_data_len = short_block_len - ecc_len + 1;
+    (0..max_data_len)
+        .flat_map(|idx| blocks.iter().filter_map(move |(data, _)| data.get(idx)))
+        .chain((0..ecc_len).flat_map(|idx| blocks.iter().map(move |(_, ecc)| &ecc[idx])))
+        .copied()
+        .collect()
+}
+
+// multiplication in GF(2^8), modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    (0..8).rev().fold(0_u8, |z, idx| {
+        let z = (z << 1) ^ ((z >> 7) * 0x1d);
+        z ^ (((y >> idx) & 1) * x)
+    })
+}
+
+// coefficients of the generator polynomial of the degree (highest degree first, except x^degree)
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result: Vec<u8> = vec![0; degree];
+    result[degree - 1] = 1;
+
+    let mut root: u8 = 1;
+    (0..degree).for_each(|_| {
+        // multiply by (x - root)
+        (0..degree).for_each(|idx| {
+            result[idx] = gf_multiply(result[idx], root);
+            if idx + 1 < degree {
+                result[idx] ^= result[idx + 1];
+            }
+        });
+        root = gf_multiply(root, 0x02);
+    });
+
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    data.iter()
+        .fold(vec![0; divisor.len()], |mut result, byte| {
+            let factor = byte ^ result.remove(0);
+            result.push(0);
+            result
+                .iter_mut()
+                .zip(divisor.iter())
+                .for_each(|(elem, coef)| *elem ^= gf_multiply(*coef, factor));
+            result
+        })
+}
+
+// modules of function patterns (except format bits), and whether each module is a function module
+fn function_patterns(version: usize) -> (QrModules, Vec<Vec<bool>>) {
+    let size = size_of(version);
+    let mut modules: QrModules = vec![vec![false; size]; size];
+    let mut is_function: Vec<Vec<bool>> = vec![vec![false; size]; size];
+    let mut set_function = |(x, y): (usize, usize), is_dark: bool| {
+        modules[y][x] = is_dark;
+        is_function[y][x] = true;
+    };
+
+    // timing patterns
+    (0..size).for_each(|idx| {
+        set_function((6, idx), idx % 2 == 0);
+        set_function((idx, 6), idx % 2 == 0);
+    });
+
+    // finder patterns with separators, on 3 corners
+    [(3, 3), (size - 4, 3), (3, size - 4)]
+        .iter()
+        .for_each(|(center_x, center_y)| {
+            (-4_isize..=4).for_each(|dy| {
+                (-4_isize..=4).for_each(|dx| {
+                    let (x, y) = (*center_x as isize + dx, *center_y as isize + dy);
+                    if (0..size as isize).contains(&x) && (0..size as isize).contains(&y) {
+                        let dist = dx.abs().max(dy.abs());
+                        set_function((x as usize, y as usize), dist != 2 && dist != 4);
+                    }
+                })
+            })
+        });
+
+    // alignment patterns, except the ones overlapping finder patterns
+    let align_positions = alignment_positions(version);
+    let last = align_positions.len().saturating_sub(1);
+    align_positions
+        .iter()
+        .enumerate()
+        .for_each(|(i, center_x)| {
+            align_positions
+                .iter()
+                .enumerate()
+                .for_each(|(j, center_y)| {
+                    let is_on_finder = [(0, 0), (0, last), (last, 0)].contains(&(i, j));
+                    if !is_on_finder {
+                        (-2_isize..=2).for_each(|dy| {
+                            (-2_isize..=2).for_each(|dx| {
+                                set_function(
+                                    (
+                                        (*center_x as isize + dx) as usize,
+                                        (*center_y as isize + dy) as usize,
+                                    ),
+                                    dx.abs().max(dy.abs()) != 1,
+                                );
+                            })
+                        })
+                    }
+                })
+        });
+
+    // reserve format bits (drawn after masking), with the dark module
+    (0..9).filter(|idx| *idx != 6).for_each(|idx| {
+        set_function((8, idx), false);
+        set_function((idx, 8), false);
+    });
+    (0..8).for_each(|idx| {
+        set_function((size - 1 - idx, 8), false);
+        set_function((8, size - 1 - idx), false);
+    });
+    set_function((8, size - 8), true);
+
+    // version information
+    if version >= 7 {
+        let bits = version_bits(version);
+        (0..18).for_each(|idx| {
+            let is_dark = (bits >> idx) & 1 != 0;
+            let (a, b) = (size - 11 + idx % 3, idx / 3);
+            set_function((a, b), is_dark);
+            set_function((b, a), is_dark);
+        });
+    }
+
+    (modules, is_function)
+}
+
+// center coordinates of alignment patterns on each axis
+fn alignment_positions(version: usize) -> Vec<usize> {
+    match version {
+        1 => Vec::new(),
+        _ => {
+            let num_align = version / 7 + 2;
+            let step = (version * 8 + num_align * 3 + 5) / (num_align * 4 - 4) * 2;
+            let last = size_of(version) - 7;
+            core::iter::once(6)
+                .chain((0..num_align - 1).rev().map(|idx| last - idx * step))
+                .collect()
+        }
+    }
+}
+
+// place codewords in zigzag order from the bottom-right corner, skipping function modules
+fn place_codewords(
+    mut modules: QrModules,
+    is_function: &[Vec<bool>],
+    codewords: &[u8],
+) -> QrModules {
+    let size = modules.len();
+    let mut bits = codewords
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |idx| (byte >> idx) & 1 != 0));
+
+    // pairs of columns from the right, skipping the vertical timing pattern
+    let mut right = size - 1;
+    loop {
+        if right == 6 {
+            right = 5;
+        }
+        let is_upward = (right + 1) & 2 == 0;
+        (0..size).for_each(|vert| {
+            let y = match is_upward {
+                true => size - 1 - vert,
+                false => vert,
+            };
+            (0..2).for_each(|dx| {
+                let x = right - dx;
+                if !is_function[y][x] {
+                    // remainder bits are light
+                    modules[y][x] = bits.next().unwrap_or(false);
+                }
+            });
+        });
+
+        match right {
+            1 => break,
+            _ => right -= 2,
+        }
+    }
+
+    modules
+}
+
+fn apply_mask(mut modules: QrModules, is_function: &[Vec<bool>], mask: u32) -> QrModules {
+    modules.iter_mut().enumerate().for_each(|(y, row)| {
+        row.iter_mut().enumerate().for_each(|(x, module)| {
+            let is_inverted = match mask {
+                0 => (x + y) % 2 == 0,
+                1 => y % 2 == 0,
+                2 => x % 3 == 0,
+                3 => (x + y) % 3 == 0,
+                4 => (x / 3 + y / 2) % 2 == 0,
+                5 => x * y % 2 + x * y % 3 == 0,
+                6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+            };
+            *module ^= is_inverted && !is_function[y][x];
+        })
+    });
+
+    modules
+}
+
+// version (6 bits) with its BCH(18, 6) code
+fn version_bits(version: usize) -> u32 {
+    let remainder = (0..12).fold(version as u32, |rem, _| (rem << 1) ^ ((rem >> 11) * 0x1f25));
+    (version as u32) << 12 | remainder
+}
+
+// ecc level and mask (5 bits) with its BCH(15, 5) code, xored with the fixed pattern
+fn format_bits(mask: u32) -> u32 {
+    let data = ECC_LEVEL_H_BITS << 3 | mask;
+    let remainder = (0..10).fold(data, |rem, _| (rem << 1) ^ ((rem >> 9) * 0x537));
+    (data << 10 | remainder) ^ 0x5412
+}
+
+fn draw_format_bits(modules: &mut QrModules, mask: u32) {
+    let size = modules.len();
+    let bits = format_bits(mask);
+    let bit = |idx: usize| (bits >> idx) & 1 != 0;
+
+    // around the top-left finder pattern
+    (0..6).for_each(|idx| modules[idx][8] = bit(idx));
+    modules[7][8] = bit(6);
+    modules[8][8] = bit(7);
+    modules[8][7] = bit(8);
+    (9..15).for_each(|idx| modules[8][14 - idx] = bit(idx));
+
+    // around the top-right and bottom-left finder patterns
+    (0..8).for_each(|idx| modules[8][size - 1 - idx] = bit(idx));
+    (8..15).for_each(|idx| modules[size - 15 + idx][8] = bit(idx));
+}
+
+// penalty of the symbol, to choose the mask
+fn penalty_score(modules: &QrModules) -> usize {
+    const PENALTY_RUN: usize = 3;
+    const PENALTY_BLOCK: usize = 3;
+    const PENALTY_FINDER_LIKE: usize = 40;
+    const PENALTY_BALANCE: usize = 10;
+    const FINDER_LIKE: [bool; 11] = [
+        true, false, true, true, true, false, true, false, false, false, false,
+    ];
+
+    let size = modules.len();
+    let columns: QrModules = (0..size)
+        .map(|x| modules.iter().map(|row| row[x]).collect())
+        .collect();
+
+    let line_penalty = |line: &Vec<bool>| -> usize {
+        // runs of 5 or more modules of the same color
+        let runs = run_lengths(line)
+            .into_iter()
+            .filter(|len| *len >= 5)
+            .map(|len| PENALTY_RUN + len - 5)
+            .sum::<usize>();
+        // 1:1:3:1:1 pattern with 4 light modules on either side
+        let finder_like = line
+            .windows(FINDER_LIKE.len())
+            .filter(|window| {
+                window.iter().eq(FINDER_LIKE.iter()) || window.iter().eq(FINDER_LIKE.iter().rev())
+            })
+            .count()
+            * PENALTY_FINDER_LIKE;
+        runs + finder_like
+    };
+
+    let lines = modules
+        .iter()
+        .chain(columns.iter())
+        .map(line_penalty)
+        .sum::<usize>();
+
+    // 2x2 blocks of the same color
+    let blocks = modules
+        .windows(2)
+        .map(|rows| {
+            (0..size - 1)
+                .filter(|x| {
+                    let color = rows[0][*x];
+                    rows[0][x + 1] == color && rows[1][*x] == color && rows[1][x + 1] == color
+                })
+                .count()
+        })
+        .sum::<usize>()
+        * PENALTY_BLOCK;
+
+    // imbalance of dark and light modules, per 5% from 50%
+    let total = size * size;
+    let dark = modules.iter().flatten().filter(|module| **module).count();
+    let balance = ((dark * 20).abs_diff(total * 10) + total - 1) / total;
+    let balance = balance.saturating_sub(1) * PENALTY_BALANCE;
+
+    lines + blocks + balance
+}
+
+// lengths of runs of the same color
+fn run_lengths(line: &[bool]) -> Vec<usize> {
+    line.iter()
+        .fold(Vec::new(), |mut runs: Vec<(bool, usize)>, module| {
+            match runs.last_mut() {
+                Some((color, len)) if color == module => *len += 1,
+                _ => runs.push((*module, 1)),
+            };
+            runs
+        })
+        .into_iter()
+        .map(|(_, len)| len)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reed_solomon_generator() {
+        // x^7 + 127x^6 + 122x^5 + 154x^4 + 164x^3 + 11x^2 + 68x + 117
+        assert_eq!(reed_solomon_divisor(7), [127, 122, 154, 164, 11, 68, 117]);
+    }
+
+    #[test]
+    fn reed_solomon_ecc_codewords() {
+        // ISO/IEC 18004 Annex I: "01234567" on version 1-M
+        let data = [
+            0x10, 0x20, 0x0c, 0x56, 0x61, 0x80, 0xec, 0x11, 0xec, 0x11, 0xec, 0x11, 0xec, 0x11,
+            0xec, 0x11,
+        ];
+        assert_eq!(
+            reed_solomon_remainder(&data, &reed_solomon_divisor(10)),
+            [0xa5, 0x24, 0xd4, 0xc1, 0xed, 0x36, 0xc7, 0x87, 0x2c, 0x55]
+        );
+
+        // "HELLO WORLD" on version 1-M
+        let data = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+        assert_eq!(
+            reed_solomon_remainder(&data, &reed_solomon_divisor(10)),
+            [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+        );
+    }
+
+    #[test]
+    fn byte_mode_data_codewords() {
+        // mode 0100, count 00000010, 'h' 01101000, 'i' 01101001, terminator 0000, then pad bytes
+        assert_eq!(
+            data_codewords(b"hi", 1),
+            [0x40, 0x26, 0x86, 0x90, 0xec, 0x11, 0xec, 0x11, 0xec]
+        );
+    }
+
+    #[test]
+    fn version_by_byte_capacity() {
+        // max bytes of each version on level H: 1 => 7, 2 => 14, 10 => 119, 40 => 1273
+        [
+            (7, 1),
+            (8, 2),
+            (14, 2),
+            (15, 3),
+            (119, 10),
+            (120, 11),
+            (1273, 40),
+        ]
+        .iter()
+        .for_each(|(data_len, version)| {
+            assert_eq!(
+                qr_code_modules(&vec![0; *data_len]).len(),
+                size_of(*version),
+                "{}",
+                data_len
+            )
+        });
+    }
+
+    #[test]
+    fn format_and_version_information() {
+        // format information of level H, for each mask
+        assert_eq!(
+            (0..8).map(format_bits).collect::<Vec<u32>>(),
+            [
+                0b001011010001001,
+                0b001001110111110,
+                0b001110011100111,
+                0b001100111010000,
+                0b000011101100010,
+                0b000001001010101,
+                0b000110100001100,
+                0b000100000111011,
+            ]
+        );
+
+        [(7, 0x07c94), (8, 0x085bc), (21, 0x15683), (40, 0x28c69)]
+            .iter()
+            .for_each(|(version, bits)| assert_eq!(version_bits(*version), *bits, "{}", version));
+    }
+
+    #[test]
+    fn function_patterns_of_symbol() {
+        let modules = qr_code_modules(b"0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc");
+        let size = modules.len();
+        assert_eq!(size, size_of(5));
+
+        // finder patterns on 3 corners
+        const FINDER: [&str; 7] = [
+            "#######", "#.....#", "#.###.#", "#.###.#", "#.###.#", "#.....#", "#######",
+        ];
+        [(0, 0), (size - 7, 0), (0, size - 7)]
+            .iter()
+            .for_each(|(left, top)| {
+                FINDER.iter().enumerate().for_each(|(y, row)| {
+                    row.bytes().enumerate().for_each(|(x, module)| {
+                        assert_eq!(modules[top + y][left + x], module == b'#')
+                    })
+                })
+            });
+
+        // timing patterns, and the dark module
+        (8..size - 8).for_each(|idx| {
+            assert_eq!(modules[6][idx], idx % 2 == 0);
+            assert_eq!(modules[idx][6], idx % 2 == 0);
+        });
+        assert!(modules[size - 8][8]);
+
+        // both copies of format information are of the same mask
+        let first_copy = (0..6)
+            .map(|idx| modules[idx][8])
+            .chain([modules[7][8], modules[8][8], modules[8][7]])
+            .chain((9..15).map(|idx| modules[8][14 - idx]));
+        let second_copy = (0..8)
+            .map(|idx| modules[8][size - 1 - idx])
+            .chain((8..15).map(|idx| modules[size - 15 + idx][8]));
+        fn to_bits(copy: impl Iterator<Item = bool>) -> u32 {
+            copy.enumerate()
+                .fold(0, |bits, (idx, bit)| bits | (bit as u32) << idx)
+        }
+        let bits = to_bits(first_copy);
+        assert_eq!(bits, to_bits(second_copy));
+        assert!((0..8).map(format_bits).any(|format| format == bits));
+    }
+}
@@ -0,0 +1,27 @@
+use crate::global_type_helper::{ColorClass, RgbPalette};
+
+use alloc::format;
+use alloc::vec::Vec;
+
+// Convert indexed raw data to binary (P6) ppm data
+pub fn indexed_data_to_ppm(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: Vec<Vec<ColorClass>>,
+    dimension: (usize, usize),
+) -> Vec<u8> {
+    const MAX_VAL: u8 = u8::MAX;
+
+    let header = format!("P6\n{} {}\n{}\n", dimension.0, dimension.1, MAX_VAL);
+
+    // build ppm
+    let mut ret_data: Vec<u8> = Vec::with_capacity(header.len() + dimension.0 * dimension.1 * 3);
+    ret_data.extend_from_slice(header.as_bytes());
+    indexed_bitmap.iter().for_each(|row| {
+        ret_data.extend(row.iter().flat_map(|class| {
+            let (r, g, b) = indexed_palette[class];
+            [r, g, b]
+        }));
+    });
+
+    ret_data
+}
@@ -0,0 +1,118 @@
+use crate::global_type_helper::{ColorClass, RgbPalette, RgbPixel};
+
+use alloc::vec::Vec;
+
+// Convert indexed raw data to qoi data
+// https://qoiformat.org/qoi-specification.pdf
+pub fn indexed_data_to_qoi(
+    indexed_palette: RgbPalette,
+    indexed_bitmap: Vec<Vec<ColorClass>>,
+    dimension: (usize, usize),
+) -> Vec<u8> {
+    // build qoi
+    let mut ret_data: Vec<u8> = qoi_header(dimension);
+    ret_data.append(&mut qoi_data(
+        indexed_bitmap
+            .iter()
+            .flat_map(|row| row.iter().map(|class| indexed_palette[class])),
+    ));
+    ret_data.append(&mut qoi_end_marker());
+
+    ret_data
+}
+
+fn qoi_header(dimension: (usize, usize)) -> Vec<u8> {
+    const MAGIC: &[u8] = b"qoif";
+    const CHANNELS: u8 = 3; // RGB
+    const COLORSPACE: u8 = 0; // sRGB with linear alpha
+
+    let mut header: Vec<u8> = MAGIC.to_vec();
+
+    header.extend_from_slice(&(dimension.0 as u32).to_be_bytes());
+    header.extend_from_slice(&(dimension.1 as u32).to_be_bytes());
+    header.extend_from_slice(&[CHANNELS, COLORSPACE]);
+
+    header
+}
+
+fn qoi_data<P: Iterator<Item = RgbPixel>>(pixels: P) -> Vec<u8> {
+    const QOI_OP_INDEX: u8 = 0b00_000000;
+    const QOI_OP_DIFF: u8 = 0b01_000000;
+    const QOI_OP_LUMA: u8 = 0b10_000000;
+    const QOI_OP_RUN: u8 = 0b11_000000;
+    const QOI_OP_RGB: u8 = 0b1111_1110;
+    const MAX_RUN: u8 = 62;
+
+    // alpha is always opaque (255), so it is only used for hashing
+    fn index_position((r, g, b): RgbPixel) -> usize {
+        (r as usize * 3 + g as usize * 5 + b as usize * 7 + 255 * 11) % 64
+    }
+
+    let mut data: Vec<u8> = Vec::new();
+    // initial values of the decoder are (0, 0, 0, 0), which never match any opaque pixel
+    let mut seen_pixels: [Option<RgbPixel>; 64] = [None; 64];
+    let mut prev_pixel: RgbPixel = (0, 0, 0);
+    let mut run: u8 = 0;
+
+    pixels.for_each(|pixel| {
+        if pixel == prev_pixel {
+            run += 1;
+            if run == MAX_RUN {
+                data.push(QOI_OP_RUN | (run - 1));
+                run = 0;
+            }
+            return;
+        }
+
+        if run > 0 {
+            data.push(QOI_OP_RUN | (run - 1));
+            run = 0;
+        }
+
+        let index_pos = index_position(pixel);
+        match seen_pixels[index_pos] == Some(pixel) {
+            true => data.push(QOI_OP_INDEX | index_pos as u8),
+            false => {
+                seen_pixels[index_pos] = Some(pixel);
+
+                let dr = pixel.0.wrapping_sub(prev_pixel.0) as i8;
+                let dg = pixel.1.wrapping_sub(prev_pixel.1) as i8;
+                let db = pixel.2.wrapping_sub(prev_pixel.2) as i8;
+                let (dr_dg, db_dg) = (dr.wrapping_sub(dg), db.wrapping_sub(dg));
+
+                let is_small_diff = [dr, dg, db].iter().all(|d| (-2..=1).contains(d));
+                let is_luma_diff = (-32..=31).contains(&dg)
+                    && (-8..=7).contains(&dr_dg)
+                    && (-8..=7).contains(&db_dg);
+
+                match (is_small_diff, is_luma_diff) {
+                    (true, _) => data.push(
+                        QOI_OP_DIFF
+                            | ((dr + 2) as u8) << 4
+                            | ((dg + 2) as u8) << 2
+                            | (db + 2) as u8,
+                    ),
+                    (false, true) => data.extend_from_slice(&[
+                        QOI_OP_LUMA | (dg + 32) as u8,
+                        ((dr_dg + 8) as u8) << 4 | (db_dg + 8) as u8,
+                    ]),
+                    (false, false) => {
+                        data.extend_from_slice(&[QOI_OP_RGB, pixel.0, pixel.1, pixel.2])
+                    }
+                }
+            }
+        }
+
+        prev_pixel = pixel;
+    });
+
+    if run > 0 {
+        data.push(QOI_OP_RUN | (run - 1));
+    }
+
+    data
+}
+
+fn qoi_end_marker() -> Vec<u8> {
+    [0, 0, 0, 0, 0, 0, 0, 1].to_vec()
+}
@@ -0,0 +1,912 @@
+#[cfg(feature = "compressed_png")]
+use super::bit_writer::BitWriter;
+#[cfg(feature = "compressed_png")]
+use super::huffman::{canonical_codes, code_length_tokens, huffman_code_lengths};
+
+#[cfg(feature = "compressed_png")]
+use alloc::vec;
+use alloc::vec::Vec;
+
+// https://www.rfc-editor.org/rfc/rfc1950
+// https://www.rfc-editor.org/rfc/rfc1951
+
+// Wrap data into zlib stream, with uncompressed (stored) blocks only
+pub fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const FLEVEL_FASTEST: u8 = 0;
+
+    let mut zlib_data: Vec<u8> = zlib_header(FLEVEL_FASTEST).to_vec();
+    zlib_data.append(&mut stored_blocks(data));
+    zlib_data.extend_from_slice(&adler32(data).to_be_bytes());
+
+    zlib_data
+}
+
+// deflate stored blocks of the data, the last one marked as final
+fn stored_blocks(data: &[u8]) -> Vec<u8> {
+    const BTYPE_STORED: u8 = 0b00;
+    const MAX_STORED_LEN: usize = u16::MAX as usize;
+
+    let mut blocks: Vec<u8> = Vec::new();
+
+    // an empty input still needs one (final) block
+    let block_count = data.len().saturating_sub(1) / MAX_STORED_LEN + 1;
+    (0..block_count)
+        .map(|idx| &data[(idx * MAX_STORED_LEN)..data.len().min((idx + 1) * MAX_STORED_LEN)])
+        .enumerate()
+        .for_each(|(idx, block)| {
+            let is_last_block = (idx + 1 == block_count) as u8;
+            blocks.push(BTYPE_STORED << 1 | is_last_block);
+
+            let block_len: u16 = block.len() as u16;
+            blocks.extend_from_slice(&block_len.to_le_bytes());
+            blocks.extend_from_slice(&(!block_len).to_le_bytes());
+            blocks.extend_from_slice(block);
+        });
+
+    blocks
+}
+
+// Compress data into zlib stream, with the smallest of single fixed or dynamic huffman blocks
+// (or stored blocks, if none of them is smaller).
+// Blockies scanlines are mostly runs of a byte and repeats of the upper scanline,
+// so the tokens are chosen by the bit cost of each code (optimal parse), rather than the longest match.
+// The optimal parse keeps match candidates of every position, so larger inputs get the lazy parse only
+#[cfg(feature = "compressed_png")]
+pub fn zlib_compressed(data: &[u8]) -> Vec<u8> {
+    const FLEVEL_MAXIMUM: u8 = 3;
+    const DYNAMIC_ITERATIONS: usize = 3;
+    const MAX_OPTIMAL_PARSE_LEN: usize = 1 << 17;
+
+    let mut best_block = stored_blocks(data);
+    let mut keep_smaller = |block: Vec<u8>| {
+        if block.len() < best_block.len() {
+            best_block = block;
+        }
+    };
+
+    // lazy parse, with the longest match at each position
+    let tokens = lazy_tokens(data);
+    keep_smaller(deflate_block(&tokens, BlockType::FixedHuffman));
+    keep_smaller(deflate_block(&tokens, BlockType::DynamicHuffman));
+
+    if data.len() <= MAX_OPTIMAL_PARSE_LEN {
+        let matches = match_candidates(data);
+        let mut costs = Vec::with_capacity(data.len() + 1);
+
+        // fixed huffman block
+        let fixed_code_lengths = fixed_code_lengths();
+        let mut tokens = cheapest_tokens(data, &matches, &fixed_code_lengths, &mut costs);
+        keep_smaller(deflate_block(&tokens, BlockType::FixedHuffman));
+
+        // dynamic huffman blocks, with the codes from the previous tokens
+        for _ in 0..DYNAMIC_ITERATIONS {
+            let code_lengths = dynamic_code_lengths(&tokens);
+            tokens = cheapest_tokens(data, &matches, &code_lengths, &mut costs);
+            keep_smaller(deflate_block(&tokens, BlockType::DynamicHuffman));
+        }
+    }
+
+    let mut zlib_data: Vec<u8> = zlib_header(FLEVEL_MAXIMUM).to_vec();
+    zlib_data.append(&mut best_block);
+    zlib_data.extend_from_slice(&adler32(data).to_be_bytes());
+
+    zlib_data
+}
+
+fn zlib_header(flevel: u8) -> [u8; 2] {
+    const CM: u8 = 8;
+    const CINFO: u8 = 7;
+    const CMF: u8 = CINFO << 4 | CM;
+    const FDICT: u8 = 0;
+
+    // (CMF * 256 + FLG) should be a multiple of 31
+    let flg_without_check = flevel << 6 | FDICT << 5;
+    let fcheck = (31 - (((CMF as u16) << 8 | flg_without_check as u16) % 31)) % 31;
+
+    [CMF, flg_without_check | fcheck as u8]
+}
+
+// a literal byte, or a backward reference of (length, distance)
+#[cfg(feature = "compressed_png")]
+enum Token {
+    Literal(u8),
+    Copy(usize, usize),
+}
+
+#[cfg(feature = "compressed_png")]
+enum BlockType {
+    FixedHuffman,
+    DynamicHuffman,
+}
+
+#[cfg(feature = "compressed_png")]
+const NUM_LITLEN_CODES: usize = 286;
+#[cfg(feature = "compressed_png")]
+const NUM_DISTANCE_CODES: usize = 30;
+#[cfg(feature = "compressed_png")]
+const END_OF_BLOCK: usize = 256;
+#[cfg(feature = "compressed_png")]
+const MIN_COPY_LENGTH: usize = 3;
+#[cfg(feature = "compressed_png")]
+const MAX_COPY_LENGTH: usize = 258;
+#[cfg(feature = "compressed_png")]
+const WINDOW_SIZE: usize = 32768;
+
+// build a final deflate block of the tokens
+#[cfg(feature = "compressed_png")]
+fn deflate_block(tokens: &[Token], block_type: BlockType) -> Vec<u8> {
+    const IS_LAST_BLOCK: u32 = 1;
+    const BTYPE_FIXED_HUFFMAN: u32 = 0b01;
+    const BTYPE_DYNAMIC_HUFFMAN: u32 = 0b10;
+
+    let mut writer = BitWriter::new();
+    writer.write_bits(IS_LAST_BLOCK, 1);
+
+    let (litlen_code_lengths, distance_code_lengths) = match block_type {
+        BlockType::FixedHuffman => {
+            writer.write_bits(BTYPE_FIXED_HUFFMAN, 2);
+            fixed_code_lengths()
+        }
+        BlockType::DynamicHuffman => {
+            let code_lengths = dynamic_code_lengths(tokens);
+            writer.write_bits(BTYPE_DYNAMIC_HUFFMAN, 2);
+            write_dynamic_code_lengths(&mut writer, &code_lengths);
+            code_lengths
+        }
+    };
+    let (litlen_codes, distance_codes) = (
+        canonical_codes(&litlen_code_lengths),
+        canonical_codes(&distance_code_lengths),
+    );
+
+    tokens.iter().for_each(|token| match token {
+        Token::Literal(byte) => {
+            let (code, len) = litlen_codes[*byte as usize];
+            writer.write_bits(code, len);
+        }
+        Token::Copy(length, distance) => {
+            let (length_symbol, extra_bits, extra_bits_value) = length_encode(*length);
+            let (code, len) = litlen_codes[length_symbol];
+            writer.write_bits(code, len);
+            writer.write_bits(extra_bits_value, extra_bits);
+
+            let (distance_symbol, extra_bits, extra_bits_value) = distance_encode(*distance);
+            let (code, len) = distance_codes[distance_symbol];
+            writer.write_bits(code, len);
+            writer.write_bits(extra_bits_value, extra_bits);
+        }
+    });
+    let (code, len) = litlen_codes[END_OF_BLOCK];
+    writer.write_bits(code, len);
+
+    writer.finish()
+}
+
+// (literal/length, distance) code lengths of the fixed huffman codes
+#[cfg(feature = "compressed_png")]
+fn fixed_code_lengths() -> (Vec<u8>, Vec<u8>) {
+    // 2 more literal/length codes (never used) take part in the fixed code construction
+    const NUM_FIXED_LITLEN_CODES: usize = 288;
+    const DISTANCE_CODE_LENGTH: u8 = 5;
+
+    let litlen_code_lengths = (0..NUM_FIXED_LITLEN_CODES)
+        .map(|symbol| match symbol {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        })
+        .collect();
+
+    (
+        litlen_code_lengths,
+        vec![DISTANCE_CODE_LENGTH; NUM_DISTANCE_CODES],
+    )
+}
+
+// (literal/length, distance) code lengths of huffman codes, built from the tokens
+#[cfg(feature = "compressed_png")]
+fn dynamic_code_lengths(tokens: &[Token]) -> (Vec<u8>, Vec<u8>) {
+    const MAX_CODE_LENGTH: u8 = 15;
+
+    let mut litlen_histogram = vec![0_u32; NUM_LITLEN_CODES];
+    let mut distance_histogram = vec![0_u32; NUM_DISTANCE_CODES];
+    tokens.iter().for_each(|token| match token {
+        Token::Literal(byte) => litlen_histogram[*byte as usize] += 1,
+        Token::Copy(length, distance) => {
+            litlen_histogram[length_encode(*length).0] += 1;
+            distance_histogram[distance_encode(*distance).0] += 1;
+        }
+    });
+    litlen_histogram[END_OF_BLOCK] += 1;
+
+    (
+        huffman_code_lengths(&litlen_histogram, MAX_CODE_LENGTH),
+        huffman_code_lengths(&distance_histogram, MAX_CODE_LENGTH),
+    )
+}
+
+// write code lengths of a dynamic huffman block header, using the code length code
+#[cfg(feature = "compressed_png")]
+fn write_dynamic_code_lengths(
+    writer: &mut BitWriter,
+    (litlen_code_lengths, distance_code_lengths): &(Vec<u8>, Vec<u8>),
+) {
+    const MAX_CODE_LENGTH_CODE_LENGTH: u8 = 7;
+    const CODE_LENGTH_CODE_ORDER: [usize; 19] = [
+        16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+    ];
+    const MIN_LITLEN_CODES: usize = END_OF_BLOCK + 1;
+    const MIN_DISTANCE_CODES: usize = 1;
+    const MIN_CODE_LENGTH_CODES: usize = 4;
+
+    // trim unused codes at the end
+    let used_len = |code_lengths: &[u8], min_len: usize| -> usize {
+        code_lengths
+            .iter()
+            .rposition(|len| *len != 0)
+            .map_or(0, |pos| pos + 1)
+            .max(min_len)
+    };
+    let num_litlen_codes = used_len(litlen_code_lengths, MIN_LITLEN_CODES);
+    let num_distance_codes = used_len(distance_code_lengths, MIN_DISTANCE_CODES);
+
+    // both code lengths are encoded as a single sequence
+    let tokens = code_length_tokens(
+        &[
+            &litlen_code_lengths[..num_litlen_codes],
+            &distance_code_lengths[..num_distance_codes],
+        ]
+        .concat(),
+    );
+
+    let code_length_code_lengths = {
+        let mut histogram = [0_u32; CODE_LENGTH_CODE_ORDER.len()];
+        tokens
+            .iter()
+            .for_each(|(symbol, _, _)| histogram[*symbol as usize] += 1);
+        huffman_code_lengths(&histogram, MAX_CODE_LENGTH_CODE_LENGTH)
+    };
+    let code_length_codes = canonical_codes(&code_length_code_lengths);
+    let num_code_length_codes = CODE_LENGTH_CODE_ORDER
+        .iter()
+        .rposition(|symbol| code_length_code_lengths[*symbol] != 0)
+        .map_or(0, |pos| pos + 1)
+        .max(MIN_CODE_LENGTH_CODES);
+
+    writer.write_bits((num_litlen_codes - MIN_LITLEN_CODES) as u32, 5);
+    writer.write_bits((num_distance_codes - MIN_DISTANCE_CODES) as u32, 5);
+    writer.write_bits((num_code_length_codes - MIN_CODE_LENGTH_CODES) as u32, 4);
+    CODE_LENGTH_CODE_ORDER[..num_code_length_codes]
+        .iter()
+        .for_each(|symbol| {
+            writer.write_bits(code_length_code_lengths[*symbol] as u32, 3);
+        });
+
+    tokens
+        .iter()
+        .for_each(|(symbol, extra_bits, extra_bits_value)| {
+            let (code, len) = code_length_codes[*symbol as usize];
+            writer.write_bits(code, len);
+            writer.write_bits(*extra_bits_value, *extra_bits);
+        });
+}
+
+// hash chains over positions in the window, on the byte run at the position:
+// (byte, run length, and 2 bytes after the run), which is the 3-byte prefix out of runs.
+// positions in a run are chained apart by the rest of the run, to be found by the same runs
+#[cfg(feature = "compressed_png")]
+struct HashChains<'a> {
+    data: &'a [u8],
+    head: Vec<usize>,
+    prev: Vec<usize>,
+    // (start, end) of the last run looked up
+    run: (usize, usize),
+}
+
+#[cfg(feature = "compressed_png")]
+impl<'a> HashChains<'a> {
+    const HASH_BITS: u32 = 14;
+    const NO_POS: usize = usize::MAX;
+
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            head: vec![Self::NO_POS; 1 << Self::HASH_BITS],
+            prev: vec![Self::NO_POS; WINDOW_SIZE],
+            run: (0, 0),
+        }
+    }
+
+    fn hash(&mut self, pos: usize) -> usize {
+        if !(self.run.0..self.run.1).contains(&pos) {
+            let run_len = self.data[pos..]
+                .iter()
+                .take_while(|byte| **byte == self.data[pos])
+                .count();
+            self.run = (pos, pos + run_len);
+        }
+        let run_end = self.run.1;
+        let run_len = (run_end - pos).min(MAX_COPY_LENGTH);
+        let byte_at = |pos: usize| self.data.get(pos).map_or(0, |byte| *byte as u64);
+
+        let key = (run_len as u64) << 24
+            | byte_at(pos) << 16
+            | byte_at(run_end) << 8
+            | byte_at(run_end + 1);
+        (key.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (u64::BITS - Self::HASH_BITS)) as usize
+    }
+
+    fn insert(&mut self, pos: usize) {
+        if pos + MIN_COPY_LENGTH > self.data.len() {
+            return;
+        }
+        let hash_val = self.hash(pos);
+        self.prev[pos % WINDOW_SIZE] = self.head[hash_val];
+        self.head[hash_val] = pos;
+    }
+
+    // distances to the chained positions of the same hash as `pos`, nearest first
+    fn distances(
+        &mut self,
+        pos: usize,
+        max_chain_length: usize,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let first = match pos + MIN_COPY_LENGTH > self.data.len() {
+            true => Self::NO_POS,
+            false => {
+                let hash_val = self.hash(pos);
+                self.head[hash_val]
+            }
+        };
+        let prev = &self.prev;
+        core::iter::successors(Some(first), move |candidate| {
+            Some(prev[candidate % WINDOW_SIZE])
+        })
+        .take_while(move |candidate| *candidate != Self::NO_POS && pos - candidate <= WINDOW_SIZE)
+        .take(max_chain_length)
+        .map(move |candidate| pos - candidate)
+    }
+}
+
+// length of the match at `pos` against `distance` bytes before, up to `max_length`:
+// the first `known_length` bytes are known to match
+#[cfg(feature = "compressed_png")]
+fn match_length(
+    data: &[u8],
+    pos: usize,
+    distance: usize,
+    max_length: usize,
+    known_length: usize,
+) -> usize {
+    known_length
+        + data[(pos + known_length)..(pos + max_length)]
+            .iter()
+            .zip(data[(pos + known_length - distance)..].iter())
+            .take_while(|(cur, ref_byte)| cur == ref_byte)
+            .count()
+}
+
+// tokens of the longest matches, deferred by a byte if the next position has a longer one (as zlib does).
+// distances are from: the byte run (distance 1), the distance of the last copy
+// (to follow the upper scanline through runs), and the hash chains
+#[cfg(feature = "compressed_png")]
+fn lazy_tokens(data: &[u8]) -> Vec<Token> {
+    const MAX_CHAIN_LENGTH: usize = 16;
+    // matches at least this long are taken without looking at the next position
+    const MAX_LAZY_LENGTH: usize = 32;
+    // the shortest matches are not worth far distances
+    const MAX_MIN_LENGTH_DISTANCE: usize = 4096;
+
+    let longest_match =
+        |chains: &mut HashChains, pos: usize, last_distance: usize| -> (usize, usize) {
+            let max_length = MAX_COPY_LENGTH.min(data.len() - pos);
+            if max_length < MIN_COPY_LENGTH {
+                return (0, 0);
+            }
+            let mut longest = (0, 0);
+            for distance in [1, last_distance]
+                .into_iter()
+                .chain(chains.distances(pos, MAX_CHAIN_LENGTH))
+                .filter(|distance| (1..=pos).contains(distance))
+            {
+                let length = match_length(data, pos, distance, max_length, 0);
+                if length > longest.0 {
+                    longest = (length, distance);
+                    if length == max_length {
+                        break;
+                    }
+                }
+            }
+            match longest {
+                (MIN_COPY_LENGTH, distance) if distance > MAX_MIN_LENGTH_DISTANCE => (0, 0),
+                longest => longest,
+            }
+        };
+
+    let mut chains = HashChains::new(data);
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut last_distance = 0;
+    let mut pos = 0;
+    while pos < data.len() {
+        let (length, distance) = longest_match(&mut chains, pos, last_distance);
+        chains.insert(pos);
+        if length < MIN_COPY_LENGTH {
+            tokens.push(Token::Literal(data[pos]));
+            pos += 1;
+            continue;
+        }
+        if length < MAX_LAZY_LENGTH && longest_match(&mut chains, pos + 1, distance).0 > length {
+            tokens.push(Token::Literal(data[pos]));
+            pos += 1;
+            continue;
+        }
+
+        ((pos + 1)..(pos + length)).for_each(|pos| chains.insert(pos));
+        tokens.push(Token::Copy(length, distance));
+        last_distance = distance;
+        pos += length;
+    }
+
+    tokens
+}
+
+// backward reference candidates of all positions, in a flat buffer:
+// candidates at `pos` are `pairs[offsets[pos]..offsets[pos + 1]]`
+#[cfg(feature = "compressed_png")]
+struct MatchCandidates {
+    offsets: Vec<usize>,
+    pairs: Vec<(usize, usize)>,
+}
+
+#[cfg(feature = "compressed_png")]
+impl MatchCandidates {
+    fn at(&self, pos: usize) -> &[(usize, usize)] {
+        &self.pairs[self.offsets[pos]..self.offsets[pos + 1]]
+    }
+}
+
+// backward reference candidates at each position, as (max length, distance) pairs:
+// each pair covers lengths longer than the max length of the previous pair, with the nearest distance.
+// distances are from: the byte run (distance 1), the candidates of the previous position
+// (to follow the upper scanline through runs), and the hash chains
+#[cfg(feature = "compressed_png")]
+fn match_candidates(data: &[u8]) -> MatchCandidates {
+    const MAX_CHAIN_LENGTH: usize = 16;
+    const NICE_LENGTH: usize = 64;
+
+    let mut chains = HashChains::new(data);
+    let mut offsets: Vec<usize> = Vec::with_capacity(data.len() + 1);
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut distances: Vec<(usize, usize)> = Vec::new();
+    offsets.push(0);
+
+    for pos in 0..data.len() {
+        if pos + MIN_COPY_LENGTH > data.len() {
+            offsets.push(pairs.len());
+            continue;
+        }
+        let max_length = MAX_COPY_LENGTH.min(data.len() - pos);
+
+        // pairs after the offset of the previous position are its candidates,
+        // matching 1 byte shorter at least
+        distances.clear();
+        if pos > 0 {
+            distances.extend(
+                core::iter::once((1, 0))
+                    .chain(
+                        pairs[offsets[pos - 1]..]
+                            .iter()
+                            .map(|(length, distance)| (*distance, (length - 1).min(max_length))),
+                    )
+                    .filter(|(distance, _)| *distance <= pos),
+            );
+        }
+        // chains are not looked up if a candidate is long enough already (as in a repetition)
+        if distances
+            .iter()
+            .all(|(_, known_length)| *known_length < (max_length - 1).min(NICE_LENGTH))
+        {
+            distances.extend(
+                chains
+                    .distances(pos, MAX_CHAIN_LENGTH)
+                    .map(|distance| (distance, 0)),
+            );
+        }
+        chains.insert(pos);
+
+        distances.sort_unstable_by(
+            |(distance, known_length), (other_distance, other_known_length)| {
+                distance
+                    .cmp(other_distance)
+                    .then(other_known_length.cmp(known_length))
+            },
+        );
+        distances.dedup_by_key(|(distance, _)| *distance);
+        let start = pairs.len();
+        for (distance, known_length) in distances.iter() {
+            let longest = pairs[start..]
+                .last()
+                .map_or(MIN_COPY_LENGTH - 1, |(len, _)| *len);
+            if longest >= max_length {
+                break;
+            }
+            let length = match_length(data, pos, *distance, max_length, *known_length);
+            if length > longest {
+                pairs.push((length, *distance));
+            }
+        }
+        offsets.push(pairs.len());
+    }
+
+    MatchCandidates { offsets, pairs }
+}
+
+// tokens of the least total bits with the given code lengths (shortest path on positions).
+// symbols without codes are regarded as expensive, but still available.
+// `costs` is a buffer reused across calls, holding
+// (total bits to reach the position, (length, distance) of the last step)
+#[cfg(feature = "compressed_png")]
+fn cheapest_tokens(
+    data: &[u8],
+    matches: &MatchCandidates,
+    (litlen_code_lengths, distance_code_lengths): &(Vec<u8>, Vec<u8>),
+    costs: &mut Vec<(u32, (usize, usize))>,
+) -> Vec<Token> {
+    const UNUSED_CODE_BITS: u32 = 16;
+
+    let code_bits = |len: u8| -> u32 {
+        match len {
+            0 => UNUSED_CODE_BITS,
+            len => len as u32,
+        }
+    };
+    let length_bits: Vec<u32> = (0..=MAX_COPY_LENGTH)
+        .map(|length| match length < MIN_COPY_LENGTH {
+            true => 0,
+            false => {
+                let (symbol, extra_bits, _) = length_encode(length);
+                code_bits(litlen_code_lengths[symbol]) + extra_bits as u32
+            }
+        })
+        .collect();
+    let distance_bits = |distance: usize| -> u32 {
+        let (symbol, extra_bits, _) = distance_encode(distance);
+        code_bits(distance_code_lengths[symbol]) + extra_bits as u32
+    };
+
+    // lengths sharing a length symbol cost the same bits:
+    // only the shortest and the longest ones of each symbol are tried
+    let symbol_edge_lengths: Vec<usize> = (MIN_COPY_LENGTH..=MAX_COPY_LENGTH)
+        .filter(|length| {
+            let symbol = length_encode(*length).0;
+            [MIN_COPY_LENGTH, MAX_COPY_LENGTH].contains(length)
+                || symbol != length_encode(length - 1).0
+                || symbol != length_encode(length + 1).0
+        })
+        .collect();
+
+    costs.clear();
+    costs.resize(data.len() + 1, (u32::MAX, (0, 0)));
+    costs[0].0 = 0;
+    (0..data.len()).for_each(|pos| {
+        let cost = costs[pos].0;
+
+        let literal_cost = cost + code_bits(litlen_code_lengths[data[pos] as usize]);
+        if literal_cost < costs[pos + 1].0 {
+            costs[pos + 1] = (literal_cost, (1, 0));
+        }
+
+        // inside a repetition (the longest copies from the previous position too),
+        // only the longest copy is tried
+        let pairs = matches.at(pos);
+        let is_in_repetition = pos > 0
+            && [matches.at(pos - 1), pairs].iter().all(|pairs| {
+                pairs
+                    .last()
+                    .map_or(false, |(max_length, _)| *max_length == MAX_COPY_LENGTH)
+            });
+        let (pairs, min_length) = match is_in_repetition {
+            true => (&pairs[(pairs.len() - 1)..], MAX_COPY_LENGTH),
+            false => (pairs, MIN_COPY_LENGTH),
+        };
+        pairs
+            .iter()
+            .fold(min_length, |min_length, (max_length, distance)| {
+                let cost = cost + distance_bits(*distance);
+                let edges = symbol_edge_lengths
+                    [symbol_edge_lengths.partition_point(|length| *length <= min_length)..]
+                    .iter()
+                    .take_while(|length| *length < max_length);
+                core::iter::once(min_length)
+                    .chain(edges.copied())
+                    .chain((*max_length > min_length).then_some(*max_length))
+                    .for_each(|length| {
+                        let copy_cost = cost + length_bits[length];
+                        if copy_cost < costs[pos + length].0 {
+                            costs[pos + length] = (copy_cost, (length, *distance));
+                        }
+                    });
+                max_length + 1
+            });
+    });
+
+    // trace back from the end
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut pos = data.len();
+    while pos > 0 {
+        let (length, distance) = costs[pos].1;
+        pos -= length;
+        tokens.push(match distance {
+            0 => Token::Literal(data[pos]),
+            _ => Token::Copy(length, distance),
+        });
+    }
+    tokens.reverse();
+
+    tokens
+}
+
+// (length symbol, extra bits count, extra bits value) of a copy length (3 - 258)
+#[cfg(feature = "compressed_png")]
+fn length_encode(length: usize) -> (usize, u8, u32) {
+    const LENGTH_BASES: [u16; 29] = [
+        3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115,
+        131, 163, 195, 227, 258,
+    ];
+    const LENGTH_EXTRA_BITS: [u8; 29] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+    ];
+
+    let idx = LENGTH_BASES
+        .iter()
+        .rposition(|base| *base as usize <= length)
+        .expect("copy length should be at least 3");
+
+    (
+        END_OF_BLOCK + 1 + idx,
+        LENGTH_EXTRA_BITS[idx],
+        (length - LENGTH_BASES[idx] as usize) as u32,
+    )
+}
+
+// (distance symbol, extra bits count, extra bits value) of a copy distance (1 - 32768)
+#[cfg(feature = "compressed_png")]
+fn distance_encode(distance: usize) -> (usize, u8, u32) {
+    let distance = distance as u32 - 1;
+    match distance < 4 {
+        true => (distance as usize, 0, 0),
+        false => {
+            let highest_bit = u32::BITS - 1 - distance.leading_zeros();
+            let second_highest_bit = (distance >> (highest_bit - 1)) & 1;
+            let extra_bits = highest_bit - 1;
+            (
+                (2 * highest_bit + second_highest_bit) as usize,
+                extra_bits as u8,
+                distance & ((1 << extra_bits) - 1),
+            )
+        }
+    }
+}
+
+fn adler32(buf: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    const MAX_CHUNK: usize = 5552;
+
+    let (a, b) = buf
+        .chunks(MAX_CHUNK)
+        .fold((1_u32, 0_u32), |(a, b), idx_data_tuple| {
+            let (a_new, b_new) = idx_data_tuple
+                .iter()
+                .fold((a, b), |(a_inner, b_inner), elem| {
+                    (a_inner + *elem as u32, a_inner + b_inner + *elem as u32)
+                });
+
+            (a_new % MOD_ADLER, b_new % MOD_ADLER)
+        });
+
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "compressed_png")]
+    use alloc::format;
+    use alloc::vec;
+
+    fn inflate(zlib_data: &[u8]) -> Vec<u8> {
+        miniz_oxide::inflate::decompress_to_vec_zlib(zlib_data).expect("valid zlib stream")
+    }
+
+    // unfiltered png scanlines of indexed blockies, as the png encoder passes them
+    #[cfg(feature = "compressed_png")]
+    fn blockies_scanlines(seed: &str, dim: usize) -> Vec<u8> {
+        use crate::{BlockiesGenerator, EthBlockies};
+
+        let (_, bitmap) = EthBlockies::indexed_data(seed);
+        (0..dim)
+            .flat_map(|y| {
+                core::iter::once(0)
+                    .chain((0..dim).map(move |x| bitmap[y * 8 / dim][x * 8 / dim] as u8))
+            })
+            .collect()
+    }
+
+    // pseudo-random bytes, not compressible
+    fn noise(len: usize) -> Vec<u8> {
+        (0..len as u32)
+            .scan(0x2545_f491_u32, |state, _| {
+                *state ^= *state << 13;
+                *state ^= *state >> 17;
+                *state ^= *state << 5;
+                Some(*state as u8)
+            })
+            .collect()
+    }
+
+    fn test_inputs() -> Vec<Vec<u8>> {
+        vec![
+            vec![],
+            vec![0x42],
+            vec![0, 1],
+            vec![7; 3],
+            vec![7; 1000],
+            b"abcabcabcabcabcabcabd".to_vec(),
+            noise(1000),
+            noise(70_000),
+            (0..70_000).map(|idx| (idx / 300) as u8).collect(),
+        ]
+    }
+
+    #[test]
+    fn header() {
+        [0, 1, 2, 3].iter().for_each(|flevel| {
+            let [cmf, flg] = zlib_header(*flevel);
+            assert_eq!(cmf, 0x78);
+            assert_eq!(flg >> 6, *flevel);
+            assert_eq!((cmf as u16 * 256 + flg as u16) % 31, 0);
+        });
+    }
+
+    #[test]
+    fn adler32_known_values() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        // sums kept under the modulus with chunks, same as without chunks
+        let data = [0xff; 100_000];
+        let (a, b) = data.iter().fold((1_u64, 0_u64), |(a, b), elem| {
+            ((a + *elem as u64) % 65521, (b + a + *elem as u64) % 65521)
+        });
+        assert_eq!(adler32(&data), (b << 16 | a) as u32);
+    }
+
+    #[test]
+    fn stored_empty() {
+        // header, a final stored block of length 0, and adler32 of nothing
+        assert_eq!(
+            zlib_stored(&[]),
+            [0x78, 0x01, 0b001, 0x00, 0x00, 0xff, 0xff, 0, 0, 0, 1]
+        );
+        assert_eq!(inflate(&zlib_stored(&[])), []);
+    }
+
+    #[test]
+    fn stored_block_split() {
+        const MAX_STORED_LEN: usize = 65535;
+        const BLOCK_HEADER_LEN: usize = 5;
+
+        // exactly one full block, then one more byte
+        [MAX_STORED_LEN, MAX_STORED_LEN + 1, 2 * MAX_STORED_LEN + 10]
+            .iter()
+            .for_each(|len| {
+                let data = noise(*len);
+                let zlib_data = zlib_stored(&data);
+                let block_lens: Vec<usize> = data.chunks(MAX_STORED_LEN).map(<[u8]>::len).collect();
+
+                assert_eq!(
+                    zlib_data.len(),
+                    2 + block_lens.len() * BLOCK_HEADER_LEN + len + 4,
+                    "len: {}",
+                    len
+                );
+                block_lens
+                    .iter()
+                    .enumerate()
+                    .fold(2, |offset, (idx, block_len)| {
+                        let is_last_block = (idx + 1 == block_lens.len()) as u8;
+                        let block_len_bytes = (*block_len as u16).to_le_bytes();
+                        let nlen_bytes = (!(*block_len as u16)).to_le_bytes();
+                        assert_eq!(
+                            zlib_data[offset..(offset + BLOCK_HEADER_LEN)],
+                            [
+                                is_last_block,
+                                block_len_bytes[0],
+                                block_len_bytes[1],
+                                nlen_bytes[0],
+                                nlen_bytes[1]
+                            ],
+                            "len: {}, block: {}",
+                            len,
+                            idx
+                        );
+                        offset + BLOCK_HEADER_LEN + block_len
+                    });
+                assert_eq!(inflate(&zlib_data), data, "len: {}", len);
+            });
+    }
+
+    #[test]
+    fn stored_round_trip() {
+        test_inputs().iter().for_each(|data| {
+            assert_eq!(inflate(&zlib_stored(data)), *data, "len: {}", data.len());
+        });
+    }
+
+    #[cfg(feature = "compressed_png")]
+    #[test]
+    fn compressed_round_trip() {
+        test_inputs()
+            .into_iter()
+            .chain(
+                ["generic_seed", "0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc"]
+                    .iter()
+                    .flat_map(|seed| [8, 100, 256].map(|dim| blockies_scanlines(seed, dim))),
+            )
+            .for_each(|data| {
+                let zlib_data = zlib_compressed(&data);
+                assert_eq!(zlib_data[..2], zlib_header(3));
+                assert_eq!(inflate(&zlib_data), data, "len: {}", data.len());
+            });
+    }
+
+    // the lazy parse alone, as used on inputs too large for the optimal parse
+    #[cfg(feature = "compressed_png")]
+    #[test]
+    fn lazy_round_trip() {
+        test_inputs()
+            .into_iter()
+            .chain(core::iter::once(blockies_scanlines("generic_seed", 512)))
+            .for_each(|data| {
+                [BlockType::FixedHuffman, BlockType::DynamicHuffman]
+                    .into_iter()
+                    .for_each(|block_type| {
+                        let mut zlib_data = zlib_header(3).to_vec();
+                        zlib_data.append(&mut deflate_block(&lazy_tokens(&data), block_type));
+                        zlib_data.extend_from_slice(&adler32(&data).to_be_bytes());
+                        assert_eq!(inflate(&zlib_data), data, "len: {}", data.len());
+                    });
+            });
+    }
+
+    #[cfg(feature = "compressed_png")]
+    #[test]
+    fn compressed_empty() {
+        let zlib_data = zlib_compressed(&[]);
+        assert_eq!(zlib_data[zlib_data.len() - 4..], [0, 0, 0, 1]);
+        assert_eq!(inflate(&zlib_data), []);
+    }
+
+    // the in-crate compressor replaced the `deflate` crate (v1.0.0), and should not make blockies bigger
+    #[cfg(feature = "compressed_png")]
+    #[test]
+    fn compressed_size_against_deflate() {
+        (0..40)
+            .map(|idx| format!("seed-{}", idx))
+            .flat_map(|seed| [16, 64, 128, 256].map(|dim| blockies_scanlines(&seed, dim)))
+            .for_each(|data| {
+                let (len, deflate_len) = (
+                    zlib_compressed(&data).len(),
+                    deflate::deflate_bytes_zlib(&data).len(),
+                );
+                assert!(
+                    len <= deflate_len,
+                    "{} > {} (deflate), input len: {}",
+                    len,
+                    deflate_len,
+                    data.len()
+                );
+            });
+    }
+}
@@ -0,0 +1,451 @@
+use super::{sha256::sha256, SeedInput};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Bitcoin address as a blockies seed
+///
+/// The address string itself is used as the seed, so that the icon is the same as
+/// blockies of the address string shown in wallets:
+///
+/// * Base58 (legacy P2PKH/P2SH, `1...`, `3...`, `m...`, `n...`, `2...`): used as-is (case-sensitive)
+/// * Bech32/Bech32m (segwit, `bc1...`, `tb1...`, `bcrt1...`): converted to lowercase
+///
+/// Both of the Base58Check checksum and the Bech32/Bech32m checksum are verified,
+/// along with the version byte of Base58 addresses,
+/// and the witness version and the witness program length of segwit addresses
+/// (2 to 40 bytes, and 20 or 32 bytes for version 0).
+///
+/// # Example
+///
+/// ```
+/// use eth_blockies::*;
+///
+/// let legacy = BitcoinAddress::new("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2").unwrap();
+/// let segwit = BitcoinAddress::new("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4").unwrap();
+/// assert_eq!(segwit.as_str(), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+/// let taproot = BitcoinAddress::new(
+///     "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"
+/// ).unwrap();
+///
+/// // invalid checksum
+/// assert!(BitcoinAddress::new("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3").is_none());
+/// assert!(BitcoinAddress::new("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5").is_none());
+///
+/// let icon = Blockies::<8>::data(&segwit);
+/// assert_eq!(icon, Blockies::<8>::data("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct BitcoinAddress(String);
+
+impl BitcoinAddress {
+    /// Validate and canonicalize a Bitcoin address, or return `None` if invalid
+    pub fn new(addr: &str) -> Option<Self> {
+        // version bytes of P2PKH, P2SH on mainnet and testnet
+        const BASE58_VERSIONS: [u8; 4] = [0x00, 0x05, 0x6f, 0xc4];
+        // payload (21 bytes) + checksum (4 bytes)
+        const BASE58_LEN: usize = 25;
+        const BASE58_CHECKSUM_LEN: usize = 4;
+        const BECH32_HRPS: [&str; 3] = ["bc", "tb", "bcrt"];
+        // BIP-141: witness program of 2 to 40 bytes, of 20 (P2WPKH) or 32 (P2WSH) bytes on version 0
+        const WITNESS_PROGRAM_LENS: core::ops::RangeInclusive<usize> = 2..=40;
+        const WITNESS_V0_PROGRAM_LENS: [usize; 2] = [20, 32];
+
+        match bech32_decode(addr) {
+            Some((hrp, data, checksum)) => {
+                let (witness_version, program) = data.split_first()?;
+                let expected_checksum = match witness_version {
+                    0 => Checksum::Bech32,
+                    _ => Checksum::Bech32m,
+                };
+                let program_len = convert_5_to_8_bits(program)?.len();
+                (BECH32_HRPS.contains(&hrp.as_str())
+                    && *witness_version <= 16
+                    && checksum == expected_checksum
+                    && WITNESS_PROGRAM_LENS.contains(&program_len)
+                    && (*witness_version != 0 || WITNESS_V0_PROGRAM_LENS.contains(&program_len)))
+                .then(|| BitcoinAddress(addr.to_ascii_lowercase()))
+            }
+            None => base58_decode(addr)
+                .filter(|data| data.len() == BASE58_LEN && BASE58_VERSIONS.contains(&data[0]))
+                .filter(|data| {
+                    // first 4 bytes of double SHA-256 of the payload
+                    let (payload, checksum) = data.split_at(BASE58_LEN - BASE58_CHECKSUM_LEN);
+                    sha256(&sha256(payload))[..BASE58_CHECKSUM_LEN] == *checksum
+                })
+                .map(|_| BitcoinAddress(String::from(addr))),
+        }
+    }
+
+    /// Get the canonical address string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl SeedInput for BitcoinAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+impl SeedInput for &BitcoinAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+/// Solana address as a blockies seed
+///
+/// The Base58 address string (public key of 32 bytes) is used as-is (case-sensitive),
+/// so that the icon is the same as blockies of the address string shown in wallets.
+///
+/// # Example
+///
+/// ```
+/// use eth_blockies::*;
+///
+/// let addr = SolanaAddress::new("So11111111111111111111111111111111111111112").unwrap();
+/// assert_eq!(addr.as_str(), "So11111111111111111111111111111111111111112");
+///
+/// // not a 32-byte public key
+/// assert!(SolanaAddress::new("So1111111111111111111111111111111111111111").is_none());
+///
+/// let icon = Blockies::<8>::data(&addr);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct SolanaAddress(String);
+
+impl SolanaAddress {
+    /// Validate a Solana address, or return `None` if invalid
+    pub fn new(addr: &str) -> Option<Self> {
+        const PUBKEY_LEN: usize = 32;
+
+        base58_decode(addr)
+            .filter(|data| data.len() == PUBKEY_LEN)
+            .map(|_| SolanaAddress(String::from(addr)))
+    }
+
+    /// Get the canonical address string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl SeedInput for SolanaAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+impl SeedInput for &SolanaAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+/// Cosmos SDK address as a blockies seed
+///
+/// Addresses of any Bech32 prefix (such as `cosmos1...`, `osmo1...`, `cosmosvaloper1...`) are accepted,
+/// and converted to lowercase. The lowercase address string is used as the seed,
+/// so that the icon is the same as blockies of the address string shown in wallets.
+///
+/// # Example
+///
+/// ```
+/// use eth_blockies::*;
+///
+/// let addr = CosmosAddress::new("COSMOS1QYPQXPQ9QCRSSZG2PVXQ6RS0ZQG3YYC5LZV7XU").unwrap();
+/// assert_eq!(addr.as_str(), "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu");
+/// assert_eq!(addr.prefix(), "cosmos");
+///
+/// // mixed case
+/// assert!(CosmosAddress::new("cosmos1QYPQXPQ9QCRSSZG2PVXQ6RS0ZQG3YYC5LZV7XU").is_none());
+///
+/// let icon = Blockies::<8>::data(&addr);
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct CosmosAddress(String);
+
+impl CosmosAddress {
+    /// Validate and canonicalize a Cosmos SDK address, or return `None` if invalid
+    pub fn new(addr: &str) -> Option<Self> {
+        bech32_decode(addr)
+            .filter(|(_, data, checksum)| !data.is_empty() && *checksum == Checksum::Bech32)
+            .map(|_| CosmosAddress(addr.to_ascii_lowercase()))
+    }
+
+    /// Get the canonical address string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Get the human-readable prefix of the address, such as `cosmos`
+    pub fn prefix(&self) -> &str {
+        self.0.rsplit_once('1').map_or("", |(hrp, _)| hrp)
+    }
+}
+
+impl SeedInput for CosmosAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+impl SeedInput for &CosmosAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+/// ENS-style name as a blockies seed
+///
+/// The name is used as-is, without normalization or resolution to the address.
+/// To get blockies of the address the name resolves to, resolve it yourself and use
+/// [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed) instead.
+///
+/// # Example
+///
+/// ```
+/// use eth_blockies::*;
+///
+/// let name = EnsName::new("vitalik.eth").unwrap();
+/// assert_eq!(name.as_str(), "vitalik.eth");
+///
+/// // empty label
+/// assert!(EnsName::new("vitalik..eth").is_none());
+///
+/// let icon = Blockies::<8>::data(&name);
+/// assert_eq!(icon, Blockies::<8>::data("vitalik.eth"));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct EnsName(String);
+
+impl EnsName {
+    /// Check that the name consists of 2 or more non-empty labels without whitespace,
+    /// or return `None` if not
+    pub fn new(name: &str) -> Option<Self> {
+        (name.split('.').count() >= 2
+            && name.split('.').all(|label| !label.is_empty())
+            && !name.contains(char::is_whitespace))
+        .then(|| EnsName(String::from(name)))
+    }
+
+    /// Get the name string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl SeedInput for EnsName {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+impl SeedInput for &EnsName {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+// Decode base58 string (Bitcoin alphabet) to bytes, or return None if invalid
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    // big-endian number, multiplied by 58 and added with each digit
+    let mut number: Vec<u8> = Vec::new();
+    for c in s.bytes() {
+        let mut carry = ALPHABET.iter().position(|digit| *digit == c)? as u32;
+        number.iter_mut().rev().for_each(|byte| {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        });
+        while carry > 0 {
+            number.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    // each leading '1' is a leading zero byte
+    let leading_zeros = s.bytes().take_while(|c| *c == ALPHABET[0]).count();
+    (!s.is_empty()).then(|| [alloc::vec![0; leading_zeros], number].concat())
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+enum Checksum {
+    Bech32,
+    Bech32m,
+}
+
+// Decode bech32/bech32m string to (lowercase hrp, 5-bit data without checksum, checksum type),
+// or return None if invalid
+fn bech32_decode(s: &str) -> Option<(String, Vec<u8>, Checksum)> {
+    const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    const MAX_LEN: usize = 90;
+    const CHECKSUM_LEN: usize = 6;
+    const BECH32_CONST: u32 = 1;
+    const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+    let is_mixed_case =
+        s.bytes().any(|c| c.is_ascii_lowercase()) && s.bytes().any(|c| c.is_ascii_uppercase());
+    if s.len() > MAX_LEN || is_mixed_case || !s.bytes().all(|c| (33..=126).contains(&c)) {
+        return None;
+    }
+
+    let lowercase = s.to_ascii_lowercase();
+    let (hrp, data) = lowercase.rsplit_once('1')?;
+    if hrp.is_empty() || data.len() < CHECKSUM_LEN {
+        return None;
+    }
+    let data: Vec<u8> = data
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|digit| *digit == c)
+                .map(|v| v as u8)
+        })
+        .collect::<Option<_>>()?;
+
+    // hrp high bits, 0, hrp low bits, then data
+    let polymod_values = hrp
+        .bytes()
+        .map(|c| c >> 5)
+        .chain(core::iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 0x1f))
+        .chain(data.iter().copied());
+    let checksum = match bech32_polymod(polymod_values) {
+        BECH32_CONST => Checksum::Bech32,
+        BECH32M_CONST => Checksum::Bech32m,
+        _ => return None,
+    };
+
+    Some((
+        String::from(hrp),
+        data[..data.len() - CHECKSUM_LEN].to_vec(),
+        checksum,
+    ))
+}
+
+// Regroup 5-bit values to bytes, or return None if the padding is more than 4 bits or non-zero
+fn convert_5_to_8_bits(values: &[u8]) -> Option<Vec<u8>> {
+    let (bytes, acc, bits) = values.iter().fold(
+        (Vec::new(), 0_u32, 0_u32),
+        |(mut bytes, acc, bits), value| {
+            let (acc, bits) = ((acc << 5) | *value as u32, bits + 5);
+            match bits >= 8 {
+                true => {
+                    bytes.push((acc >> (bits - 8)) as u8);
+                    (bytes, acc & ((1 << (bits - 8)) - 1), bits - 8)
+                }
+                false => (bytes, acc, bits),
+            }
+        },
+    );
+
+    (bits <= 4 && acc == 0).then_some(bytes)
+}
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+
+    values.fold(1, |chk, value| {
+        let top = chk >> 25;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| (top >> idx) & 1 != 0)
+            .fold(
+                ((chk & 0x01ff_ffff) << 5) ^ value as u32,
+                |chk, (_, gen)| chk ^ gen,
+            )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitcoin_address_valid() {
+        [
+            // base58: P2PKH, P2SH on mainnet and testnet
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+            "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn",
+            "2MzQwSSnBHWHqSAqtTVQ6v47XtaisrJa1Vc",
+            // segwit: version 0 of 20 and 32 bytes, version 1 of 32 and 40 bytes, version 16 of 2 bytes
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            "tb1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusqnfe5xq",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+            "bc1pqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7ruszzg3rysjjvfeghcgnay",
+            "BC1SW50QGDZ25J",
+        ]
+        .iter()
+        .for_each(|addr| assert!(BitcoinAddress::new(addr).is_some(), "{}", addr));
+    }
+
+    #[test]
+    fn bitcoin_address_invalid_base58() {
+        [
+            // invalid checksum
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3",
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLz",
+            "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfo",
+            // invalid character ('0')
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN0",
+            // invalid length
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNV",
+            "",
+        ]
+        .iter()
+        .for_each(|addr| assert!(BitcoinAddress::new(addr).is_none(), "{}", addr));
+    }
+
+    #[test]
+    fn bitcoin_address_invalid_segwit() {
+        // valid bech32/bech32m strings, but invalid segwit addresses
+        [
+            // invalid witness program length for witness version 0 (16 bytes)
+            "bc1qqypqxpq9qcrsszg2pvxq6rs0zqlxvppt",
+            // invalid witness program length (1 byte)
+            "bc1pw5dgrnzv",
+            // invalid witness program length (41 bytes)
+            "bc1pqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7ruszzg3rysjjvfeg9yfzvla3",
+            // zero padding of more than 4 bits
+            "bc1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5qms9mnw",
+            // non-zero padding
+            "bc1pqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7ruspnqm8ep",
+            // bech32m checksum on witness version 0, bech32 checksum on witness version 1
+            "bc1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5uyze8n",
+            "bc1pqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusqm2l7p3",
+            // invalid witness version (17)
+            "bc13qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusqjz4s3l",
+            // invalid human-readable part
+            "tc1pqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusqcz8y2h",
+            // empty data
+            "bc1gmk9yu",
+        ]
+        .iter()
+        .for_each(|addr| {
+            assert!(bech32_decode(addr).is_some(), "{}", addr);
+            assert!(BitcoinAddress::new(addr).is_none(), "{}", addr);
+        });
+
+        [
+            // invalid checksum
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+            // mixed case
+            "tb1qrp33g2q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7",
+            // invalid character ('o')
+            "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+        ]
+        .iter()
+        .for_each(|addr| assert!(BitcoinAddress::new(addr).is_none(), "{}", addr));
+    }
+}
@@ -0,0 +1,98 @@
+use super::SeedInput;
+
+use alloc::vec::Vec;
+
+/// Blockies implementation in other ecosystems, to generate the same icon as
+///
+/// All implementations share the same algorithm, so the same seed always results in the same blockies.
+/// They differ in how the seed is preprocessed and in the default size of the image,
+/// which is why the icon of the same address may look different between wallets and dapps:
+///
+/// | Profile | Seed | Default scale |
+/// |---------|------|---------------|
+/// | [`EthereumBlockies`](CompatProfile::EthereumBlockies) (also `blockies-ts`) | as-is | 4 (32x32) |
+/// | [`EthereumBlockiesBase64`](CompatProfile::EthereumBlockiesBase64) | lowercase | 16 (128x128) |
+///
+/// Each profile is validated against the golden testcases of the library
+/// (`tests/algorithm_validation/golden/compat.txt`).
+///
+/// For the identicon of MetaMask, which is not blockies, see [`Jazzicon`](crate::Jazzicon).
+///
+/// # Example
+///
+/// * Blockies of `ethereum-blockies-base64` (lowercase seed, 128x128)
+///   ```
+///   use eth_blockies::*;
+///
+///   let addr = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+///   let profile = CompatProfile::EthereumBlockiesBase64;
+///
+///   let data_uri = EthBlockies::png_data_base64(
+///       profile.seed(addr),
+///       profile.output_dim(8),
+///       true,
+///   );
+///   assert_eq!(
+///       data_uri,
+///       EthBlockies::png_data_base64("0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc", (128, 128), true)
+///   );
+///   ```
+///
+/// * Blockies of `ethereum-blockies` (seed as-is, 32x32)
+///   ```
+///   use eth_blockies::*;
+///
+///   let addr = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+///   let profile = CompatProfile::EthereumBlockies;
+///
+///   assert_eq!(profile.output_dim(8), (32, 32));
+///
+///   // checksummed and lowercase address result in different icons
+///   assert_ne!(
+///       EthBlockies::data(profile.seed(addr)),
+///       EthBlockies::data(CompatProfile::EthereumBlockiesBase64.seed(addr))
+///   );
+///   ```
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub enum CompatProfile {
+    /// [`ethereum-blockies`](https://github.com/ethereum/blockies), the original implementation
+    ///
+    /// * Seed: used as-is (pass a lowercase address, if the dapp lowercases it before)
+    /// * Default scale: 4 pixels per cell (32x32 for 8x8 blockies)
+    ///
+    /// [`blockies-ts`](https://www.npmjs.com/package/blockies-ts), a TypeScript port of `ethereum-blockies`,
+    /// preprocesses the seed and scales the image the same, so use this profile for it as well.
+    #[default]
+    EthereumBlockies,
+    /// [`ethereum-blockies-base64`](https://www.npmjs.com/package/ethereum-blockies-base64),
+    /// which generates png data uri of an address
+    ///
+    /// * Seed: converted to lowercase
+    /// * Default scale: 16 pixels per cell (128x128 for 8x8 blockies)
+    EthereumBlockiesBase64,
+}
+
+impl CompatProfile {
+    /// Preprocess the seed as the implementation does
+    pub fn seed<I: SeedInput>(&self, seed: I) -> Vec<u8> {
+        let seed = seed.to_seed_bytes();
+        match self {
+            CompatProfile::EthereumBlockies => seed.into_owned(),
+            CompatProfile::EthereumBlockiesBase64 => seed.to_ascii_lowercase(),
+        }
+    }
+
+    /// Get the default scale (pixels per cell) of the implementation
+    pub fn scale(&self) -> usize {
+        match self {
+            CompatProfile::EthereumBlockies => 4,
+            CompatProfile::EthereumBlockiesBase64 => 16,
+        }
+    }
+
+    /// Get the default image dimension of the implementation, for blockies of `size`
+    /// (such as `8` for [`EthBlockies`](crate::EthBlockies))
+    pub fn output_dim(&self, size: usize) -> (usize, usize) {
+        (size * self.scale(), size * self.scale())
+    }
+}
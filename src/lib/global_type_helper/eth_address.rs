@@ -0,0 +1,291 @@
+use super::{keccak::keccak256, secp256k1, SeedInput};
+
+use alloc::vec::Vec;
+
+/// Ethereum address derived in-crate, as a blockies seed
+///
+/// Derives the address from a secp256k1 public key or a contract creation,
+/// without any private key.
+/// The derived address is a well-formed Ethereum address seed,
+/// the same as [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed) of the address.
+///
+/// # Example
+///
+/// * Blockies of a contract before it is deployed
+///   ```
+///   use eth_blockies::*;
+///
+///   let deployer = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+///   let contract = EthAddress::from_create(deployer, 0).unwrap();
+///   assert_eq!(contract.as_str(), "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d");
+///
+///   let icon = EthBlockies::data(&contract);
+///   assert_eq!(
+///       icon,
+///       EthBlockies::data("0xCD234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d".to_ethaddr_seed())
+///   );
+///   ```
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct EthAddress([u8; 42]);
+
+impl EthAddress {
+    /// Derive the address from a secp256k1 public key, or return `None` if the key is invalid
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key` - Public key in one of the following forms:
+    ///   * Compressed: 33 bytes (`0x02` or `0x03`, x)
+    ///   * Uncompressed: 65 bytes (`0x04`, x, y)
+    ///   * Raw: 64 bytes (x, y)
+    ///
+    /// The key is validated to be a point on the curve.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eth_blockies::*;
+    ///
+    /// // public key of the private key `1` (generator point)
+    /// let x = [
+    ///     0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce, 0x87, 0x0b, 0x07,
+    ///     0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16, 0xf8, 0x17, 0x98,
+    /// ];
+    /// let y = [
+    ///     0x48, 0x3a, 0xda, 0x77, 0x26, 0xa3, 0xc4, 0x65, 0x5d, 0xa4, 0xfb, 0xfc, 0x0e, 0x11, 0x08, 0xa8,
+    ///     0xfd, 0x17, 0xb4, 0x48, 0xa6, 0x85, 0x54, 0x19, 0x9c, 0x47, 0xd0, 0x8f, 0xfb, 0x10, 0xd4, 0xb8,
+    /// ];
+    /// let uncompressed = [&[0x04][..], &x, &y].concat();
+    /// let compressed = [&[0x02][..], &x].concat(); // y is even
+    ///
+    /// let addr = EthAddress::from_public_key(&uncompressed).unwrap();
+    /// assert_eq!(addr.as_str(), "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+    /// assert_eq!(EthAddress::from_public_key(&compressed), Some(addr));
+    ///
+    /// // not on the curve
+    /// assert!(EthAddress::from_public_key(&[&[0x04][..], &x, &x].concat()).is_none());
+    /// ```
+    pub fn from_public_key(public_key: &[u8]) -> Option<Self> {
+        const COORD_LEN: usize = 32;
+        const PREFIX_EVEN: u8 = 0x02;
+        const PREFIX_ODD: u8 = 0x03;
+        const PREFIX_UNCOMPRESSED: u8 = 0x04;
+
+        let (x, y): (&[u8], [u8; COORD_LEN]) = match public_key {
+            [prefix @ (PREFIX_EVEN | PREFIX_ODD), x @ ..] if x.len() == COORD_LEN => {
+                (x, secp256k1::decompress_y(x, *prefix == PREFIX_ODD)?)
+            }
+            [PREFIX_UNCOMPRESSED, xy @ ..] | xy if xy.len() == COORD_LEN * 2 => {
+                let (x, y) = xy.split_at(COORD_LEN);
+                match secp256k1::is_on_curve(x, y) {
+                    true => (x, y.try_into().ok()?),
+                    false => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(EthAddress::from_hash(keccak256(&[x, &y].concat())))
+    }
+
+    /// Derive the address of a contract created by `CREATE`, or return `None` if the deployer is not an Ethereum address
+    ///
+    /// The address is `keccak256(rlp([deployer, nonce]))[12..]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `deployer` - Address of the deployer, in any form valid for [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed)
+    /// * `nonce` - Nonce of the deployer at the creation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eth_blockies::*;
+    ///
+    /// let deployer = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+    /// assert_eq!(
+    ///     EthAddress::from_create(deployer, 1).unwrap().as_str(),
+    ///     "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8"
+    /// );
+    ///
+    /// // not an Ethereum address
+    /// assert!(EthAddress::from_create("hello", 1).is_none());
+    /// ```
+    pub fn from_create<I: SeedInput>(deployer: I, nonce: u64) -> Option<Self> {
+        const RLP_STRING_OFFSET: u8 = 0x80;
+        const RLP_LIST_OFFSET: u8 = 0xc0;
+
+        // rlp of the nonce as an integer: 0 as empty string, < 0x80 as itself
+        let nonce_bytes = nonce.to_be_bytes();
+        let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+        let rlp_nonce: Vec<u8> = match nonce {
+            1..=0x7f => nonce_bytes.to_vec(),
+            _ => [
+                &[RLP_STRING_OFFSET + nonce_bytes.len() as u8][..],
+                nonce_bytes,
+            ]
+            .concat(),
+        };
+        let rlp_deployer = [&[RLP_STRING_OFFSET + 20][..], &ethaddr_bytes(deployer)?].concat();
+
+        // the payload is at most 30 bytes (short list)
+        let payload_len = (rlp_deployer.len() + rlp_nonce.len()) as u8;
+        Some(EthAddress::from_hash(keccak256(
+            &[
+                &[RLP_LIST_OFFSET + payload_len][..],
+                &rlp_deployer,
+                &rlp_nonce,
+            ]
+            .concat(),
+        )))
+    }
+
+    /// Derive the address of a contract created by `CREATE2`, or return `None` if the deployer is not an Ethereum address
+    ///
+    /// The address is `keccak256(0xff ++ deployer ++ salt ++ init_code_hash)[12..]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `deployer` - Address of the deployer, in any form valid for [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed)
+    /// * `salt` - Salt of 32 bytes
+    /// * `init_code_hash` - Keccak-256 hash of the init code
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eth_blockies::*;
+    ///
+    /// // example of EIP-1014, with init code `0x00`
+    /// let init_code_hash = [
+    ///     0xbc, 0x36, 0x78, 0x9e, 0x7a, 0x1e, 0x28, 0x14, 0x36, 0x46, 0x42, 0x29, 0x82, 0x8f, 0x81, 0x7d,
+    ///     0x66, 0x12, 0xf7, 0xb4, 0x77, 0xd6, 0x65, 0x91, 0xff, 0x96, 0xa9, 0xe0, 0x64, 0xbc, 0xc9, 0x8a,
+    /// ];
+    /// let addr = EthAddress::from_create2(
+    ///     "0xdeadbeef00000000000000000000000000000000",
+    ///     [0; 32],
+    ///     init_code_hash,
+    /// );
+    /// assert_eq!(
+    ///     addr.unwrap().as_str(),
+    ///     "0xb928f69bb1d91cd65274e3c79d8986362984fda3"
+    /// );
+    ///
+    /// // not an Ethereum address
+    /// assert!(EthAddress::from_create2("hello", [0; 32], init_code_hash).is_none());
+    /// ```
+    pub fn from_create2<I: SeedInput>(
+        deployer: I,
+        salt: [u8; 32],
+        init_code_hash: [u8; 32],
+    ) -> Option<Self> {
+        const CREATE2_PREFIX: u8 = 0xff;
+
+        Some(EthAddress::from_hash(keccak256(
+            &[
+                &[CREATE2_PREFIX][..],
+                &ethaddr_bytes(deployer)?,
+                &salt,
+                &init_code_hash,
+            ]
+            .concat(),
+        )))
+    }
+
+    /// Get the address string, such as `0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc`
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.0).expect("as_str()")
+    }
+
+    // address from the last 20 bytes of the hash
+    fn from_hash(hash: [u8; 32]) -> Self {
+        EthAddress((&hash[12..]).to_ethaddr_seed())
+    }
+}
+
+impl SeedInput for EthAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn to_ethaddr_seed(&self) -> [u8; 42] {
+        self.0
+    }
+}
+impl SeedInput for &EthAddress {
+    fn as_seed_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn to_ethaddr_seed(&self) -> [u8; 42] {
+        self.0
+    }
+}
+
+// raw 20 bytes of the address, or None if the seed is not an Ethereum address
+// (to_ethaddr_seed() falls back to zeroes for other seeds, which must not be derived from)
+fn ethaddr_bytes<I: SeedInput>(addr: I) -> Option<[u8; 20]> {
+    let hex_value = |c: u8| match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        _ => None,
+    };
+
+    let ethaddr = addr.to_ethaddr_seed();
+    if !ethaddr.starts_with(b"0x") {
+        return None;
+    }
+    let mut bytes = [0_u8; 20];
+    for (byte, digits) in bytes.iter_mut().zip(ethaddr[2..].chunks_exact(2)) {
+        *byte = hex_value(digits[0])? << 4 | hex_value(digits[1])?;
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPLOYER: &str = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+
+    #[test]
+    fn create_deployer_forms() {
+        let expected = EthAddress::from_create(DEPLOYER, 1);
+        assert!(expected.is_some());
+
+        let raw: [u8; 20] = ethaddr_bytes(DEPLOYER).unwrap();
+        [
+            EthAddress::from_create("0x6AC7EA33F8831EA9DCC53393AAA88B25A785DBF0", 1),
+            EthAddress::from_create(&DEPLOYER[2..], 1),
+            EthAddress::from_create(&raw[..], 1),
+        ]
+        .iter()
+        .for_each(|addr| assert_eq!(*addr, expected));
+    }
+
+    #[test]
+    fn create_invalid_deployer() {
+        [
+            "hello",
+            "",
+            // not hex
+            "0xzz00000000000000000000000000000000000000",
+            "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbfg",
+            "zz00000000000000000000000000000000000000",
+            // 42 letters without '0x'
+            "006ac7ea33f8831ea9dcc53393aaa88b25a785dbf0",
+        ]
+        .iter()
+        .for_each(|deployer| {
+            assert_eq!(EthAddress::from_create(*deployer, 1), None, "{}", deployer);
+            assert_eq!(
+                EthAddress::from_create2(*deployer, [0; 32], [0; 32]),
+                None,
+                "{}",
+                deployer
+            );
+        });
+
+        // the zero address itself is valid
+        assert!(EthAddress::from_create("0x0000000000000000000000000000000000000000", 1).is_some());
+    }
+}
@@ -0,0 +1,103 @@
+// Keccak-256 (original Keccak padding as used in Ethereum, not SHA3-256)
+
+const RATE: usize = 136;
+const ROUNDS: usize = 24;
+
+const ROUND_CONSTANTS: [u64; ROUNDS] = [
+    0x0000_0000_0000_0001,
+    0x0000_0000_0000_8082,
+    0x8000_0000_0000_808a,
+    0x8000_0000_8000_8000,
+    0x0000_0000_0000_808b,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8009,
+    0x0000_0000_0000_008a,
+    0x0000_0000_0000_0088,
+    0x0000_0000_8000_8009,
+    0x0000_0000_8000_000a,
+    0x0000_0000_8000_808b,
+    0x8000_0000_0000_008b,
+    0x8000_0000_0000_8089,
+    0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002,
+    0x8000_0000_0000_0080,
+    0x0000_0000_0000_800a,
+    0x8000_0000_8000_000a,
+    0x8000_0000_8000_8081,
+    0x8000_0000_0000_8080,
+    0x0000_0000_8000_0001,
+    0x8000_0000_8000_8008,
+];
+
+// rotation offsets and destination lanes of rho and pi steps, in the visiting order from lane 1
+const RHO_OFFSETS: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+const PI_LANES: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+fn keccak_f(state: &mut [u64; 25]) {
+    ROUND_CONSTANTS.iter().for_each(|round_constant| {
+        // theta
+        let parity: [u64; 5] =
+            core::array::from_fn(|x| (0..5).fold(0, |acc, y| acc ^ state[x + y * 5]));
+        (0..25).for_each(|idx| {
+            let x = idx % 5;
+            state[idx] ^= parity[(x + 4) % 5] ^ parity[(x + 1) % 5].rotate_left(1);
+        });
+
+        // rho and pi
+        let mut lane = state[1];
+        RHO_OFFSETS
+            .iter()
+            .zip(PI_LANES.iter())
+            .for_each(|(offset, dest)| {
+                let next = state[*dest];
+                state[*dest] = lane.rotate_left(*offset);
+                lane = next;
+            });
+
+        // chi
+        (0..5).for_each(|y| {
+            let row: [u64; 5] = core::array::from_fn(|x| state[x + y * 5]);
+            (0..5).for_each(|x| {
+                state[x + y * 5] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            });
+        });
+
+        // iota
+        state[0] ^= round_constant;
+    });
+}
+
+// Keccak-256 hash of the data
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut state = [0_u64; 25];
+    let absorb = |state: &mut [u64; 25], block: &[u8]| {
+        block.chunks(8).enumerate().for_each(|(idx, lane)| {
+            let mut lane_bytes = [0_u8; 8];
+            lane_bytes[..lane.len()].copy_from_slice(lane);
+            state[idx] ^= u64::from_le_bytes(lane_bytes);
+        });
+        keccak_f(state);
+    };
+
+    let mut blocks = data.chunks_exact(RATE);
+    blocks.by_ref().for_each(|block| absorb(&mut state, block));
+
+    // pad10*1 with the domain byte 0x01
+    let remainder = blocks.remainder();
+    let mut last_block = [0_u8; RATE];
+    last_block[..remainder.len()].copy_from_slice(remainder);
+    last_block[remainder.len()] ^= 0x01;
+    last_block[RATE - 1] ^= 0x80;
+    absorb(&mut state, &last_block);
+
+    let mut hash = [0_u8; 32];
+    hash.chunks_exact_mut(8)
+        .zip(state.iter())
+        .for_each(|(chunk, lane)| chunk.copy_from_slice(&lane.to_le_bytes()));
+    hash
+}
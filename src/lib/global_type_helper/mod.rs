@@ -1,4 +1,23 @@
+mod chain_address;
+pub use chain_address::*;
+mod compat_profile;
+pub use compat_profile::*;
+mod eth_address;
+pub use eth_address::*;
+mod keccak;
 mod palette;
 pub use palette::*;
+mod png_options;
+pub use png_options::*;
+#[cfg(feature = "qr")]
+mod qr_options;
+#[cfg(feature = "qr")]
+pub use qr_options::*;
+mod render_options;
+pub use render_options::*;
+mod secp256k1;
 mod seed_input;
 pub use seed_input::*;
+mod sha256;
+mod sheet_options;
+pub use sheet_options::*;
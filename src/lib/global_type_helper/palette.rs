@@ -10,6 +10,10 @@ type RgbElem = u8;
 /// Represents [(](tuple) `Red`, `Green`, `Blue` [)](tuple), with a range of 0-255.
 pub type RgbPixel = (RgbElem, RgbElem, RgbElem);
 const RGBPIXEL_ELEM_COUNT: usize = 3;
+/// Unit RGBA pixel data
+///
+/// Represents [(](tuple) `Red`, `Green`, `Blue`, `Alpha` [)](tuple), with a range of 0-255.
+pub type RgbaPixel = (RgbElem, RgbElem, RgbElem, RgbElem);
 
 /// Type of colors in Ethereum-style blockies
 ///
@@ -39,115 +43,115 @@ impl TryFrom<u8> for ColorClass {
     }
 }
 
-impl Into<u8> for ColorClass {
-    fn into(self) -> u8 {
-        self as u8
+impl From<ColorClass> for u8 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as u8
     }
 }
-impl Into<u8> for &ColorClass {
-    fn into(self) -> u8 {
-        (*self).into()
+impl From<&ColorClass> for u8 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<u16> for ColorClass {
-    fn into(self) -> u16 {
-        self as u16
+impl From<ColorClass> for u16 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as u16
     }
 }
-impl Into<u16> for &ColorClass {
-    fn into(self) -> u16 {
-        (*self).into()
+impl From<&ColorClass> for u16 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<u32> for ColorClass {
-    fn into(self) -> u32 {
-        self as u32
+impl From<ColorClass> for u32 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as u32
     }
 }
-impl Into<u32> for &ColorClass {
-    fn into(self) -> u32 {
-        (*self).into()
+impl From<&ColorClass> for u32 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<u64> for ColorClass {
-    fn into(self) -> u64 {
-        self as u64
+impl From<ColorClass> for u64 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as u64
     }
 }
-impl Into<u64> for &ColorClass {
-    fn into(self) -> u64 {
-        (*self).into()
+impl From<&ColorClass> for u64 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<u128> for ColorClass {
-    fn into(self) -> u128 {
-        self as u128
+impl From<ColorClass> for u128 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as u128
     }
 }
-impl Into<u128> for &ColorClass {
-    fn into(self) -> u128 {
-        (*self).into()
+impl From<&ColorClass> for u128 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<usize> for ColorClass {
-    fn into(self) -> usize {
-        self as usize
+impl From<ColorClass> for usize {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as usize
     }
 }
-impl Into<usize> for &ColorClass {
-    fn into(self) -> usize {
-        (*self).into()
+impl From<&ColorClass> for usize {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
 
-impl Into<i16> for ColorClass {
-    fn into(self) -> i16 {
-        self as i16
+impl From<ColorClass> for i16 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as i16
     }
 }
-impl Into<i16> for &ColorClass {
-    fn into(self) -> i16 {
-        (*self).into()
+impl From<&ColorClass> for i16 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<i32> for ColorClass {
-    fn into(self) -> i32 {
-        self as i32
+impl From<ColorClass> for i32 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as i32
     }
 }
-impl Into<i32> for &ColorClass {
-    fn into(self) -> i32 {
-        (*self).into()
+impl From<&ColorClass> for i32 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<i64> for ColorClass {
-    fn into(self) -> i64 {
-        self as i64
+impl From<ColorClass> for i64 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as i64
     }
 }
-impl Into<i64> for &ColorClass {
-    fn into(self) -> i64 {
-        (*self).into()
+impl From<&ColorClass> for i64 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<i128> for ColorClass {
-    fn into(self) -> i128 {
-        self as i128
+impl From<ColorClass> for i128 {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as i128
     }
 }
-impl Into<i128> for &ColorClass {
-    fn into(self) -> i128 {
-        (*self).into()
+impl From<&ColorClass> for i128 {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
-impl Into<isize> for ColorClass {
-    fn into(self) -> isize {
-        self as isize
+impl From<ColorClass> for isize {
+    fn from(colorclass: ColorClass) -> Self {
+        colorclass as isize
     }
 }
-impl Into<isize> for &ColorClass {
-    fn into(self) -> isize {
-        (*self).into()
+impl From<&ColorClass> for isize {
+    fn from(colorclass: &ColorClass) -> Self {
+        (*colorclass).into()
     }
 }
 
@@ -0,0 +1,85 @@
+use super::RenderOptions;
+
+/// Scanline filter of png image data
+///
+/// Filters transform each scanline before compression,
+/// which may let the compressed png get smaller.
+/// Filters are not effective on uncompressed png.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub enum PngFilter {
+    /// Filter type 0 (`None`) on all scanlines
+    #[default]
+    None,
+    /// Filter type 1 (`Sub`) on all scanlines
+    Sub,
+    /// Filter type 2 (`Up`) on all scanlines
+    Up,
+    /// Filter type 3 (`Average`) on all scanlines
+    Average,
+    /// Filter type 4 (`Paeth`) on all scanlines
+    Paeth,
+    /// Filter type chosen for each scanline,
+    /// with the minimum sum of absolute differences heuristic
+    Adaptive,
+    /// Try all filters above, and keep the one with the smallest compressed output
+    ///
+    /// This is the slowest, as the image data is compressed once per filter.
+    Smallest,
+}
+
+/// Options for generating png image data
+///
+/// Default options generate the same png data
+/// as [`png_data`](crate::BlockiesGenerator::png_data)
+/// and [`compressed_png_data`](crate::BlockiesGenerator::compressed_png_data).
+///
+/// # Example
+/// ```
+/// use eth_blockies::*;
+///
+/// // options for the smallest png output
+/// let png_options = PngOptions {
+///     filter: PngFilter::Smallest,
+///     reduce_bit_depth: true,
+///     ..Default::default()
+/// };
+///
+/// // options for png output with metadata
+/// let png_options_metadata = PngOptions {
+///     embed_seed: true,
+///     dpi: Some(144),
+///     srgb: true,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(PngOptions::default().filter, PngFilter::None);
+/// assert_ne!(png_options, PngOptions::default());
+/// assert_ne!(png_options_metadata, PngOptions::default());
+/// ```
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub struct PngOptions {
+    /// Scanline filter of png image data (Default: [`PngFilter::None`])
+    pub filter: PngFilter,
+    /// Use bit depth 1 instead of 2, if only two or less colors are used in the image (Default: `false`)
+    ///
+    /// Unused colors are dropped from the palette in this case.
+    pub reduce_bit_depth: bool,
+    /// Record the seed and the blockies size in text chunks (Default: `false`)
+    ///
+    /// A utf-8 seed is recorded in `tEXt` chunk (or `iTXt` chunk if not ascii),
+    /// and other binary seed is recorded in `tEXt` chunk as hex string.
+    pub embed_seed: bool,
+    /// Target DPI recorded in `pHYs` chunk (Default: `None`)
+    pub dpi: Option<u32>,
+    /// Mark colors as sRGB with `sRGB` and `gAMA` chunks (Default: `false`)
+    pub srgb: bool,
+    /// Interlace image data with Adam7 method, for progressive loading (Default: `false`)
+    ///
+    /// This makes a coarse image be displayed before the whole data is loaded,
+    /// at the cost of a slightly larger output.
+    pub interlaced: bool,
+    /// Options for rendering blockies image, such as border and mask (Default: [`RenderOptions::default()`])
+    ///
+    /// Transparent pixels of the mask are stored with `tRNS` chunk.
+    pub render: RenderOptions,
+}
@@ -0,0 +1,53 @@
+use super::RgbPixel;
+
+/// Options for generating a QR code of an Ethereum address, with blockies in the centre
+///
+/// The QR code is encoded on error correction level H (recovers ~30% of the symbol),
+/// so that it still scans with blockies covering the centre of it.
+///
+/// # Example
+/// ```
+/// use eth_blockies::*;
+///
+/// // EIP-681 uri, with larger modules and a narrower quiet zone
+/// let qr_options = QrOptions {
+///     eip681_uri: true,
+///     module_size: 8,
+///     quiet_zone: 2,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(QrOptions::default().module_size, 4);
+/// assert_ne!(qr_options, QrOptions::default());
+/// ```
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct QrOptions {
+    /// Encode the address as EIP-681 uri (`ethereum:0x...`),
+    /// instead of the address only (Default: `false`)
+    pub eip681_uri: bool,
+    /// Size of each QR module in pixels (Default: `4`)
+    ///
+    /// `0` is regarded as `1`.
+    pub module_size: usize,
+    /// Width of the quiet zone around the QR code, in modules (Default: `4`)
+    pub quiet_zone: usize,
+    /// Color of dark modules (Default: `(0, 0, 0)`)
+    pub dark_color: RgbPixel,
+    /// Color of light modules and the quiet zone (Default: `(255, 255, 255)`)
+    pub light_color: RgbPixel,
+    /// Draw blockies of the address in the centre of the QR code (Default: `true`)
+    pub blockies: bool,
+}
+
+impl Default for QrOptions {
+    fn default() -> Self {
+        QrOptions {
+            eip681_uri: false,
+            module_size: 4,
+            quiet_zone: 4,
+            dark_color: (0, 0, 0),
+            light_color: (255, 255, 255),
+            blockies: true,
+        }
+    }
+}
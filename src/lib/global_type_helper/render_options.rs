@@ -0,0 +1,166 @@
+use super::RgbPixel;
+
+/// Outer padding of rendered blockies image, filled with the background color
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum Padding {
+    /// Padding in pixels, on each side
+    Pixels(usize),
+    /// Padding in blockies cells, on each side
+    ///
+    /// The cell size is calculated as if the image (inside the border)
+    /// consists of `SIZE + 2 * n` cells on each axis.
+    Cells(usize),
+}
+
+impl Default for Padding {
+    fn default() -> Self {
+        Padding::Pixels(0)
+    }
+}
+
+/// Shape of rendered blockies image
+///
+/// Pixels outside the shape are transparent.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub enum Mask {
+    /// Rectangle without clipping
+    #[default]
+    None,
+    /// Circle (or ellipse, if width and height differ) inscribed in the image
+    Circle,
+    /// Rectangle with rounded corners, of the given radius in pixels
+    RoundedSquare(usize),
+}
+
+/// Scaling mode of blockies into the image
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub enum ScaleMode {
+    /// Nearest-neighbour, with leftover pixels distributed over cells
+    ///
+    /// Same as [`BlockiesHelper::scale`](crate::BlockiesHelper::scale).
+    /// Cell widths may differ by a pixel,
+    /// if the dimension is not a multiple of [`SIZE`](crate::BlockiesHelper::SIZE).
+    #[default]
+    Distributed,
+    /// Nearest-neighbour with square cells of the same integer size,
+    /// with leftover pixels as centered padding
+    ///
+    /// Falls back to [`ScaleMode::Distributed`], if the dimension is smaller than
+    /// [`SIZE`](crate::BlockiesHelper::SIZE).
+    Exact,
+    /// Area-averaged (anti-aliased) scaling, which blends colors of cells at cell edges
+    ///
+    /// This generates truecolor output instead of indexed one,
+    /// mostly for tiny sizes such as favicons.
+    AreaAveraged,
+}
+
+/// Text of caption drawn under blockies
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub enum CaptionText {
+    /// Abbreviated Ethereum address of the seed, such as `0xe686…17dc`
+    ///
+    /// See [`to_abbreviated_ethaddr()`](crate::SeedInput::to_abbreviated_ethaddr).
+    /// Seeds other than an Ethereum address are drawn as [`CaptionText::Seed`].
+    EthAddress,
+    /// Seed itself if utf-8, or hex string of the seed otherwise
+    #[default]
+    Seed,
+}
+
+/// Caption drawn under blockies, with the built-in bitmap font
+///
+/// The caption takes a band of `7 * scale` pixels at the bottom of the image,
+/// and blockies is rendered in the remaining area above it.
+/// Text wider than the image is truncated with an ellipsis,
+/// and characters not in printable ascii are drawn as `?`.
+///
+/// # Example
+/// ```
+/// use eth_blockies::*;
+///
+/// // white caption with abbreviated address, on transparent background
+/// let caption = Caption {
+///     text: CaptionText::EthAddress,
+///     color: (255, 255, 255),
+///     background: None,
+///     scale: 2,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(Caption::default().text, CaptionText::Seed);
+/// assert_ne!(caption, Caption::default());
+/// ```
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct Caption {
+    /// Text of the caption (Default: [`CaptionText::Seed`])
+    pub text: CaptionText,
+    /// Color of the text (Default: `(0, 0, 0)`)
+    pub color: RgbPixel,
+    /// Background color of the caption band, or transparent if `None` (Default: `Some((255, 255, 255))`)
+    pub background: Option<RgbPixel>,
+    /// Scale of the 3x5 font, in pixels per font pixel (Default: `1`)
+    ///
+    /// `0` is regarded as `1`.
+    pub scale: usize,
+}
+
+impl Default for Caption {
+    fn default() -> Self {
+        Caption {
+            text: CaptionText::default(),
+            color: (0, 0, 0),
+            background: Some((255, 255, 255)),
+            scale: 1,
+        }
+    }
+}
+
+/// Options for rendering blockies image
+///
+/// From the outside to the inside, a rendered image consists of:
+/// border, padding, and blockies scaled with the scale mode.
+/// The whole image is clipped by the mask.
+/// If a caption is given, all of above are rendered above the caption.
+///
+/// Default options render the same image as
+/// [`BlockiesHelper::scale`](crate::BlockiesHelper::scale).
+///
+/// # Example
+/// ```
+/// use eth_blockies::*;
+///
+/// // circular icon with 1-cell padding and white border
+/// let render_options = RenderOptions {
+///     padding: Padding::Cells(1),
+///     border_width: 2,
+///     border_color: (255, 255, 255),
+///     mask: Mask::Circle,
+///     ..Default::default()
+/// };
+///
+/// // anti-aliased icon for tiny sizes, such as 16x16 favicon
+/// let render_options_favicon = RenderOptions {
+///     scale_mode: ScaleMode::AreaAveraged,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(RenderOptions::default().mask, Mask::None);
+/// assert_ne!(render_options, RenderOptions::default());
+/// assert_ne!(render_options_favicon, RenderOptions::default());
+/// ```
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub struct RenderOptions {
+    /// Padding between the border and blockies (Default: `Padding::Pixels(0)`)
+    pub padding: Padding,
+    /// Width of the border in pixels (Default: `0`)
+    pub border_width: usize,
+    /// Color of the border (Default: `(0, 0, 0)`)
+    pub border_color: RgbPixel,
+    /// Shape of the image, clipping the outside of it as transparent (Default: [`Mask::None`])
+    pub mask: Mask,
+    /// Scaling mode of blockies (Default: [`ScaleMode::Distributed`])
+    pub scale_mode: ScaleMode,
+    /// Caption drawn under blockies (Default: `None`)
+    pub caption: Option<Caption>,
+}
@@ -0,0 +1,156 @@
+// Minimal arithmetic on the field of secp256k1 (y^2 = x^3 + 7 mod p),
+// to validate and decompress public keys
+
+// 256-bit integer, as little-endian 64-bit limbs
+type U256 = [u64; 4];
+
+// p = 2^256 - 2^32 - 977
+const P: U256 = [
+    0xffff_fffe_ffff_fc2f,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+];
+// 2^256 mod p
+const P_COMPLEMENT: u64 = 0x1_0000_03d1;
+// (p + 1) / 4, to get the square root as a^((p + 1) / 4), since p = 3 (mod 4)
+const SQRT_EXPONENT: U256 = [
+    0xffff_ffff_bfff_ff0c,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0x3fff_ffff_ffff_ffff,
+];
+const CURVE_B: U256 = [7, 0, 0, 0];
+
+fn from_be_bytes(bytes: &[u8]) -> U256 {
+    core::array::from_fn(|idx| {
+        let start = (3 - idx) * 8;
+        let mut limb = [0_u8; 8];
+        limb.copy_from_slice(&bytes[start..start + 8]);
+        u64::from_be_bytes(limb)
+    })
+}
+
+fn to_be_bytes(value: U256) -> [u8; 32] {
+    let mut bytes = [0_u8; 32];
+    bytes
+        .chunks_exact_mut(8)
+        .zip(value.iter().rev())
+        .for_each(|(chunk, limb)| chunk.copy_from_slice(&limb.to_be_bytes()));
+    bytes
+}
+
+fn is_less(a: U256, b: U256) -> bool {
+    a.iter().rev().cmp(b.iter().rev()) == core::cmp::Ordering::Less
+}
+
+// a - b, ignoring the borrow out
+fn sub(a: U256, b: U256) -> U256 {
+    let mut borrow = false;
+    core::array::from_fn(|idx| {
+        let (diff, borrow1) = a[idx].overflowing_sub(b[idx]);
+        let (diff, borrow2) = diff.overflowing_sub(borrow as u64);
+        borrow = borrow1 || borrow2;
+        diff
+    })
+}
+
+// a + (carry * 2^256) mod p, for a < 2^256 and carry of 0 or 1
+fn normalize(mut a: U256, carry: bool) -> U256 {
+    if carry {
+        // 2^256 = P_COMPLEMENT (mod p), and a is small enough not to overflow again
+        let mut carry = P_COMPLEMENT as u128;
+        a.iter_mut().for_each(|limb| {
+            let sum = *limb as u128 + carry;
+            *limb = sum as u64;
+            carry = sum >> 64;
+        });
+    }
+    while !is_less(a, P) {
+        a = sub(a, P);
+    }
+    a
+}
+
+fn add_mod(a: U256, b: U256) -> U256 {
+    let mut carry = 0_u128;
+    let sum: U256 = core::array::from_fn(|idx| {
+        let sum = a[idx] as u128 + b[idx] as u128 + carry;
+        carry = sum >> 64;
+        sum as u64
+    });
+    normalize(sum, carry != 0)
+}
+
+fn mul_mod(a: U256, b: U256) -> U256 {
+    // 512-bit product
+    let mut wide = [0_u64; 8];
+    (0..4).for_each(|i| {
+        let mut carry = 0_u128;
+        (0..4).for_each(|j| {
+            let sum = wide[i + j] as u128 + a[i] as u128 * b[j] as u128 + carry;
+            wide[i + j] = sum as u64;
+            carry = sum >> 64;
+        });
+        wide[i + 4] = carry as u64;
+    });
+
+    // low + high * 2^256 = low + high * P_COMPLEMENT (mod p), twice
+    let mut carry = 0_u128;
+    let folded: [u64; 5] = core::array::from_fn(|idx| match idx {
+        4 => carry as u64,
+        _ => {
+            let sum = wide[idx] as u128 + wide[idx + 4] as u128 * P_COMPLEMENT as u128 + carry;
+            carry = sum >> 64;
+            sum as u64
+        }
+    });
+    let mut carry = folded[4] as u128 * P_COMPLEMENT as u128;
+    let result: U256 = core::array::from_fn(|idx| {
+        let sum = folded[idx] as u128 + carry;
+        carry = sum >> 64;
+        sum as u64
+    });
+    normalize(result, carry != 0)
+}
+
+fn pow_mod(base: U256, exponent: U256) -> U256 {
+    (0..256).rev().fold([1, 0, 0, 0], |result, bit| {
+        let result = mul_mod(result, result);
+        match (exponent[bit / 64] >> (bit % 64)) & 1 {
+            1 => mul_mod(result, base),
+            _ => result,
+        }
+    })
+}
+
+// x^3 + 7
+fn curve_rhs(x: U256) -> U256 {
+    add_mod(mul_mod(mul_mod(x, x), x), CURVE_B)
+}
+
+// Check whether (x, y) (big-endian, 32 bytes each) is on the curve
+pub fn is_on_curve(x: &[u8], y: &[u8]) -> bool {
+    let (x, y) = (from_be_bytes(x), from_be_bytes(y));
+    is_less(x, P) && is_less(y, P) && mul_mod(y, y) == curve_rhs(x)
+}
+
+// Get y of the point from x (big-endian, 32 bytes) and the parity of y,
+// or None if there is no such point on the curve
+pub fn decompress_y(x: &[u8], is_odd: bool) -> Option<[u8; 32]> {
+    let x = from_be_bytes(x);
+    if !is_less(x, P) {
+        return None;
+    }
+
+    let rhs = curve_rhs(x);
+    let y = pow_mod(rhs, SQRT_EXPONENT);
+    if mul_mod(y, y) != rhs {
+        return None;
+    }
+
+    Some(to_be_bytes(match (y[0] & 1 == 1) == is_odd {
+        true => y,
+        false => sub(P, y),
+    }))
+}
@@ -1,17 +1,73 @@
 extern crate alloc;
 
-use alloc::{string::String, vec::Vec};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, vec::Vec};
 
 /// Available types of input seed
 ///
 /// Currently supports:
-/// * { [Vector](Vec), [array], [slice] } of [bytes](u8)
-/// * [String], [str slice](str)
+/// * { [Vector](Vec), [array], [slice], [Box], [Rc], [Arc](alloc::sync::Arc), [Cow] } of [bytes](u8)
+/// * { [String], [str slice](str), [Box], [Rc], [Arc](alloc::sync::Arc), [Cow] } of [str]
+/// * Formatted seed: [`FormattedSeed`]
+/// * Ethereum address derived from a public key or a contract creation: [`EthAddress`](crate::EthAddress)
+/// * Addresses of other chains: [`BitcoinAddress`](crate::BitcoinAddress), [`SolanaAddress`](crate::SolanaAddress),
+///   [`CosmosAddress`](crate::CosmosAddress), [`EnsName`](crate::EnsName)
+/// * Address types of other crates, canonicalized to a well-formed Ethereum address seed automatically
+///   (no need to call [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed)):
+///   * `primitive_types::H160` (also `ethers::types::Address`), with crate feature `primitive_types` (or `ethers`)
+///   * `alloy_primitives::Address`, with crate feature `alloy_primitives`
+///
+/// # Example
+///
+/// ```
+/// use eth_blockies::*;
+/// use std::{borrow::Cow, rc::Rc, sync::Arc};
+///
+/// let icon = Blockies::<8>::data("eth-blockies");
+///
+/// assert_eq!(Blockies::<8>::data(Cow::Borrowed("eth-blockies")), icon);
+/// assert_eq!(Blockies::<8>::data(Rc::<str>::from("eth-blockies")), icon);
+/// assert_eq!(Blockies::<8>::data(Arc::<str>::from("eth-blockies")), icon);
+/// assert_eq!(Blockies::<8>::data(b"eth-blockies".to_vec().into_boxed_slice()), icon);
+/// ```
+///
+/// * Address types of other crates (with crate feature `primitive_types` or `alloy_primitives`)
+/// ```
+/// # #[cfg(any(feature = "primitive_types", feature = "alloy_primitives"))]
+/// # {
+/// use eth_blockies::*;
+///
+/// let addr_bytes = [
+///     0xe6, 0x86, 0xc1, 0x4f, 0xf9, 0xc1, 0x10, 0x38, 0xf2, 0xb1,
+///     0xc9, 0xad, 0x61, 0x7f, 0x23, 0x46, 0xcf, 0xb8, 0x17, 0xdc,
+/// ];
+/// let icon = EthBlockies::data("0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC".to_ethaddr_seed());
+///
+/// #[cfg(feature = "primitive_types")]
+/// {
+///     let h160 = primitive_types::H160(addr_bytes);
+///     assert_eq!(EthBlockies::data(h160), icon);
+/// }
+/// #[cfg(feature = "alloy_primitives")]
+/// {
+///     let address = alloy_primitives::Address::from(addr_bytes);
+///     assert_eq!(EthBlockies::data(address), icon);
+/// }
+/// # }
+/// ```
 pub trait SeedInput {
     #[doc(hidden)]
     /// Get reference of byte array inside
     fn as_seed_bytes(&self) -> &[u8];
 
+    #[doc(hidden)]
+    /// Get seed bytes to generate blockies from, which may be converted from the input
+    /// (e.g. address types canonicalized to a well-formed Ethereum address seed)
+    fn to_seed_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_seed_bytes())
+    }
+
     /// Convert given Ethereum address string or raw bytes data to the following well-formed Ethereum address seed: `0x(hex_letters_lowercase)`
     ///
     /// Apply this function on seeds to generate *standard* Ethereum blockies  
@@ -68,12 +124,67 @@ pub trait SeedInput {
     fn to_ethaddr_seed(&self) -> [u8; 42] {
         self.as_seed_bytes().to_ethaddr_seed()
     }
+
+    /// Convert given Ethereum address string or raw bytes data to the following abbreviated Ethereum address: `0x(first_4_hex_letters)…(last_4_hex_letters)`,
+    /// or return `None` if the seed is not an Ethereum address
+    ///
+    /// The abbreviation is taken from the well-formed address of [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed),
+    /// so that the same address in any valid form is abbreviated identically.  
+    /// Valid input seeds are the same as [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed),
+    /// with hex letters only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eth_blockies::SeedInput;
+    ///
+    /// let addr_abbrev = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///     .to_abbreviated_ethaddr();
+    /// assert_eq!(addr_abbrev.as_deref(), Some("0xe686\u{2026}17dc"));
+    ///
+    /// // same abbreviation for the same address in another form
+    /// assert_eq!(
+    ///     "e686c14ff9c11038f2b1c9ad617f2346cfb817dc".to_abbreviated_ethaddr(),
+    ///     addr_abbrev
+    /// );
+    ///
+    /// // not an Ethereum address
+    /// assert_eq!("generic_seed".to_abbreviated_ethaddr(), None);
+    /// ```
+    fn to_abbreviated_ethaddr(&self) -> Option<String> {
+        const HEAD_LEN: usize = 6; // '0x' + 4 hex letters
+        const TAIL_LEN: usize = 4;
+        const ELLIPSIS: char = '\u{2026}';
+
+        // to_ethaddr_seed() falls back to zeroes for other seeds, which must not be abbreviated
+        let ethaddr = self.to_ethaddr_seed();
+        let is_ethaddr = ethaddr.starts_with(b"0x")
+            && ethaddr[2..]
+                .iter()
+                .all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f'));
+        if !is_ethaddr {
+            return None;
+        }
+
+        Some(
+            ethaddr[..HEAD_LEN]
+                .iter()
+                .map(|byte| *byte as char)
+                .chain(core::iter::once(ELLIPSIS))
+                .chain(
+                    ethaddr[ethaddr.len() - TAIL_LEN..]
+                        .iter()
+                        .map(|byte| *byte as char),
+                )
+                .collect(),
+        )
+    }
 }
 
 // byte-slice
 impl SeedInput for &[u8] {
     fn as_seed_bytes(&self) -> &[u8] {
-        *self
+        self
     }
 
     fn to_ethaddr_seed(&self) -> [u8; 42] {
@@ -104,14 +215,15 @@ impl SeedInput for &[u8] {
                 let data: [u8; 20] = (*self).try_into().expect("to_ethaddr_seed()");
 
                 const HEX_TABLE: &[u8; 16] = b"0123456789abcdef";
-                ret[2..].chunks_exact_mut(2).zip(data.into_iter()).for_each(
-                    |(ret_chunk, data_elem)| {
+                ret[2..]
+                    .chunks_exact_mut(2)
+                    .zip(data)
+                    .for_each(|(ret_chunk, data_elem)| {
                         ret_chunk.copy_from_slice(&[
                             HEX_TABLE[(data_elem >> 4) as usize],
                             HEX_TABLE[(data_elem & 0x0F) as usize],
                         ]);
-                    },
-                );
+                    });
 
                 ret
             }
@@ -163,3 +275,113 @@ impl SeedInput for &String {
         (**self).as_seed_bytes()
     }
 }
+
+// smart pointers and cow, of byte-slice or string-slice
+macro_rules! impl_seed_input_for_deref {
+    ($($type:ty),*) => {
+        $(
+            impl SeedInput for $type {
+                fn as_seed_bytes(&self) -> &[u8] {
+                    (**self).as_ref()
+                }
+            }
+            impl SeedInput for &$type {
+                fn as_seed_bytes(&self) -> &[u8] {
+                    (***self).as_ref()
+                }
+            }
+        )*
+    };
+}
+impl_seed_input_for_deref!(
+    Box<[u8]>,
+    Box<str>,
+    Rc<[u8]>,
+    Rc<str>,
+    Cow<'_, [u8]>,
+    Cow<'_, str>
+);
+#[cfg(target_has_atomic = "ptr")]
+impl_seed_input_for_deref!(Arc<[u8]>, Arc<str>);
+
+/// Seed formatted from [`format_args!`](core::format_args), without `std`
+///
+/// # Example
+///
+/// ```
+/// use eth_blockies::*;
+///
+/// let user_id = 42;
+/// let seed = FormattedSeed::new(format_args!("user-{}", user_id));
+///
+/// assert_eq!(Blockies::<8>::data(&seed), Blockies::<8>::data("user-42"));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct FormattedSeed(String);
+
+impl FormattedSeed {
+    /// Format the arguments into a seed
+    pub fn new(args: core::fmt::Arguments) -> Self {
+        FormattedSeed(alloc::fmt::format(args))
+    }
+}
+
+impl From<core::fmt::Arguments<'_>> for FormattedSeed {
+    fn from(args: core::fmt::Arguments) -> Self {
+        FormattedSeed::new(args)
+    }
+}
+
+impl SeedInput for FormattedSeed {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+impl SeedInput for &FormattedSeed {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+// address types of other crates, canonicalized to a well-formed Ethereum address seed
+#[cfg(feature = "primitive_types")]
+impl SeedInput for primitive_types::H160 {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    fn to_seed_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.to_ethaddr_seed().to_vec())
+    }
+}
+#[cfg(feature = "primitive_types")]
+impl SeedInput for &primitive_types::H160 {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    fn to_seed_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.to_ethaddr_seed().to_vec())
+    }
+}
+
+#[cfg(feature = "alloy_primitives")]
+impl SeedInput for alloy_primitives::Address {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    fn to_seed_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.to_ethaddr_seed().to_vec())
+    }
+}
+#[cfg(feature = "alloy_primitives")]
+impl SeedInput for &alloy_primitives::Address {
+    fn as_seed_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    fn to_seed_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.to_ethaddr_seed().to_vec())
+    }
+}
@@ -0,0 +1,197 @@
+// SHA-256 (FIPS 180-4), as used in Base58Check checksums
+
+const BLOCK_LEN: usize = 64;
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    // message schedule
+    let mut schedule = [0_u32; 64];
+    block.chunks_exact(4).enumerate().for_each(|(idx, word)| {
+        schedule[idx] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    });
+    (16..64).for_each(|idx| {
+        let (w15, w2) = (schedule[idx - 15], schedule[idx - 2]);
+        let s0 = w15.rotate_right(7) ^ w15.rotate_right(18) ^ (w15 >> 3);
+        let s1 = w2.rotate_right(17) ^ w2.rotate_right(19) ^ (w2 >> 10);
+        schedule[idx] = schedule[idx - 16]
+            .wrapping_add(s0)
+            .wrapping_add(schedule[idx - 7])
+            .wrapping_add(s1);
+    });
+
+    // rounds, on working variables [a, b, c, d, e, f, g, h]
+    let vars = ROUND_CONSTANTS.iter().zip(schedule.iter()).fold(
+        *state,
+        |[a, b, c, d, e, f, g, h], (round_constant, word)| {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(choice)
+                .wrapping_add(*round_constant)
+                .wrapping_add(*word);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(majority);
+
+            [
+                temp1.wrapping_add(temp2),
+                a,
+                b,
+                c,
+                d.wrapping_add(temp1),
+                e,
+                f,
+                g,
+            ]
+        },
+    );
+
+    state
+        .iter_mut()
+        .zip(vars.iter())
+        .for_each(|(word, var)| *word = word.wrapping_add(*var));
+}
+
+// SHA-256 hash of the data
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state = INITIAL_STATE;
+
+    let mut blocks = data.chunks_exact(BLOCK_LEN);
+    blocks
+        .by_ref()
+        .for_each(|block| compress(&mut state, block));
+
+    // padding: 0x80, zeroes, then the bit length in 8 bytes (in 1 or 2 blocks)
+    let remainder = blocks.remainder();
+    let mut last_blocks = [0_u8; BLOCK_LEN * 2];
+    last_blocks[..remainder.len()].copy_from_slice(remainder);
+    last_blocks[remainder.len()] = 0x80;
+    let last_blocks_len = match remainder.len() + 1 + 8 <= BLOCK_LEN {
+        true => BLOCK_LEN,
+        false => BLOCK_LEN * 2,
+    };
+    last_blocks[last_blocks_len - 8..last_blocks_len]
+        .copy_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+    last_blocks[..last_blocks_len]
+        .chunks_exact(BLOCK_LEN)
+        .for_each(|block| compress(&mut state, block));
+
+    let mut hash = [0_u8; 32];
+    hash.chunks_exact_mut(4)
+        .zip(state.iter())
+        .for_each(|(chunk, word)| chunk.copy_from_slice(&word.to_be_bytes()));
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sha256;
+
+    fn hex(hash: [u8; 32]) -> alloc::string::String {
+        hash.iter()
+            .map(|byte| alloc::format!("{:02x}", byte))
+            .collect()
+    }
+
+    #[test]
+    fn known_values() {
+        assert_eq!(
+            hex(sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // 56 bytes: padding spans 2 blocks
+        assert_eq!(
+            hex(sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        // 1,000,000 bytes of 'a'
+        assert_eq!(
+            hex(sha256(&alloc::vec![b'a'; 1_000_000])),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+}
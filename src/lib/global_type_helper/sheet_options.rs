@@ -0,0 +1,63 @@
+use super::{CaptionText, RgbPixel};
+
+/// Options for generating a sheet of multiple blockies
+///
+/// A sheet lays out blockies of each seed in a grid, from left to right and top to bottom,
+/// with optional labels under each blockies.
+///
+/// # Example
+/// ```
+/// use eth_blockies::*;
+///
+/// // 4 blockies per row with labels, on transparent background
+/// let sheet_options = SheetOptions {
+///     columns: 4,
+///     background: None,
+///     labels: true,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(SheetOptions::default().columns, 8);
+/// assert_ne!(sheet_options, SheetOptions::default());
+/// ```
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct SheetOptions {
+    /// Maximum number of blockies in each row (Default: `8`)
+    ///
+    /// `0` is regarded as `1`.
+    pub columns: usize,
+    /// Dimension of each blockies in pixels (Default: `(64, 64)`)
+    pub icon_dimension: (usize, usize),
+    /// Spacing between blockies and around the sheet in pixels (Default: `8`)
+    pub spacing: usize,
+    /// Background color of the sheet, or transparent if `None` (Default: `Some((255, 255, 255))`)
+    pub background: Option<RgbPixel>,
+    /// Draw a label under each blockies, with the built-in bitmap font (Default: `false`)
+    ///
+    /// A label wider than the blockies is truncated with an ellipsis.
+    /// Characters not in printable ascii are drawn as `?`.
+    pub labels: bool,
+    /// Text of labels (Default: [`CaptionText::Seed`])
+    pub label_text: CaptionText,
+    /// Color of labels (Default: `(0, 0, 0)`)
+    pub label_color: RgbPixel,
+    /// Scale of the 3x5 label font, in pixels per font pixel (Default: `1`)
+    ///
+    /// `0` is regarded as `1`.
+    pub label_scale: usize,
+}
+
+impl Default for SheetOptions {
+    fn default() -> Self {
+        SheetOptions {
+            columns: 8,
+            icon_dimension: (64, 64),
+            spacing: 8,
+            background: Some((255, 255, 255)),
+            labels: false,
+            label_text: CaptionText::Seed,
+            label_color: (0, 0, 0),
+            label_scale: 1,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// MT19937, the same as `mersenne-twister` npm package used by jazzicon
+
+const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908_b0df;
+const UPPER_MASK: u32 = 0x8000_0000;
+const LOWER_MASK: u32 = 0x7fff_ffff;
+
+pub struct MersenneTwister {
+    state: [u32; N],
+    idx: usize,
+}
+
+impl MersenneTwister {
+    // init_genrand()
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0_u32; N];
+        state[0] = seed;
+        (1..N).for_each(|idx| {
+            let prev = state[idx - 1];
+            state[idx] = 1_812_433_253_u32
+                .wrapping_mul(prev ^ (prev >> 30))
+                .wrapping_add(idx as u32);
+        });
+
+        Self { state, idx: N }
+    }
+
+    // genrand_int32()
+    pub fn next_u32(&mut self) -> u32 {
+        if self.idx >= N {
+            self.twist();
+        }
+
+        let mut y = self.state[self.idx];
+        self.idx += 1;
+
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9d2c_5680;
+        y ^= (y << 15) & 0xefc6_0000;
+        y ^ (y >> 18)
+    }
+
+    // random(): f64 in range [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        self.next_u32() as f64 * (1_f64 / 4_294_967_296_f64)
+    }
+
+    fn twist(&mut self) {
+        (0..N).for_each(|idx| {
+            let y = (self.state[idx] & UPPER_MASK) | (self.state[(idx + 1) % N] & LOWER_MASK);
+            self.state[idx] = self.state[(idx + M) % N]
+                ^ (y >> 1)
+                ^ match y & 1 {
+                    0 => 0,
+                    _ => MATRIX_A,
+                };
+        });
+        self.idx = 0;
+    }
+}
@@ -0,0 +1,375 @@
+mod mersenne_twister;
+
+use crate::data_encoder::indexed_svg::hex_color;
+use crate::global_type_helper::{RgbPixel, SeedInput};
+use mersenne_twister::MersenneTwister;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// base colors of jazzicon, before the hue shift
+const COLORS: [RgbPixel; 10] = [
+    (0x01, 0x88, 0x8c), // teal
+    (0xfc, 0x75, 0x00), // bright orange
+    (0x03, 0x4f, 0x5d), // dark teal
+    (0xf7, 0x3f, 0x01), // orangered
+    (0xfc, 0x19, 0x60), // magenta
+    (0xc7, 0x14, 0x4c), // raspberry
+    (0xf3, 0xc1, 0x00), // goldenrod
+    (0x15, 0x98, 0xf2), // lightning blue
+    (0x24, 0x65, 0xe1), // sail blue
+    (0xf1, 0x9e, 0x02), // gold
+];
+const SHAPE_COUNT: usize = 3;
+// range of the hue shift in degrees
+const WOBBLE: f64 = 30_f64;
+// border radius of the container in pixels
+const BORDER_RADIUS: f64 = 50_f64;
+
+/// Jazzicon, the default identicon of MetaMask
+///
+/// This is an equivalent of [`jazzicon`](https://github.com/MetaMask/jazzicon),
+/// which consists of a background and 3 rotated squares in hue-shifted colors.
+/// Unlike blockies, the seed is a 32-bit integer:
+/// MetaMask uses the first 8 hex digits of an address (see [`from_ethaddr()`](Jazzicon::from_ethaddr)).
+///
+/// # Example
+///
+/// ```
+/// use eth_blockies::*;
+///
+/// let icon = Jazzicon::from_ethaddr("0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC").unwrap();
+/// assert_eq!(icon, Jazzicon::new(0xe686c14f));
+///
+/// assert_eq!(icon.background(), (0xf5, 0xd8, 0x00));
+/// let shape = &icon.shapes()[0];
+/// assert_eq!(shape.color(), (0xfb, 0x18, 0x4d));
+/// assert_eq!(format!("{:.1}", shape.rotate()), "407.9");
+///
+/// let (tx, ty) = shape.translate(100);
+/// assert!((tx - 1.5690453484104903).abs() < 1e-9);
+/// assert!((ty - -0.14722829505598234).abs() < 1e-9);
+///
+/// // svg of 100x100
+/// let svg = icon.svg_data(100);
+/// assert!(svg.contains("fill=\"#017e8e\""));
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Jazzicon {
+    seed: u32,
+    background: RgbPixel,
+    shapes: [JazziconShape; SHAPE_COUNT],
+}
+
+/// Square of [`Jazzicon`], of the same size as the icon
+#[derive(PartialEq, Clone, Debug)]
+pub struct JazziconShape {
+    idx: usize,
+    first_rot: f64,
+    velocity: f64,
+    second_rot: f64,
+    color: RgbPixel,
+}
+
+impl Jazzicon {
+    /// Generate jazzicon from a 32-bit seed
+    pub fn new(seed: u32) -> Self {
+        let mut generator = MersenneTwister::new(seed);
+
+        let amount = generator.next_f64() * WOBBLE - WOBBLE / 2_f64;
+        let mut remaining_colors: Vec<RgbPixel> = COLORS
+            .iter()
+            .map(|color| rotate_hue(*color, amount))
+            .collect();
+
+        let background = pick_color(&mut generator, &mut remaining_colors);
+        let shapes = core::array::from_fn(|idx| {
+            let first_rot = generator.next_f64();
+            let velocity = generator.next_f64();
+            let second_rot = generator.next_f64();
+            JazziconShape {
+                idx,
+                first_rot,
+                velocity,
+                second_rot,
+                color: pick_color(&mut generator, &mut remaining_colors),
+            }
+        });
+
+        Self {
+            seed,
+            background,
+            shapes,
+        }
+    }
+
+    /// Generate jazzicon of an Ethereum address, the same as MetaMask,
+    /// or return `None` if `addr` is not an Ethereum address
+    ///
+    /// The seed is the first 8 hex digits of the address (`parseInt(addr.slice(2, 10), 16)`).
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Address in any form valid for [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eth_blockies::*;
+    ///
+    /// let icon = Jazzicon::from_ethaddr("e686c14ff9c11038f2b1c9ad617f2346cfb817dc");
+    /// assert_eq!(icon, Some(Jazzicon::new(0xe686c14f)));
+    ///
+    /// // not an Ethereum address
+    /// assert_eq!(Jazzicon::from_ethaddr("0xe686c14FF9C11038F2B1c9aD617F2346CFB817dZ"), None);
+    /// ```
+    pub fn from_ethaddr<I: SeedInput>(addr: I) -> Option<Self> {
+        let ethaddr = addr.to_ethaddr_seed();
+        if !ethaddr.starts_with(b"0x") {
+            return None;
+        }
+        let digits: Vec<u32> = ethaddr[2..]
+            .iter()
+            .map(|c| match c {
+                b'0'..=b'9' => Some((c - b'0') as u32),
+                b'a'..=b'f' => Some((c - b'a' + 10) as u32),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+        let seed = digits[..8]
+            .iter()
+            .fold(0_u32, |acc, digit| (acc << 4) | digit);
+
+        Some(Self::new(seed))
+    }
+
+    /// Get the background color
+    pub fn background(&self) -> RgbPixel {
+        self.background
+    }
+
+    /// Get the squares, in the order of drawing
+    pub fn shapes(&self) -> &[JazziconShape] {
+        &self.shapes
+    }
+
+    /// Get svg data of the jazzicon, of `diameter` pixels
+    ///
+    /// The icon is clipped as the container of jazzicon does (border radius of 50px),
+    /// which is a circle if `diameter` is 100 or less.
+    pub fn svg_data(&self, diameter: usize) -> String {
+        let clip_id = format!("jazzicon-{:08x}-{}", self.seed, diameter);
+        let radius = BORDER_RADIUS.min(diameter as f64 / 2_f64);
+        let center = diameter as f64 / 2_f64;
+
+        let shapes: String = self
+            .shapes
+            .iter()
+            .map(|shape| {
+                let (tx, ty) = shape.translate(diameter);
+                format!(
+                    "<rect x=\"0\" y=\"0\" width=\"{d}\" height=\"{d}\" \
+                     transform=\"translate({tx} {ty}) rotate({rot:.1} {c} {c})\" fill=\"{fill}\"/>",
+                    d = diameter,
+                    tx = tx,
+                    ty = ty,
+                    rot = shape.rotate(),
+                    c = center,
+                    fill = hex_color(shape.color),
+                )
+            })
+            .collect();
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{d}\" height=\"{d}\" \
+             viewBox=\"0 0 {d} {d}\"><clipPath id=\"{id}\">\
+             <rect width=\"{d}\" height=\"{d}\" rx=\"{r}\"/></clipPath>\
+             <g clip-path=\"url(#{id})\"><rect width=\"{d}\" height=\"{d}\" fill=\"{bg}\"/>{shapes}</g></svg>",
+            d = diameter,
+            id = clip_id,
+            r = radius,
+            bg = hex_color(self.background),
+            shapes = shapes,
+        )
+    }
+}
+
+impl JazziconShape {
+    /// Get the fill color
+    pub fn color(&self) -> RgbPixel {
+        self.color
+    }
+
+    /// Get the translation `(x, y)` in pixels, for jazzicon of `diameter` pixels
+    pub fn translate(&self, diameter: usize) -> (f64, f64) {
+        let diameter = diameter as f64;
+        let total = SHAPE_COUNT as f64;
+
+        let angle = core::f64::consts::PI * 2_f64 * self.first_rot;
+        let velocity = diameter / total * self.velocity + (self.idx as f64 * diameter / total);
+        let (sin, cos) = sin_cos(angle);
+
+        (cos * velocity, sin * velocity)
+    }
+
+    /// Get the rotation in degrees around the centre, applied after the translation
+    pub fn rotate(&self) -> f64 {
+        (self.first_rot * 360_f64) + self.second_rot * 180_f64
+    }
+}
+
+// pick a random color out of the remaining colors
+fn pick_color(generator: &mut MersenneTwister, remaining_colors: &mut Vec<RgbPixel>) -> RgbPixel {
+    // jazzicon discards a random number here
+    generator.next_f64();
+    let idx = (remaining_colors.len() as f64 * generator.next_f64()) as usize;
+    remaining_colors.remove(idx)
+}
+
+// rotate the hue, the same as `color` npm package:
+// each conversion between rgb and hsl is rounded to integers
+fn rotate_hue(rgb: RgbPixel, degrees: f64) -> RgbPixel {
+    fn round(value: f64) -> f64 {
+        ((value + 0.5_f64) as u32) as f64
+    }
+
+    let (hue, saturation, lightness) = {
+        let (r, g, b) = (
+            rgb.0 as f64 / 255_f64,
+            rgb.1 as f64 / 255_f64,
+            rgb.2 as f64 / 255_f64,
+        );
+        let (min, max) = (r.min(g).min(b), r.max(g).max(b));
+        let delta = max - min;
+
+        let hue = match max == min {
+            true => 0_f64,
+            false if r == max => (g - b) / delta,
+            false if g == max => 2_f64 + (b - r) / delta,
+            false => 4_f64 + (r - g) / delta,
+        };
+        let hue = match (hue * 60_f64).min(360_f64) {
+            hue if hue < 0_f64 => hue + 360_f64,
+            hue => hue,
+        };
+        let lightness = (min + max) / 2_f64;
+        let saturation = match max == min {
+            true => 0_f64,
+            false if lightness <= 0.5_f64 => delta / (max + min),
+            false => delta / (2_f64 - max - min),
+        };
+
+        (
+            round(hue),
+            round(saturation * 100_f64),
+            round(lightness * 100_f64),
+        )
+    };
+
+    let hue = match (hue + degrees) % 360_f64 {
+        hue if hue < 0_f64 => 360_f64 + hue,
+        hue => hue,
+    };
+
+    let (h, s, l) = (hue / 360_f64, saturation / 100_f64, lightness / 100_f64);
+    let rgb_frac: [f64; 3] = match s == 0_f64 {
+        true => [l; 3],
+        false => {
+            let t2 = match l < 0.5_f64 {
+                true => l * (1_f64 + s),
+                false => l + s - l * s,
+            };
+            let t1 = 2_f64 * l - t2;
+            core::array::from_fn(|idx| {
+                let t3 = match h + 1_f64 / 3_f64 * -(idx as f64 - 1_f64) {
+                    t3 if t3 < 0_f64 => t3 + 1_f64,
+                    t3 if t3 > 1_f64 => t3 - 1_f64,
+                    t3 => t3,
+                };
+                match t3 {
+                    _ if 6_f64 * t3 < 1_f64 => t1 + (t2 - t1) * 6_f64 * t3,
+                    _ if 2_f64 * t3 < 1_f64 => t2,
+                    _ if 3_f64 * t3 < 2_f64 => t1 + (t2 - t1) * (2_f64 / 3_f64 - t3) * 6_f64,
+                    _ => t1,
+                }
+            })
+        }
+    };
+
+    (
+        round(rgb_frac[0] * 255_f64) as u8,
+        round(rgb_frac[1] * 255_f64) as u8,
+        round(rgb_frac[2] * 255_f64) as u8,
+    )
+}
+
+// (sin, cos) of a non-negative angle in radians, without std
+fn sin_cos(angle: f64) -> (f64, f64) {
+    use core::f64::consts::FRAC_PI_2;
+
+    // reduce to [-pi/4, pi/4] and the quadrant
+    let quadrant = (angle / FRAC_PI_2 + 0.5_f64) as u64;
+    let x = angle - quadrant as f64 * FRAC_PI_2;
+
+    // taylor series, accurate enough in the reduced range
+    let x2 = x * x;
+    let (sin, cos) = (1..=10).rev().fold((1_f64, 1_f64), |(sin, cos), n| {
+        let n = n as f64;
+        (
+            1_f64 - x2 / ((2_f64 * n) * (2_f64 * n + 1_f64)) * sin,
+            1_f64 - x2 / ((2_f64 * n - 1_f64) * (2_f64 * n)) * cos,
+        )
+    });
+    let (sin, cos) = (x * sin, cos);
+
+    match quadrant % 4 {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Jazzicon;
+
+    #[test]
+    fn from_ethaddr_valid() {
+        let icon = Jazzicon::new(0xe686c14f);
+        [
+            "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC",
+            "0XE686C14FF9C11038F2B1C9AD617F2346CFB817DC",
+            "e686c14ff9c11038f2b1c9ad617f2346cfb817dc",
+        ]
+        .iter()
+        .for_each(|addr| assert_eq!(Jazzicon::from_ethaddr(*addr).as_ref(), Some(&icon)));
+        assert_eq!(
+            Jazzicon::from_ethaddr([
+                0xe6, 0x86, 0xc1, 0x4f, 0xf9, 0xc1, 0x10, 0x38, 0xf2, 0xb1, 0xc9, 0xad, 0x61, 0x7f,
+                0x23, 0x46, 0xcf, 0xb8, 0x17, 0xdc,
+            ]),
+            Some(icon)
+        );
+    }
+
+    #[test]
+    fn from_ethaddr_invalid() {
+        [
+            // too short
+            "",
+            "abc",
+            "0xe686c14f",
+            // not hex digits
+            "0x-_______________________________________",
+            "0x-___1038f2b1c9ad617f2346cfb817dcffffffff",
+            "0xe686c14ff9c11038f2b1c9ad617f2346cfb817dg",
+            "0x e686c14ff9c11038f2b1c9ad617f2346cfb817d",
+            // without '0x' prefix, but of 42 letters
+            "00e686c14ff9c11038f2b1c9ad617f2346cfb817dc",
+        ]
+        .iter()
+        .for_each(|addr| assert_eq!(Jazzicon::from_ethaddr(*addr), None, "{:?}", addr));
+    }
+}
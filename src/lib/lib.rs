@@ -1,7 +1,7 @@
 //! A lightweight library in pure Rust to get Ethereum-style blocky identicon data,
 //! which can be used for generating blockies icon images, printing to terminal, etc.
 //!
-//! Useful when getting raw RGB data of Ethereum-style blockies, as well as complete png image files.
+//! Useful when getting raw RGB data of Ethereum-style blockies, as well as complete image files (png, bmp, ppm, tga, svg).
 //!
 //!
 //! # Basic Usage
@@ -75,6 +75,7 @@
 //!    let in_indexed_2d_arr = Icon::indexed_data("eth-blockies");
 //!    let in_gray_2d_arr = Icon::data_mapped("eth-blockies", to_gray);
 //!    let in_png_data_vec = Icon::png_data("eth-blockies", (128, 128));
+//!    let in_bmp_data_vec = Icon::bmp_data("eth-blockies", (128, 128));
 //!    ```
 //!
 //!
@@ -146,25 +147,65 @@
 //!   * This feature enables following functions:
 //!     * [`compressed_png_data()`](BlockiesGenerator::compressed_png_data)
 //!     * [`compressed_png_data_base64()`](BlockiesGenerator::compressed_png_data_base64)
-//!   * This feature adds a following external dependency:
-//!     * [`deflate`] crate
+//!     * [`compressed_png_data_with_options()`](BlockiesGenerator::compressed_png_data_with_options)
+//!     * [`compressed_sheet_png_data()`](BlockiesGenerator::compressed_sheet_png_data)
+//!   * This feature does not add any external dependency.
 //!   * If png compression is not needed,
 //!     disable this feature as follows when adding the crate:
 //!     * E.g.
 //!       * Shell: `cargo add eth-blockies@1.1 --no-default-features`
 //!       * Cargo.toml: `eth-blockies = { version = "1.1", default-features = false }`
+//! * `alloy_primitives`
+//!   * This feature implements [`SeedInput`] for `alloy_primitives::Address`,
+//!     canonicalized to a well-formed Ethereum address seed automatically.
+//!   * This feature adds `alloy-primitives` as a dependency (which requires a newer Rust than this crate).
+//! * `ethers`
+//!   * Same as `primitive_types` (`ethers::types::Address` is `primitive_types::H160`).
+//! * `qoi`
+//!   * This feature enables following function:
+//!     * [`qoi_data()`](BlockiesGenerator::qoi_data)
+//!   * This feature does not add any external dependency.
+//! * `primitive_types`
+//!   * This feature implements [`SeedInput`] for `primitive_types::H160` (including `ethers::types::Address`),
+//!     canonicalized to a well-formed Ethereum address seed automatically.
+//!   * This feature adds `primitive-types` as a dependency.
+//! * `qr`
+//!   * This feature enables following functions, for QR code of Ethereum address with blockies in the centre:
+//!     * [`qr_png_data()`](BlockiesGenerator::qr_png_data)
+//!     * [`compressed_qr_png_data()`](BlockiesGenerator::compressed_qr_png_data) (with `compressed_png`)
+//!     * [`qr_svg_data()`](BlockiesGenerator::qr_svg_data)
+//!   * This feature does not add any external dependency.
+//! * `webp_lossless`
+//!   * This feature enables following function:
+//!     * [`webp_data()`](BlockiesGenerator::webp_data)
+//!   * This feature does not add any external dependency.
+//! * `std`
+//!   * This feature enables [`RenderCache`], a thread-safe LRU cache of encoded blockies data.
+//!   * This feature does not add any external dependency, but requires the standard library.
+//! * `server`
+//!   * This feature enables `serve` subcommand of the binary, serving blockies images over http.
+//!   * This feature does not add any external dependency, but requires the standard library. (implies `std`)
 
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 mod blockies;
-pub use blockies::{Blockies, BlockiesHelper};
+pub use blockies::{Blockies, BlockiesHelper, KeyGenerator, SeedHasher};
 mod global_type_helper;
 pub use global_type_helper::*;
 mod data_encoder;
 use data_encoder::*;
+mod jazzicon;
+pub use jazzicon::{Jazzicon, JazziconShape};
+#[cfg(feature = "std")]
+mod render_cache;
+#[cfg(feature = "std")]
+pub use render_cache::{RenderCache, RenderFormat};
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -176,7 +217,10 @@ pub type EthBlockies<T> = Blockies<8, T>;
 /// Used for generating data of a new blocky identicon in various form, including:
 /// * Raw blockies data
 /// * Terminal printable string (ANSI sequence)
-/// * Image file data (png)
+/// * Image file data (png, bmp, ppm, tga, svg)
+///
+/// Methods added after v1.1.0 have default implementations,
+/// so that existing implementors of this trait keep compiling.
 pub trait BlockiesGenerator<const S: usize> {
     /// Generate an Ethereum-style blockies data
     ///
@@ -480,7 +524,7 @@ pub trait BlockiesGenerator<const S: usize> {
     ///
     /// * `seed` - Input seed
     /// * `output_dim` - (width, height) of output png binary data.
-    ///                  Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
     /// * `is_utf8` - Determine whether to print using UTF-8 characters.
     ///   * [`true`]: Output data contain UTF-8 characters,
     ///     which makes blockies printed in compact size.
@@ -557,7 +601,7 @@ pub trait BlockiesGenerator<const S: usize> {
     ///
     /// * `seed` - Input seed
     /// * `output_dim` - (width, height) of output png binary data.
-    ///                  Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
     ///
     /// # Return
     ///
@@ -634,7 +678,7 @@ pub trait BlockiesGenerator<const S: usize> {
     ///
     /// * `seed` - Input seed
     /// * `output_dim` - (width, height) of output png binary data.
-    ///                  Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
     ///
     /// # Return
     ///
@@ -702,7 +746,7 @@ pub trait BlockiesGenerator<const S: usize> {
     ///
     /// * `seed` - Input seed
     /// * `output_dim` - (width, height) of output png binary data.
-    ///                  Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
     /// * `data_uri_output` - Determine if the result output is prefixed with data URI scheme
     ///
     /// # Return
@@ -788,7 +832,7 @@ pub trait BlockiesGenerator<const S: usize> {
     ///
     /// * `seed` - Input seed
     /// * `output_dim` - (width, height) of output png binary data.
-    ///                  Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
     /// * `data_uri_output` - Determine if the result output is prefixed with data URI scheme
     ///
     /// # Return
@@ -856,64 +900,1190 @@ pub trait BlockiesGenerator<const S: usize> {
         output_dim: (usize, usize),
         data_uri_output: bool,
     ) -> String;
-}
-
-impl<const S: usize> BlockiesGenerator<S> for Blockies<S> {
-    fn data<I: SeedInput>(seed: I) -> Blockies<S, RgbPixel> {
-        Blockies::data_mapped(seed, |rgb_pixel| rgb_pixel)
-    }
 
-    fn data_mapped<I: SeedInput, T: Clone, F: Fn(RgbPixel) -> T>(
+    /// Generate an Ethereum-style blockies data in uncompressed indexed png format, with png options
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output png binary data.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    /// * `options` - Options for png generation (See [`PngOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of png binary data
+    ///
+    /// # Example
+    ///
+    /// * Get uncompressed png data of RGB blockies, with reduced bit depth if possible
+    ///
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator, PngOptions};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (16, 16); // multiples of size recommended
+    ///   let options = PngOptions {
+    ///       reduce_bit_depth: true,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate blockies
+    ///   let img_png_data =
+    ///       EthBlockies::png_data_with_options(seed, output_dim, options);
+    ///
+    ///   // test
+    ///   {
+    ///       // same as png_data() with default options
+    ///       assert_eq!(
+    ///           EthBlockies::png_data_with_options(seed, output_dim, PngOptions::default()),
+    ///           EthBlockies::png_data(seed, output_dim)
+    ///       );
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.png").unwrap()
+    ///       //     .write_all(&img_png_data);
+    ///   }
+    ///   ```
+    fn png_data_with_options<I: SeedInput>(
         seed: I,
-        map_fn: F,
-    ) -> Blockies<S, T> {
-        let (palette, class_bitmap) = Blockies::indexed_data_mapped(seed, map_fn);
-
-        class_bitmap.map_2d(|class, _| palette[class].clone())
-    }
-
-    fn indexed_data<I: SeedInput>(seed: I) -> (RgbPalette, Blockies<S, ColorClass>) {
-        blockies::new_blockies(seed.as_seed_bytes())
+        output_dim: (usize, usize),
+        options: PngOptions,
+    ) -> Vec<u8> {
+        let seed = seed.to_seed_bytes();
+        let (palette, bitmap) = Self::indexed_data(&*seed);
+        indexed_png::rendered_data_to_png(
+            blockies::render(palette, bitmap, output_dim, options.render, &seed),
+            output_dim,
+            false,
+            options,
+            (&*seed, S),
+        )
     }
 
-    fn indexed_data_mapped<I: SeedInput, T: Clone, F: Fn(RgbPixel) -> T>(
+    /// Generate an Ethereum-style blockies data in compressed indexed png format, with png options
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output png binary data.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    /// * `options` - Options for png generation (See [`PngOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of png binary data
+    ///
+    /// # Example
+    ///
+    /// * Get the smallest compressed png data of RGB blockies
+    ///
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator, PngFilter, PngOptions};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (128, 128); // multiples of size recommended
+    ///   let options = PngOptions {
+    ///       filter: PngFilter::Smallest,
+    ///       reduce_bit_depth: true,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate blockies
+    ///   let img_png_data =
+    ///       EthBlockies::compressed_png_data_with_options(seed, output_dim, options);
+    ///
+    ///   // test
+    ///   {
+    ///       assert!(img_png_data.len()
+    ///           <= EthBlockies::compressed_png_data(seed, output_dim).len());
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.png").unwrap()
+    ///       //     .write_all(&img_png_data);
+    ///   }
+    ///   ```
+    #[cfg(feature = "compressed_png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compressed_png")))]
+    fn compressed_png_data_with_options<I: SeedInput>(
         seed: I,
-        map_fn: F,
-    ) -> (Palette<T>, Blockies<S, ColorClass>) {
-        let (rgb_palette, class_bitmap) = Blockies::indexed_data(seed.as_seed_bytes());
+        output_dim: (usize, usize),
+        options: PngOptions,
+    ) -> Vec<u8> {
+        let seed = seed.to_seed_bytes();
+        let (palette, bitmap) = Self::indexed_data(&*seed);
+        indexed_png::rendered_data_to_png(
+            blockies::render(palette, bitmap, output_dim, options.render, &seed),
+            output_dim,
+            true,
+            options,
+            (&*seed, S),
+        )
+    }
 
-        (rgb_palette.map(map_fn), class_bitmap)
+    /// Generate an Ethereum-style blockies data in uncompressed indexed bmp format
+    ///
+    /// Pixels are stored as 4-bit palette indices, with 3 entries in the color table.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output bmp binary data.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of bmp binary data
+    ///
+    /// # Example
+    ///
+    /// * Get bmp data of RGB blockies
+    ///
+    ///   * General identicon
+    ///   ```
+    ///   use eth_blockies::{Blockies, BlockiesGenerator};
+    ///   type Identicon<T> = Blockies<11, T>; // user-defined blockies type
+    ///
+    ///   // args
+    ///   let seed = "general string seed";
+    ///   let output_dim = (64, 64); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let icon_bmp_data = Blockies::<11>::bmp_data(seed, output_dim);
+    ///   let icon_bmp_data_alias = Identicon::bmp_data(seed, output_dim);
+    ///
+    ///   // test
+    ///   {
+    ///       assert_eq!(icon_bmp_data, icon_bmp_data_alias);
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("icon.bmp").unwrap()
+    ///       //     .write_all(&icon_bmp_data);
+    ///   }
+    ///   ```
+    ///
+    ///   * Ethereum blockies
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (16, 16); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let img_bmp_data = EthBlockies::bmp_data(seed, output_dim);
+    ///
+    ///   // test
+    ///   {
+    ///       // file header: signature, file size, pixel array offset
+    ///       assert_eq!(&img_bmp_data[0..2], b"BM");
+    ///       assert_eq!(&img_bmp_data[2..6], &194_u32.to_le_bytes());
+    ///       assert_eq!(&img_bmp_data[10..14], &66_u32.to_le_bytes());
+    ///
+    ///       // color table: (b, g, r, 0) for each color class
+    ///       assert_eq!(&img_bmp_data[54..66], &[
+    ///           52, 173, 38, 0, 77, 222, 132, 0, 40, 201, 4, 0,
+    ///       ]);
+    ///
+    ///       // last row in file: top row of blockies
+    ///       assert_eq!(&img_bmp_data[186..194], &[0x11; 8]);
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.bmp").unwrap()
+    ///       //     .write_all(&img_bmp_data);
+    ///   }
+    ///   ```
+    fn bmp_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Vec<u8> {
+        let (palette, bitmap) = Self::indexed_data(seed);
+        indexed_bmp::indexed_data_to_bmp(palette, bitmap.scale(output_dim), output_dim)
     }
 
-    fn ansiseq_data<I: SeedInput>(
-        seed: I,
-        output_dim: (usize, usize),
-        is_utf8: bool,
-    ) -> Vec<String> {
-        let (palette, bitmap) = Blockies::<S>::indexed_data(seed);
-        match is_utf8 {
-            true => ansi_seq::indexed_data_to_ansiseq_utf8(
-                palette,
-                bitmap.scale(output_dim),
-                output_dim,
-            ),
-            false => ansi_seq::indexed_data_to_ansiseq_ascii(
-                palette,
-                bitmap.scale(output_dim),
-                output_dim,
-            ),
-        }
+    /// Generate an Ethereum-style blockies data in binary ppm (`P6`) format
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output ppm binary data.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of ppm binary data
+    ///
+    /// # Example
+    ///
+    /// * Get ppm data of RGB blockies
+    ///
+    ///   * General identicon
+    ///   ```
+    ///   use eth_blockies::{Blockies, BlockiesGenerator};
+    ///   type Identicon<T> = Blockies<13, T>; // user-defined blockies type
+    ///
+    ///   // args
+    ///   let seed = "general string seed";
+    ///   let output_dim = (64, 64); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let icon_ppm_data = Blockies::<13>::ppm_data(seed, output_dim);
+    ///   let icon_ppm_data_alias = Identicon::ppm_data(seed, output_dim);
+    ///
+    ///   // test
+    ///   {
+    ///       assert_eq!(icon_ppm_data, icon_ppm_data_alias);
+    ///   }
+    ///   ```
+    ///
+    ///   * Ethereum blockies
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (16, 16); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let img_ppm_data = EthBlockies::ppm_data(seed, output_dim);
+    ///
+    ///   // test
+    ///   {
+    ///       let header = b"P6\n16 16\n255\n";
+    ///       assert_eq!(&img_ppm_data[..header.len()], header);
+    ///       assert_eq!(img_ppm_data.len(), header.len() + 16 * 16 * 3);
+    ///
+    ///       // (r, g, b) of the first pixel
+    ///       assert_eq!(&img_ppm_data[header.len()..][..3], &[132, 222, 77]);
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.ppm").unwrap()
+    ///       //     .write_all(&img_ppm_data);
+    ///   }
+    ///   ```
+    fn ppm_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Vec<u8> {
+        let (palette, bitmap) = Self::indexed_data(seed);
+        rgb_ppm::indexed_data_to_ppm(palette, bitmap.scale(output_dim), output_dim)
     }
 
-    fn png_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Vec<u8> {
-        let (palette, bitmap) = Blockies::<S>::indexed_data(seed);
-        indexed_png::indexed_data_to_png(palette, bitmap.scale(output_dim), output_dim, false)
+    /// Generate an Ethereum-style blockies data in uncompressed color-mapped tga format
+    ///
+    /// Pixels are stored as 8-bit palette indices, from top-left to bottom-right.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output tga binary data.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///   Each of width and height should not exceed [`u16::MAX`].
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of tga binary data, or `None` if width or height exceeds [`u16::MAX`]
+    ///
+    /// # Example
+    ///
+    /// * Get tga data of RGB blockies
+    ///
+    ///   * General identicon
+    ///   ```
+    ///   use eth_blockies::{Blockies, BlockiesGenerator};
+    ///   type Identicon<T> = Blockies<10, T>; // user-defined blockies type
+    ///
+    ///   // args
+    ///   let seed = "general string seed";
+    ///   let output_dim = (64, 64); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let icon_tga_data = Blockies::<10>::tga_data(seed, output_dim).unwrap();
+    ///   let icon_tga_data_alias = Identicon::tga_data(seed, output_dim).unwrap();
+    ///
+    ///   // test
+    ///   {
+    ///       assert_eq!(icon_tga_data, icon_tga_data_alias);
+    ///   }
+    ///   ```
+    ///
+    ///   * Ethereum blockies
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (16, 16); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let img_tga_data = EthBlockies::tga_data(seed, output_dim).unwrap();
+    ///
+    ///   // test
+    ///   {
+    ///       // header
+    ///       assert_eq!(&img_tga_data[..18], &[
+    ///           0, 1, 1, 0, 0, 3, 0, 24, 0, 0, 0, 0, 16, 0, 16, 0, 8, 0x20,
+    ///       ]);
+    ///
+    ///       // color map: (b, g, r) for each color class
+    ///       assert_eq!(&img_tga_data[18..27], &[
+    ///           52, 173, 38, 77, 222, 132, 40, 201, 4,
+    ///       ]);
+    ///
+    ///       // palette index of the first pixel
+    ///       assert_eq!(img_tga_data[27], 1);
+    ///
+    ///       // footer
+    ///       assert!(img_tga_data.ends_with(b"TRUEVISION-XFILE.\0"));
+    ///
+    ///       // too wide for the header
+    ///       assert_eq!(EthBlockies::tga_data(seed, (65536, 16)), None);
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.tga").unwrap()
+    ///       //     .write_all(&img_tga_data);
+    ///   }
+    ///   ```
+    fn tga_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Option<Vec<u8>> {
+        let tga_dim = indexed_tga::tga_dimension(output_dim)?;
+        let (palette, bitmap) = Self::indexed_data(seed);
+        Some(indexed_tga::indexed_data_to_tga(
+            palette,
+            bitmap.scale(output_dim),
+            tga_dim,
+        ))
     }
 
-    #[cfg(feature = "compressed_png")]
-    fn compressed_png_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Vec<u8> {
+    /// Generate an Ethereum-style blockies data in qoi format
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output qoi binary data.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of qoi binary data
+    ///
+    /// # Example
+    ///
+    /// * Get qoi data of RGB blockies
+    ///
+    ///   * General identicon
+    ///   ```
+    ///   use eth_blockies::{Blockies, BlockiesGenerator};
+    ///   type Identicon<T> = Blockies<14, T>; // user-defined blockies type
+    ///
+    ///   // args
+    ///   let seed = "general string seed";
+    ///   let output_dim = (64, 64); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let icon_qoi_data = Blockies::<14>::qoi_data(seed, output_dim);
+    ///   let icon_qoi_data_alias = Identicon::qoi_data(seed, output_dim);
+    ///
+    ///   // test
+    ///   {
+    ///       assert_eq!(icon_qoi_data, icon_qoi_data_alias);
+    ///   }
+    ///   ```
+    ///
+    ///   * Ethereum blockies
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (16, 16); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let img_qoi_data = EthBlockies::qoi_data(seed, output_dim);
+    ///
+    ///   // test
+    ///   {
+    ///       // header: magic, width, height, channels (rgb), colorspace
+    ///       assert_eq!(&img_qoi_data[..14],
+    ///           b"qoif\x00\x00\x00\x10\x00\x00\x00\x10\x03\x00");
+    ///
+    ///       // the first pixel: QOI_OP_RGB (r, g, b)
+    ///       assert_eq!(&img_qoi_data[14..18], b"\xfe\x84\xde\x4d");
+    ///
+    ///       // end marker
+    ///       assert!(img_qoi_data.ends_with(b"\x00\x00\x00\x00\x00\x00\x00\x01"));
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.qoi").unwrap()
+    ///       //     .write_all(&img_qoi_data);
+    ///   }
+    ///   ```
+    #[cfg(feature = "qoi")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "qoi")))]
+    fn qoi_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Vec<u8> {
+        let (palette, bitmap) = Self::indexed_data(seed);
+        rgb_qoi::indexed_data_to_qoi(palette, bitmap.scale(output_dim), output_dim)
+    }
+
+    /// Generate an Ethereum-style blockies data in lossless webp format
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output webp binary data.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    ///   Each of width and height should be in range of 1-16384.
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of webp binary data, or `None` if width or height is out of range of 1-16384
+    ///
+    /// # Example
+    ///
+    /// * Get lossless webp data of RGB blockies
+    ///
+    ///   * General identicon
+    ///   ```
+    ///   use eth_blockies::{Blockies, BlockiesGenerator};
+    ///   type Identicon<T> = Blockies<16, T>; // user-defined blockies type
+    ///
+    ///   // args
+    ///   let seed = "general string seed";
+    ///   let output_dim = (64, 64); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let icon_webp_data = Blockies::<16>::webp_data(seed, output_dim).unwrap();
+    ///   let icon_webp_data_alias = Identicon::webp_data(seed, output_dim).unwrap();
+    ///
+    ///   // test
+    ///   {
+    ///       assert_eq!(icon_webp_data, icon_webp_data_alias);
+    ///   }
+    ///   ```
+    ///
+    ///   * Ethereum blockies
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (16, 16); // multiples of size recommended
+    ///
+    ///   // generate blockies
+    ///   let img_webp_data = EthBlockies::webp_data(seed, output_dim).unwrap();
+    ///
+    ///   // test
+    ///   {
+    ///       assert_eq!(img_webp_data,
+    ///           b"\x52\x49\x46\x46\x52\x00\x00\x00\x57\x45\x42\x50\x56\
+    ///             \x50\x38\x4c\x46\x00\x00\x00\x2f\x0f\xc0\x03\x00\x17\
+    ///             \x20\x10\x48\x98\x06\x9f\x2c\x21\x81\x40\x62\x33\xd6\
+    ///             \x2c\xa1\x0b\x04\x12\x8e\x3d\xf6\x17\x49\x66\xfe\xe3\
+    ///             \xde\x01\x20\x89\xe0\x36\x25\x21\x55\x17\x32\x12\x75\
+    ///             \x25\x51\xfa\x37\x0f\x11\xfd\x9f\x80\xf7\xd5\xad\x11\
+    ///             \xb9\x98\xbd\x81\x1a\xa3\x4d\x70\xcd\x42\x96\x01");
+    ///
+    ///       // out of range of the header
+    ///       assert_eq!(EthBlockies::webp_data(seed, (0, 16)), None);
+    ///       assert_eq!(EthBlockies::webp_data(seed, (16385, 16)), None);
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.webp").unwrap()
+    ///       //     .write_all(&img_webp_data);
+    ///   }
+    ///   ```
+    #[cfg(feature = "webp_lossless")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "webp_lossless")))]
+    fn webp_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Option<Vec<u8>> {
+        let webp_dim = indexed_webp::webp_dimension(output_dim)?;
+        let (palette, bitmap) = Self::indexed_data(seed);
+        Some(indexed_webp::indexed_data_to_webp(
+            palette,
+            bitmap.scale(output_dim),
+            webp_dim,
+        ))
+    }
+
+    /// Generate an Ethereum-style blockies data in raw RGBA pixel buffer, with render options
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output image.
+    ///   Multiples of `const S` ([`SIZE`](BlockiesHelper::SIZE)) recommended for both width and height.
+    /// * `options` - Options for rendering image (See [`RenderOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of RGBA pixels (4 bytes per pixel), in row-major order
+    ///
+    /// # Example
+    ///
+    /// * Get circular RGBA buffer of blockies, with border
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (64, 64);
+    ///   let options = RenderOptions {
+    ///       border_width: 2,
+    ///       border_color: (255, 255, 255),
+    ///       mask: Mask::Circle,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate blockies
+    ///   let img_rgba_data = EthBlockies::rgba_data(seed, output_dim, options);
+    ///
+    ///   // test
+    ///   {
+    ///       let pixel_at = |(x, y): (usize, usize)| {
+    ///           &img_rgba_data[(y * output_dim.0 + x) * 4..][..4]
+    ///       };
+    ///
+    ///       assert_eq!(img_rgba_data.len(), 64 * 64 * 4);
+    ///       // corner: transparent
+    ///       assert_eq!(pixel_at((0, 0)), [0, 0, 0, 0]);
+    ///       // edge of circle: border
+    ///       assert_eq!(pixel_at((32, 0)), [255, 255, 255, 255]);
+    ///   }
+    ///   ```
+    ///
+    /// * Get RGBA buffer of blockies, with abbreviated address under it
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (64, 71); // blockies: 64x64, caption: 64x7
+    ///   let options = RenderOptions {
+    ///       caption: Some(Caption {
+    ///           text: CaptionText::EthAddress,
+    ///           ..Default::default()
+    ///       }),
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate blockies
+    ///   let img_rgba_data = EthBlockies::rgba_data(seed, output_dim, options);
+    ///
+    ///   // test
+    ///   {
+    ///       let pixel_at = |(x, y): (usize, usize)| {
+    ///           &img_rgba_data[(y * output_dim.0 + x) * 4..][..4]
+    ///       };
+    ///
+    ///       // blockies: same as the one without caption
+    ///       assert_eq!(
+    ///           img_rgba_data[..64 * 64 * 4],
+    ///           EthBlockies::rgba_data(seed, (64, 64), RenderOptions::default())
+    ///       );
+    ///       // caption: white background
+    ///       assert_eq!(pixel_at((0, 64)), [255, 255, 255, 255]);
+    ///   }
+    ///   ```
+    fn rgba_data<I: SeedInput>(
+        seed: I,
+        output_dim: (usize, usize),
+        options: RenderOptions,
+    ) -> Vec<u8> {
+        let seed = seed.to_seed_bytes();
+        let (palette, bitmap) = Self::indexed_data(&*seed);
+        blockies::render(palette, bitmap, output_dim, options, &seed)
+            .into_rgba()
+            .iter()
+            .flatten()
+            .flat_map(|(r, g, b, a)| [*r, *g, *b, *a])
+            .collect()
+    }
+
+    /// Generate an Ethereum-style blockies data in svg format
+    ///
+    /// Each row of cells with the same color is drawn as a single rectangle,
+    /// so the output is scaled without any loss of sharpness.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output svg image
+    ///
+    /// # Return
+    ///
+    /// * A string of svg document
+    ///
+    /// # Example
+    ///
+    /// * Get svg data of RGB blockies
+    ///
+    ///   ```
+    ///   use eth_blockies::{EthBlockies, SeedInput, BlockiesGenerator};
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (128, 128);
+    ///
+    ///   // generate blockies
+    ///   let img_svg_data = EthBlockies::svg_data(seed, output_dim);
+    ///
+    ///   // test
+    ///   {
+    ///       assert!(img_svg_data.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    ///       assert!(img_svg_data.contains("width=\"128\" height=\"128\""));
+    ///       // background color
+    ///       assert!(img_svg_data.contains("fill=\"#26ad34\""));
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.svg").unwrap()
+    ///       //     .write_all(img_svg_data.as_bytes());
+    ///   }
+    ///   ```
+    fn svg_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> String {
+        let (palette, bitmap) = Self::indexed_data(seed);
+        indexed_svg::indexed_data_to_svg(
+            palette,
+            bitmap.iter().map(|row| row.to_vec()).collect(),
+            output_dim,
+        )
+    }
+
+    /// Generate an Ethereum-style blockies data in svg format, with render options
+    ///
+    /// Padding, border and caption are drawn as svg shapes,
+    /// and the mask is applied with a `clipPath`.
+    /// Scaling is left to the svg renderer,
+    /// so [`ScaleMode::AreaAveraged`] is drawn the same as [`ScaleMode::Distributed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output svg image
+    /// * `options` - Options for rendering image (See [`RenderOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A string of svg document
+    ///
+    /// # Example
+    ///
+    /// * Get svg data of circular blockies, with padding and border
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (128, 128);
+    ///   let options = RenderOptions {
+    ///       padding: Padding::Cells(1),
+    ///       border_width: 4,
+    ///       border_color: (255, 255, 255),
+    ///       mask: Mask::Circle,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate blockies
+    ///   let img_svg_data = EthBlockies::svg_data_with_options(seed, output_dim, options);
+    ///
+    ///   // test
+    ///   {
+    ///       assert!(img_svg_data.contains("width=\"128\" height=\"128\""));
+    ///       // mask
+    ///       assert!(img_svg_data.contains("<clipPath"));
+    ///       assert!(img_svg_data.contains("clip-path=\"url(#"));
+    ///       // border
+    ///       assert!(img_svg_data.contains("stroke=\"#ffffff\""));
+    ///
+    ///       // default options: same as svg_data()
+    ///       assert_eq!(
+    ///           EthBlockies::svg_data_with_options(seed, output_dim, RenderOptions::default()),
+    ///           EthBlockies::svg_data(seed, output_dim)
+    ///       );
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.svg").unwrap()
+    ///       //     .write_all(img_svg_data.as_bytes());
+    ///   }
+    ///   ```
+    fn svg_data_with_options<I: SeedInput>(
+        seed: I,
+        output_dim: (usize, usize),
+        options: RenderOptions,
+    ) -> String {
+        let seed = seed.to_seed_bytes();
+        let (palette, bitmap) = Self::indexed_data(&*seed);
+        blockies::render_svg(palette, bitmap, output_dim, options, &seed)
+    }
+
+    /// Generate an Ethereum-style blockies data in svg format, with a caption under blockies
+    ///
+    /// The caption is drawn with the built-in bitmap font as svg shapes,
+    /// so that the output does not depend on any font.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output svg image, including the caption
+    /// * `caption` - Caption under blockies (See [`Caption`])
+    ///
+    /// # Return
+    ///
+    /// * A string of svg document
+    ///
+    /// # Example
+    ///
+    /// * Get svg data of RGB blockies, with abbreviated address under it
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC"
+    ///       .to_ethaddr_seed();
+    ///   let output_dim = (96, 110); // blockies: 96x96, caption: 96x14
+    ///   let caption = Caption {
+    ///       text: CaptionText::EthAddress,
+    ///       scale: 2,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate blockies
+    ///   let img_svg_data = EthBlockies::svg_data_with_caption(seed, output_dim, caption);
+    ///
+    ///   // test
+    ///   {
+    ///       assert!(img_svg_data.contains("width=\"96\" height=\"110\""));
+    ///       // blockies above the caption
+    ///       assert!(img_svg_data.contains("width=\"96\" height=\"96\""));
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("test.svg").unwrap()
+    ///       //     .write_all(img_svg_data.as_bytes());
+    ///   }
+    ///   ```
+    fn svg_data_with_caption<I: SeedInput>(
+        seed: I,
+        output_dim: (usize, usize),
+        caption: Caption,
+    ) -> String {
+        Self::svg_data_with_options(
+            seed,
+            output_dim,
+            RenderOptions {
+                caption: Some(caption),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Generate a sheet of blockies for multiple seeds, in uncompressed png format
+    ///
+    /// Blockies of all seeds are composed on a single canvas, then encoded once.
+    /// The png is indexed if 256 or less colors are used, or truecolor otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - Input seeds, laid out from left to right and top to bottom
+    /// * `options` - Options for the sheet layout (See [`SheetOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of png binary data
+    ///
+    /// # Example
+    ///
+    /// * Get png data of a sheet with labels
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seeds = ["seed-1", "seed-2", "seed-3"];
+    ///   let options = SheetOptions {
+    ///       columns: 2,
+    ///       icon_dimension: (32, 32),
+    ///       labels: true,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate sheet
+    ///   let sheet_png_data = EthBlockies::sheet_png_data(&seeds, options);
+    ///
+    ///   // test
+    ///   {
+    ///       // width:  8 + 2 * (32 + 8) = 88
+    ///       // height: 8 + 2 * (32 + 6 (label) + 8) = 100
+    ///       assert_eq!(&sheet_png_data[16..24], [0, 0, 0, 88, 0, 0, 0, 100]);
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("sheet.png").unwrap()
+    ///       //     .write_all(&sheet_png_data);
+    ///   }
+    ///   ```
+    fn sheet_png_data<I: SeedInput>(seeds: &[I], options: SheetOptions) -> Vec<u8> {
+        let seeds: Vec<Cow<[u8]>> = seeds.iter().map(SeedInput::to_seed_bytes).collect();
+        let seeds: Vec<&[u8]> = seeds.iter().map(|seed| &**seed).collect();
+        let (sheet_image, sheet_dim) = blockies::render_sheet::<S>(&seeds, options);
+        indexed_png::rendered_data_to_png(
+            sheet_image,
+            sheet_dim,
+            false,
+            PngOptions::default(),
+            (&[], S),
+        )
+    }
+
+    /// Generate a sheet of blockies for multiple seeds, in compressed png format
+    ///
+    /// Blockies of all seeds are composed on a single canvas, then encoded once.
+    /// The png is indexed if 256 or less colors are used, or truecolor otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - Input seeds, laid out from left to right and top to bottom
+    /// * `options` - Options for the sheet layout (See [`SheetOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of png binary data
+    ///
+    /// # Example
+    ///
+    /// * Get compressed png data of a sheet, for Ethereum addresses
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seeds = [
+    ///       "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC".to_ethaddr_seed(),
+    ///       "0x0000000000000000000000000000000000000000".to_ethaddr_seed(),
+    ///   ];
+    ///
+    ///   // generate sheet
+    ///   let sheet_png_data =
+    ///       EthBlockies::compressed_sheet_png_data(&seeds, SheetOptions::default());
+    ///
+    ///   // test
+    ///   {
+    ///       assert!(sheet_png_data.len()
+    ///           < EthBlockies::sheet_png_data(&seeds, SheetOptions::default()).len());
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("sheet.png").unwrap()
+    ///       //     .write_all(&sheet_png_data);
+    ///   }
+    ///   ```
+    #[cfg(feature = "compressed_png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compressed_png")))]
+    fn compressed_sheet_png_data<I: SeedInput>(seeds: &[I], options: SheetOptions) -> Vec<u8> {
+        let seeds: Vec<Cow<[u8]>> = seeds.iter().map(SeedInput::to_seed_bytes).collect();
+        let seeds: Vec<&[u8]> = seeds.iter().map(|seed| &**seed).collect();
+        let (sheet_image, sheet_dim) = blockies::render_sheet::<S>(&seeds, options);
+        indexed_png::rendered_data_to_png(
+            sheet_image,
+            sheet_dim,
+            true,
+            PngOptions::default(),
+            (&[], S),
+        )
+    }
+
+    /// Generate a sheet of blockies for multiple seeds, in svg format
+    ///
+    /// # Arguments
+    ///
+    /// * `seeds` - Input seeds, laid out from left to right and top to bottom
+    /// * `options` - Options for the sheet layout (See [`SheetOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A string of svg document
+    ///
+    /// # Example
+    ///
+    /// * Get svg data of a sheet with labels, on transparent background
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seeds = ["seed-1", "seed-2", "seed-3"];
+    ///   let options = SheetOptions {
+    ///       background: None,
+    ///       labels: true,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate sheet
+    ///   let sheet_svg_data = EthBlockies::sheet_svg_data(&seeds, options);
+    ///
+    ///   // test
+    ///   {
+    ///       // width:  8 + 3 * (64 + 8) = 224
+    ///       // height: 8 + (64 + 6 (label) + 8) = 86
+    ///       assert!(sheet_svg_data.contains("width=\"224\" height=\"86\""));
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("sheet.svg").unwrap()
+    ///       //     .write_all(sheet_svg_data.as_bytes());
+    ///   }
+    ///   ```
+    fn sheet_svg_data<I: SeedInput>(seeds: &[I], options: SheetOptions) -> String {
+        let seeds: Vec<Cow<[u8]>> = seeds.iter().map(SeedInput::to_seed_bytes).collect();
+        let seeds: Vec<&[u8]> = seeds.iter().map(|seed| &**seed).collect();
+        blockies::sheet_to_svg::<S>(&seeds, options)
+    }
+
+    /// Generate QR code of Ethereum address with blockies in the centre, in uncompressed png format
+    ///
+    /// The address is encoded on error correction level H, so that the QR code still scans
+    /// with blockies covering the centre of it.
+    /// The dimension of the image is determined by the QR code version and the options.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Ethereum address, in any form valid for [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed)
+    /// * `options` - Options for the QR code (See [`QrOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of png binary data, or `None` if `seed` is not an Ethereum address
+    ///
+    /// # Example
+    ///
+    /// * Get png data of QR code, for an Ethereum address
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+    ///
+    ///   // generate QR code
+    ///   let qr_png_data = EthBlockies::qr_png_data(seed, QrOptions::default()).unwrap();
+    ///
+    ///   // test
+    ///   {
+    ///       // QR code of 37x37 modules, with 4 modules of quiet zone on each side:
+    ///       //   (37 + 4 * 2) * 4 = 180
+    ///       assert_eq!(&qr_png_data[16..24], [0, 0, 0, 180, 0, 0, 0, 180]);
+    ///
+    ///       // not an Ethereum address
+    ///       assert_eq!(EthBlockies::qr_png_data("generic_seed", QrOptions::default()), None);
+    ///       assert_eq!(
+    ///           EthBlockies::qr_png_data("0xe686c14FF9C11038F2B1c9aD617F2346CFB817dZ", QrOptions::default()),
+    ///           None
+    ///       );
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("qr.png").unwrap()
+    ///       //     .write_all(&qr_png_data);
+    ///   }
+    ///   ```
+    #[cfg(feature = "qr")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "qr")))]
+    fn qr_png_data<I: SeedInput>(seed: I, options: QrOptions) -> Option<Vec<u8>> {
+        let seed = seed.to_seed_bytes();
+        let (qr_image, qr_dim) = blockies::render_qr_badge::<S>(&seed, options)?;
+        Some(indexed_png::rendered_data_to_png(
+            qr_image,
+            qr_dim,
+            false,
+            PngOptions::default(),
+            (&[], S),
+        ))
+    }
+
+    /// Generate QR code of Ethereum address with blockies in the centre, in compressed png format
+    ///
+    /// The address is encoded on error correction level H, so that the QR code still scans
+    /// with blockies covering the centre of it.
+    /// The dimension of the image is determined by the QR code version and the options.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Ethereum address, in any form valid for [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed)
+    /// * `options` - Options for the QR code (See [`QrOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A byte vector of png binary data, or `None` if `seed` is not an Ethereum address
+    ///
+    /// # Example
+    ///
+    /// * Get compressed png data of QR code, for EIP-681 uri of an Ethereum address
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+    ///   let options = QrOptions {
+    ///       eip681_uri: true, // "ethereum:0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc"
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate QR code
+    ///   let qr_png_data = EthBlockies::compressed_qr_png_data(seed, options).unwrap();
+    ///
+    ///   // test
+    ///   {
+    ///       // QR code of 41x41 modules, with 4 modules of quiet zone on each side:
+    ///       //   (41 + 4 * 2) * 4 = 196
+    ///       assert_eq!(&qr_png_data[16..24], [0, 0, 0, 196, 0, 0, 0, 196]);
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("qr.png").unwrap()
+    ///       //     .write_all(&qr_png_data);
+    ///   }
+    ///   ```
+    #[cfg(all(feature = "qr", feature = "compressed_png"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "qr", feature = "compressed_png"))))]
+    fn compressed_qr_png_data<I: SeedInput>(seed: I, options: QrOptions) -> Option<Vec<u8>> {
+        let seed = seed.to_seed_bytes();
+        let (qr_image, qr_dim) = blockies::render_qr_badge::<S>(&seed, options)?;
+        Some(indexed_png::rendered_data_to_png(
+            qr_image,
+            qr_dim,
+            true,
+            PngOptions::default(),
+            (&[], S),
+        ))
+    }
+
+    /// Generate QR code of Ethereum address with blockies in the centre, in svg format
+    ///
+    /// The address is encoded on error correction level H, so that the QR code still scans
+    /// with blockies covering the centre of it.
+    /// The dimension of the image is determined by the QR code version and the options.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Ethereum address, in any form valid for [`to_ethaddr_seed()`](SeedInput::to_ethaddr_seed)
+    /// * `options` - Options for the QR code (See [`QrOptions`])
+    ///
+    /// # Return
+    ///
+    /// * A string of svg document, or `None` if `seed` is not an Ethereum address
+    ///
+    /// # Example
+    ///
+    /// * Get svg data of QR code, without quiet zone
+    ///
+    ///   ```
+    ///   use eth_blockies::*;
+    ///
+    ///   // args
+    ///   let seed = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+    ///   let options = QrOptions {
+    ///       module_size: 1,
+    ///       quiet_zone: 0,
+    ///       ..Default::default()
+    ///   };
+    ///
+    ///   // generate QR code
+    ///   let qr_svg_data = EthBlockies::qr_svg_data(seed, options).unwrap();
+    ///
+    ///   // test
+    ///   {
+    ///       assert!(qr_svg_data.contains("width=\"37\" height=\"37\""));
+    ///       // blockies of 7x7 modules in the centre, with 1 module margin
+    ///       assert!(qr_svg_data.contains("x=\"15\" y=\"15\" width=\"7\" height=\"7\""));
+    ///
+    ///       // uncomment below to write to file
+    ///       // use std::io::Write;
+    ///       // std::fs::File::create("qr.svg").unwrap()
+    ///       //     .write_all(qr_svg_data.as_bytes());
+    ///   }
+    ///   ```
+    #[cfg(feature = "qr")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "qr")))]
+    fn qr_svg_data<I: SeedInput>(seed: I, options: QrOptions) -> Option<String> {
+        let seed = seed.to_seed_bytes();
+        blockies::qr_badge_svg::<S>(&seed, options)
+    }
+}
+
+impl<const S: usize> BlockiesGenerator<S> for Blockies<S> {
+    fn data<I: SeedInput>(seed: I) -> Blockies<S, RgbPixel> {
+        Blockies::data_mapped(seed, |rgb_pixel| rgb_pixel)
+    }
+
+    fn data_mapped<I: SeedInput, T: Clone, F: Fn(RgbPixel) -> T>(
+        seed: I,
+        map_fn: F,
+    ) -> Blockies<S, T> {
+        let (palette, class_bitmap) = Blockies::indexed_data_mapped(seed, map_fn);
+
+        class_bitmap.map_2d(|class, _| palette[class].clone())
+    }
+
+    fn indexed_data<I: SeedInput>(seed: I) -> (RgbPalette, Blockies<S, ColorClass>) {
+        let seed = seed.to_seed_bytes();
+        blockies::new_blockies(&seed)
+    }
+
+    fn indexed_data_mapped<I: SeedInput, T: Clone, F: Fn(RgbPixel) -> T>(
+        seed: I,
+        map_fn: F,
+    ) -> (Palette<T>, Blockies<S, ColorClass>) {
+        let (rgb_palette, class_bitmap) = Blockies::indexed_data(seed);
+
+        (rgb_palette.map(map_fn), class_bitmap)
+    }
+
+    fn ansiseq_data<I: SeedInput>(
+        seed: I,
+        output_dim: (usize, usize),
+        is_utf8: bool,
+    ) -> Vec<String> {
         let (palette, bitmap) = Blockies::<S>::indexed_data(seed);
-        indexed_png::indexed_data_to_png(palette, bitmap.scale(output_dim), output_dim, true)
+        match is_utf8 {
+            true => ansi_seq::indexed_data_to_ansiseq_utf8(
+                palette,
+                bitmap.scale(output_dim),
+                output_dim,
+            ),
+            false => ansi_seq::indexed_data_to_ansiseq_ascii(
+                palette,
+                bitmap.scale(output_dim),
+                output_dim,
+            ),
+        }
+    }
+
+    fn png_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Vec<u8> {
+        Blockies::<S>::png_data_with_options(seed, output_dim, PngOptions::default())
+    }
+
+    #[cfg(feature = "compressed_png")]
+    fn compressed_png_data<I: SeedInput>(seed: I, output_dim: (usize, usize)) -> Vec<u8> {
+        Blockies::<S>::compressed_png_data_with_options(seed, output_dim, PngOptions::default())
     }
 
     fn png_data_base64<I: SeedInput>(
@@ -0,0 +1,313 @@
+use crate::global_type_helper::{PngOptions, RenderOptions, SeedInput};
+use crate::{Blockies, BlockiesGenerator};
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Format of encoded data cached in [`RenderCache`]
+///
+/// Each format generates the same data as the corresponding function of [`BlockiesGenerator`].
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum RenderFormat {
+    /// Uncompressed png, of [`png_data_with_options()`](BlockiesGenerator::png_data_with_options)
+    Png(PngOptions),
+    /// Compressed png, of [`compressed_png_data_with_options()`](BlockiesGenerator::compressed_png_data_with_options)
+    #[cfg(feature = "compressed_png")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "compressed_png")))]
+    CompressedPng(PngOptions),
+    /// Raw rgba data, of [`rgba_data()`](BlockiesGenerator::rgba_data)
+    Rgba(RenderOptions),
+    /// Bmp, of [`bmp_data()`](BlockiesGenerator::bmp_data)
+    Bmp,
+    /// Ppm, of [`ppm_data()`](BlockiesGenerator::ppm_data)
+    Ppm,
+    /// Tga, of [`tga_data()`](BlockiesGenerator::tga_data)
+    Tga,
+    /// Svg (in utf-8 bytes), of [`svg_data_with_options()`](BlockiesGenerator::svg_data_with_options)
+    Svg(RenderOptions),
+    /// Qoi, of [`qoi_data()`](BlockiesGenerator::qoi_data)
+    #[cfg(feature = "qoi")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "qoi")))]
+    Qoi,
+    /// Lossless webp, of [`webp_data()`](BlockiesGenerator::webp_data)
+    #[cfg(feature = "webp_lossless")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "webp_lossless")))]
+    Webp,
+}
+
+/// Thread-safe LRU cache of encoded blockies data, bounded by the total bytes of cached data
+///
+/// Entries are keyed by the seed (canonicalized as in generating blockies,
+/// e.g. address types of other crates to a well-formed Ethereum address seed),
+/// the blockies size, the output dimension, and the [`RenderFormat`] including its options.
+/// When the total bytes of cached data exceed the bound,
+/// least recently used entries are evicted first.
+///
+/// Data is generated without holding the lock,
+/// so threads generating data of different keys do not block each other.
+/// (Threads missing the same key at the same time may generate the data more than once.)
+///
+/// # Example
+///
+/// * Cache shared between threads
+///   ```
+///   use eth_blockies::*;
+///   use std::sync::Arc;
+///
+///   // cache up to 16 MiB of encoded data, shared between threads
+///   let cache = Arc::new(RenderCache::new(16 * 1024 * 1024));
+///
+///   let addr = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC".to_ethaddr_seed();
+///   let format = RenderFormat::Png(PngOptions::default());
+///
+///   let png = std::thread::spawn({
+///       let cache = cache.clone();
+///       move || cache.get::<8, _>(addr, (128, 128), format)
+///   })
+///   .join()
+///   .unwrap()
+///   .unwrap();
+///
+///   assert_eq!(*png, EthBlockies::png_data(addr, (128, 128)));
+///   assert_eq!(cache.len(), 1);
+///   assert_eq!(cache.bytes(), png.len());
+///
+///   // cached data is returned without generating again
+///   assert!(Arc::ptr_eq(&cache.get::<8, _>(addr, (128, 128), format).unwrap(), &png));
+///   ```
+///
+/// * Eviction of least recently used entries
+///   ```
+///   use eth_blockies::*;
+///
+///   // every bmp of the same dimension has the same length
+///   let bmp_len = EthBlockies::bmp_data("a", (64, 64)).len();
+///
+///   // room for 2 entries
+///   let cache = RenderCache::new(bmp_len * 2);
+///   cache.get::<8, _>("a", (64, 64), RenderFormat::Bmp);
+///   cache.get::<8, _>("b", (64, 64), RenderFormat::Bmp);
+///   cache.get::<8, _>("a", (64, 64), RenderFormat::Bmp); // "a" is used recently
+///   cache.get::<8, _>("c", (64, 64), RenderFormat::Bmp); // "b" is evicted
+///
+///   assert_eq!(cache.len(), 2);
+///   assert_eq!(cache.bytes(), bmp_len * 2);
+///   let a = cache.get::<8, _>("a", (64, 64), RenderFormat::Bmp).unwrap(); // "c" is used least recently
+///
+///   // shrinking the bound evicts "c"
+///   cache.set_max_bytes(bmp_len);
+///   assert_eq!(cache.len(), 1);
+///   assert!(std::sync::Arc::ptr_eq(&cache.get::<8, _>("a", (64, 64), RenderFormat::Bmp).unwrap(), &a));
+///   ```
+pub struct RenderCache {
+    state: Mutex<CacheState>,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+struct CacheKey {
+    seed: Vec<u8>,
+    size: usize,
+    output_dim: (usize, usize),
+    format: RenderFormat,
+}
+
+struct CacheEntry {
+    data: Arc<[u8]>,
+    last_used: u64,
+}
+
+struct CacheState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    // last used time -> key, in the order of use
+    lru: BTreeMap<u64, CacheKey>,
+    clock: u64,
+    bytes: usize,
+    max_bytes: usize,
+}
+
+impl RenderCache {
+    /// Create an empty cache, bounded by `max_bytes` of cached data in total
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                lru: BTreeMap::new(),
+                clock: 0,
+                bytes: 0,
+                max_bytes,
+            }),
+        }
+    }
+
+    /// Get encoded data of blockies, generating and caching it if not cached
+    ///
+    /// Data larger than the bound is returned without being cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `S` - Blockies size (e.g. `8` for [`EthBlockies`](crate::EthBlockies))
+    /// * `seed` - Input seed
+    /// * `output_dim` - (width, height) of output data
+    /// * `format` - Format of output data, with its options
+    ///
+    /// # Return
+    ///
+    /// * Encoded data, shared with the cache,
+    ///   or `None` if the format cannot encode data of `output_dim` (e.g. [`Tga`](RenderFormat::Tga) wider than [`u16::MAX`])
+    pub fn get<const S: usize, I: SeedInput>(
+        &self,
+        seed: I,
+        output_dim: (usize, usize),
+        format: RenderFormat,
+    ) -> Option<Arc<[u8]>> {
+        let key = CacheKey {
+            seed: seed.to_seed_bytes().into_owned(),
+            size: S,
+            output_dim,
+            format,
+        };
+
+        if let Some(data) = self.lock().touch(&key) {
+            return Some(data);
+        }
+
+        let data: Arc<[u8]> = render::<S>(&key.seed, output_dim, format)?.into();
+        Some(self.lock().insert(key, data))
+    }
+
+    /// Get the number of cached entries
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Check if no entry is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the total bytes of cached data
+    pub fn bytes(&self) -> usize {
+        self.lock().bytes
+    }
+
+    /// Get the bound of the total bytes of cached data
+    pub fn max_bytes(&self) -> usize {
+        self.lock().max_bytes
+    }
+
+    /// Change the bound of the total bytes of cached data,
+    /// evicting least recently used entries if exceeded
+    pub fn set_max_bytes(&self, max_bytes: usize) {
+        let mut state = self.lock();
+        state.max_bytes = max_bytes;
+        state.evict();
+    }
+
+    /// Remove all cached entries
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.lru.clear();
+        state.bytes = 0;
+    }
+
+    // state is consistent even if poisoned, as no user code runs while locked
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl core::fmt::Debug for RenderCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("RenderCache")
+            .field("len", &state.entries.len())
+            .field("bytes", &state.bytes)
+            .field("max_bytes", &state.max_bytes)
+            .finish()
+    }
+}
+
+impl CacheState {
+    // get data of the key, marking it as most recently used
+    fn touch(&mut self, key: &CacheKey) -> Option<Arc<[u8]>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+
+        let key = self.lru.remove(&entry.last_used)?;
+        entry.last_used = self.clock;
+        self.lru.insert(self.clock, key);
+
+        Some(entry.data.clone())
+    }
+
+    // insert data of the key, or get the data already inserted by another thread
+    fn insert(&mut self, key: CacheKey, data: Arc<[u8]>) -> Arc<[u8]> {
+        if let Some(data) = self.touch(&key) {
+            return data;
+        }
+        if data.len() > self.max_bytes {
+            return data;
+        }
+
+        self.bytes += data.len();
+        self.lru.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            CacheEntry {
+                data: data.clone(),
+                last_used: self.clock,
+            },
+        );
+        self.evict();
+
+        data
+    }
+
+    // evict least recently used entries, until bytes are within the bound
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes {
+            let oldest = match self.lru.keys().next() {
+                Some(last_used) => *last_used,
+                None => break,
+            };
+            if let Some(entry) = self
+                .lru
+                .remove(&oldest)
+                .and_then(|key| self.entries.remove(&key))
+            {
+                self.bytes -= entry.data.len();
+            }
+        }
+    }
+}
+
+fn render<const S: usize>(
+    seed: &[u8],
+    output_dim: (usize, usize),
+    format: RenderFormat,
+) -> Option<Vec<u8>> {
+    Some(match format {
+        RenderFormat::Png(options) => {
+            Blockies::<S>::png_data_with_options(seed, output_dim, options)
+        }
+        #[cfg(feature = "compressed_png")]
+        RenderFormat::CompressedPng(options) => {
+            Blockies::<S>::compressed_png_data_with_options(seed, output_dim, options)
+        }
+        RenderFormat::Rgba(options) => Blockies::<S>::rgba_data(seed, output_dim, options),
+        RenderFormat::Bmp => Blockies::<S>::bmp_data(seed, output_dim),
+        RenderFormat::Ppm => Blockies::<S>::ppm_data(seed, output_dim),
+        RenderFormat::Tga => Blockies::<S>::tga_data(seed, output_dim)?,
+        RenderFormat::Svg(options) => {
+            Blockies::<S>::svg_data_with_options(seed, output_dim, options).into_bytes()
+        }
+        #[cfg(feature = "qoi")]
+        RenderFormat::Qoi => Blockies::<S>::qoi_data(seed, output_dim),
+        #[cfg(feature = "webp_lossless")]
+        RenderFormat::Webp => Blockies::<S>::webp_data(seed, output_dim)?,
+    })
+}
@@ -0,0 +1,151 @@
+#!/usr/bin/env node
+
+// gen-golden-corpus.js
+//
+// generate the golden testcases (in 'golden' directory) deterministically,
+// with an independent port of 'ethereum-blockies' (seedrand, rand, createColor, createImageData)
+// and 'hsl2rgb' of 'ethereum-blockies-base64' for converting colors
+//
+// - general seeds: 64 alphanumeric seeds (length 10-73) for each resolution 1-32 ('R<size>.txt')
+// - ethereum seeds: 1024 lowercase addresses for resolution 8 ('R8_eth.txt')
+// - compatibility profiles: 256 checksummed addresses for each library entry point below ('compat.txt')
+//   - 'ethereum-blockies': blockies.create({ seed: address }) (seed as-is, scale 4)
+//   - 'ethereum-blockies-base64': makeBlockie(address) (lowercase seed, scale 16)
+//
+// usage:
+//   gen-golden-corpus.js [output-dir]
+//      - [output-dir]: output directory to write testcase files [default: 'golden' next to this script]
+//
+
+const fs = require("fs");
+const path = require("path");
+
+const OUTPUT_DIR = process.argv[2] || path.join(__dirname, "golden");
+const SEEDS_PER_SIZE = 64;
+const ETH_SEEDS = 1024;
+const COMPAT_SEEDS = 256;
+
+// blockies generator, ported from 'ethereum-blockies'
+const randseed = new Array(4);
+
+function seedrand(seed) {
+  randseed.fill(0);
+  for (let i = 0; i < seed.length; i++) {
+    randseed[i % 4] = (randseed[i % 4] << 5) - randseed[i % 4] + seed.charCodeAt(i);
+  }
+}
+
+function rand() {
+  const t = randseed[0] ^ (randseed[0] << 11);
+  randseed[0] = randseed[1];
+  randseed[1] = randseed[2];
+  randseed[2] = randseed[3];
+  randseed[3] = randseed[3] ^ (randseed[3] >> 19) ^ t ^ (t >> 8);
+  return (randseed[3] >>> 0) / ((1 << 31) >>> 0);
+}
+
+function createColor() {
+  const h = Math.floor(rand() * 360);
+  const s = rand() * 60 + 40;
+  const l = (rand() + rand() + rand() + rand()) * 25;
+  return hsl2rgb(h / 360, s / 100, l / 100);
+}
+
+function hsl2rgb(h, s, l) {
+  let r, g, b;
+  if (s == 0) {
+    r = g = b = l;
+  } else {
+    const hue2rgb = (p, q, t) => {
+      if (t < 0) t += 1;
+      if (t > 1) t -= 1;
+      if (t < 1 / 6) return p + (q - p) * 6 * t;
+      if (t < 1 / 2) return q;
+      if (t < 2 / 3) return p + (q - p) * (2 / 3 - t) * 6;
+      return p;
+    };
+    const q = l < 0.5 ? l * (1 + s) : l + s - l * s;
+    const p = 2 * l - q;
+    r = hue2rgb(p, q, h + 1 / 3);
+    g = hue2rgb(p, q, h);
+    b = hue2rgb(p, q, h - 1 / 3);
+  }
+  return [r, g, b].map((c) => Math.round(c * 255));
+}
+
+// image dimension and seed of each library entry point, for blockies of 8x8 (default size)
+const COMPAT_PROFILES = {
+  "ethereum-blockies": (address) => ({ seed: address, scale: 4 }),
+  "ethereum-blockies-base64": (address) => ({ seed: address.toLowerCase(), scale: 16 }),
+};
+
+// testcase string: '<color0>,<color1>,<color2>=<indices-left-half>'
+function testcase(seed, size) {
+  seedrand(seed);
+  const color = createColor();
+  const bgcolor = createColor();
+  const spotcolor = createColor();
+
+  let indices = "";
+  for (let y = 0; y < size; y++) {
+    for (let x = 0; x < Math.ceil(size / 2); x++) {
+      indices += Math.floor(rand() * 2.3);
+    }
+  }
+
+  const hex = (rgb) => rgb.map((c) => c.toString(16).padStart(2, "0")).join("");
+  return `${hex(bgcolor)},${hex(color)},${hex(spotcolor)}=${indices}`;
+}
+
+// deterministic seed generator (xorshift32), independent of blockies
+let state = 0x2545f491;
+function next() {
+  state ^= state << 13;
+  state ^= state >>> 17;
+  state ^= state << 5;
+  state >>>= 0;
+  return state;
+}
+function randomString(charset, len) {
+  return Array.from({ length: len }, () => charset[next() % charset.length]).join("");
+}
+
+function writeTestcases(fname, lines, format = "[input] [output (<color0>,<color1>,<color2>=<indices-left-half>)]") {
+  const header = `#\n# Auto-generated testcase file: '${fname}'\n#\n# format:\n#    ${format}\n#\n`;
+  fs.writeFileSync(path.join(OUTPUT_DIR, fname), header + lines.map((l) => l + "\n").join(""));
+}
+
+fs.mkdirSync(OUTPUT_DIR, { recursive: true });
+
+const ALNUM = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+for (let size = 1; size <= 32; size++) {
+  const lines = Array.from({ length: SEEDS_PER_SIZE }, () => {
+    const seed = randomString(ALNUM, (next() & 0x3f) + 10);
+    return `${seed} ${testcase(seed, size)}`;
+  });
+  writeTestcases(`R${size}.txt`, lines);
+}
+
+const lines = Array.from({ length: ETH_SEEDS }, () => {
+  const seed = "0x" + randomString("0123456789abcdef", 40);
+  return `${seed} ${testcase(seed, 8)}`;
+});
+writeTestcases("R8_eth.txt", lines);
+
+// checksum-like mixed case, to tell the seed preprocessing of each library apart
+const compatLines = Object.entries(COMPAT_PROFILES).flatMap(([profile, entryPoint]) =>
+  Array.from({ length: COMPAT_SEEDS }, () => {
+    const address =
+      "0x" +
+      Array.from(randomString("0123456789abcdef", 40), (c) =>
+        next() & 1 ? c.toUpperCase() : c
+      ).join("");
+    const { seed, scale } = entryPoint(address);
+    return `${profile} ${address} ${8 * scale}x${8 * scale} ${testcase(seed, 8)}`;
+  })
+);
+writeTestcases(
+  "compat.txt",
+  compatLines,
+  "[profile] [input] [output dimension (<width>x<height>)] [output (<color0>,<color1>,<color2>=<indices-left-half>)]"
+);
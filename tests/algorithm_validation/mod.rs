@@ -1,6 +1,8 @@
-// These tests are disabled (ignored) by default.
+// Tests on testcases inside subdir 'testcase' are disabled (ignored) by default.
 // To test this, first place testcases inside subdir 'testcase',
 // and run testmod 'algorithm_validation' with --ignored flag
+//
+// Tests on the golden testcases inside subdir 'golden' run by default.
 
 #[cfg(test)]
 mod algorithm_validation {
@@ -72,6 +74,59 @@ mod algorithm_validation {
         test::seed_io_test::<16, false>()
     }
 
+    // golden testcases, generated by 'gen-golden-corpus.js'
+    macro_rules! golden_test {
+        ($($name:ident: $size:literal, $is_eth:literal;)*) => {
+            $(
+                #[test]
+                fn $name() -> io::Result<()> {
+                    test::seed_io_test_in::<$size, $is_eth>(test::GOLDEN_DIR)
+                }
+            )*
+        };
+    }
+    golden_test! {
+        golden_eth: 8, true;
+        golden_r01: 1, false;
+        golden_r02: 2, false;
+        golden_r03: 3, false;
+        golden_r04: 4, false;
+        golden_r05: 5, false;
+        golden_r06: 6, false;
+        golden_r07: 7, false;
+        golden_r08: 8, false;
+        golden_r09: 9, false;
+        golden_r10: 10, false;
+        golden_r11: 11, false;
+        golden_r12: 12, false;
+        golden_r13: 13, false;
+        golden_r14: 14, false;
+        golden_r15: 15, false;
+        golden_r16: 16, false;
+        golden_r17: 17, false;
+        golden_r18: 18, false;
+        golden_r19: 19, false;
+        golden_r20: 20, false;
+        golden_r21: 21, false;
+        golden_r22: 22, false;
+        golden_r23: 23, false;
+        golden_r24: 24, false;
+        golden_r25: 25, false;
+        golden_r26: 26, false;
+        golden_r27: 27, false;
+        golden_r28: 28, false;
+        golden_r29: 29, false;
+        golden_r30: 30, false;
+        golden_r31: 31, false;
+        golden_r32: 32, false;
+    }
+
+    // golden testcases of compatibility profiles, generated by 'gen-golden-corpus.js'
+    #[test]
+    fn golden_compat() -> io::Result<()> {
+        test::compat_io_test_in(test::GOLDEN_DIR)
+    }
+
     mod test {
         use eth_blockies::*;
         use std::{fs, io};
@@ -81,10 +136,19 @@ mod algorithm_validation {
             "/tests/algorithm_validation/testcase"
         );
 
+        pub const GOLDEN_DIR: &str = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/algorithm_validation/golden"
+        );
+
         pub fn seed_io_test<const R: usize, const E: bool>() -> io::Result<()> {
+            seed_io_test_in::<R, E>(RES_DIR)
+        }
+
+        pub fn seed_io_test_in<const R: usize, const E: bool>(dir: &str) -> io::Result<()> {
             let fname = format!(
                 "{}/R{}{}.txt",
-                RES_DIR,
+                dir,
                 R,
                 match E {
                     true => "_eth",
@@ -103,6 +167,55 @@ mod algorithm_validation {
             Ok(())
         }
 
+        pub fn compat_io_test_in(dir: &str) -> io::Result<()> {
+            let fname = format!("{}/compat.txt", dir);
+            eprintln!("compat_io_test(): fname: {}", fname);
+            fs::read_to_string(fname)?
+                .lines()
+                .map(parse_compat_line)
+                .collect::<io::Result<()>>()
+        }
+
+        // try to parse test io line of compatibility profiles
+        fn parse_compat_line(line: &str) -> io::Result<()> {
+            if line.is_empty() || line.starts_with('#') {
+                return Ok(());
+            }
+
+            let invalid_data = |msg: &str| {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} (line: [{}])", msg, line),
+                ))
+            };
+            let mut line_iter = line.split_ascii_whitespace();
+            match (
+                line_iter.next().and_then(|name| match name {
+                    "ethereum-blockies" => Some(CompatProfile::EthereumBlockies),
+                    "ethereum-blockies-base64" => Some(CompatProfile::EthereumBlockiesBase64),
+                    _ => None,
+                }),
+                line_iter.next(),
+                line_iter.next().and_then(|dim| {
+                    let (width, height) = dim.split_once('x')?;
+                    Some((width.parse::<usize>().ok()?, height.parse::<usize>().ok()?))
+                }),
+                line_iter.next().and_then(parse_expected_output::<8>),
+            ) {
+                (Some(profile), Some(input), Some(dim), Some(parsed_expected_output)) => {
+                    println!("Validating line... [{}]", line);
+
+                    match profile.output_dim(8) == dim
+                        && EthBlockies::data(profile.seed(input)) == parsed_expected_output
+                    {
+                        true => Ok(()),
+                        false => invalid_data("Expected output & generated output not match!"),
+                    }
+                }
+                _ => invalid_data("Failed to parse line!"),
+            }
+        }
+
         // try to parse test io line
         fn parse_line<const R: usize>(line: &str) -> io::Result<()> {
             match line.is_empty() || line.starts_with("#") {
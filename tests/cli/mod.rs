@@ -0,0 +1,48 @@
+// Tests on arguments of the binary, comparing outputs with the library.
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod cli {
+    use eth_blockies::*;
+    use std::process::{Command, Stdio};
+
+    // run the binary with args, and get stdout (asserting success)
+    fn run(args: &[&str]) -> Vec<u8> {
+        let output = Command::new(env!("CARGO_BIN_EXE_eth-blockies"))
+            .args(args)
+            .stdin(Stdio::null())
+            .output()
+            .expect("failed to run binary");
+        assert!(output.status.success(), "{:?}", args);
+        output.stdout
+    }
+
+    fn ansi_output<const S: usize>(seed: &str) -> Vec<u8> {
+        format!(
+            "{}\n",
+            Blockies::<S>::ansiseq_data(seed, (S, S), true).join("\n")
+        )
+        .into_bytes()
+    }
+
+    // command names after '--' are seeds
+    #[test]
+    fn seed_of_command_name() {
+        assert_eq!(run(&["--", "sheet"]), ansi_output::<8>("sheet"));
+        assert_eq!(run(&["-s", "5", "--", "serve"]), ansi_output::<5>("serve"));
+        assert_eq!(run(&["--", "--"]), ansi_output::<8>("--"));
+
+        // sheet of seeds 'sheet' and 'serve'
+        assert_eq!(
+            run(&["sheet", "--", "sheet", "serve"]),
+            run(&["sheet", "sheet", "serve"])
+        );
+    }
+
+    // command names before '--' are commands
+    #[test]
+    fn command_before_separator() {
+        assert!(run(&["sheet", "seed"]).starts_with(b"\x89PNG"));
+        assert_eq!(run(&["sheet", "seed"]), run(&["sheet", "--", "seed"]));
+    }
+}
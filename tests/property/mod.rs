@@ -0,0 +1,208 @@
+// Property tests on encoders, for arbitrary seeds, sizes and dimensions.
+// Run with more cases by setting env var PROPTEST_CASES
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod property {
+    use eth_blockies::*;
+    use proptest::prelude::*;
+
+    fn seed_strategy() -> impl Strategy<Value = Vec<u8>> {
+        proptest::collection::vec(any::<u8>(), 0..64)
+    }
+
+    fn png_filter_strategy() -> impl Strategy<Value = PngFilter> {
+        prop_oneof![
+            Just(PngFilter::None),
+            Just(PngFilter::Sub),
+            Just(PngFilter::Up),
+            Just(PngFilter::Average),
+            Just(PngFilter::Paeth),
+            Just(PngFilter::Adaptive),
+            Just(PngFilter::Smallest),
+        ]
+    }
+
+    proptest! {
+        // every scaled row has exactly the given width, and there are exactly the given rows
+        #[test]
+        fn scale_dimension(seed in seed_strategy(), output_dim in (0_usize..300, 0_usize..300)) {
+            fn test<const S: usize>(seed: &[u8], output_dim: (usize, usize)) -> Result<(), TestCaseError> {
+                let scaled = Blockies::<S>::indexed_data(seed).1.scale(output_dim);
+
+                prop_assert_eq!(scaled.len(), output_dim.1);
+                prop_assert!(scaled.iter().all(|row| row.len() == output_dim.0));
+                Ok(())
+            }
+            macro_rules! run { ($($size:literal),*) => { $( test::<$size>(&seed, output_dim)?; )* }; }
+            run!(1, 2, 5, 7, 8, 13, 16, 31, 32);
+        }
+
+        // each cell is scaled to floor or ceil of (dimension / size) pixels,
+        // and exactly (dimension / size) pixels if the dimension is a multiple of size
+        #[test]
+        fn scale_cell_widths(width in 0_usize..300) {
+            fn test<const S: usize>(width: usize) -> Result<(), TestCaseError> {
+                let cells: Blockies<S, usize> = Blockies::new(|(x, _)| x);
+                let row = cells.scale((width, 1)).remove(0);
+
+                (0..S).try_for_each(|x| {
+                    let cell_width = row.iter().filter(|cell| **cell == x).count();
+                    prop_assert!(cell_width == width / S || cell_width == (width + S - 1) / S);
+                    Ok(())
+                })?;
+                prop_assert!(row.windows(2).all(|pair| pair[0] <= pair[1]));
+                Ok(())
+            }
+            macro_rules! run { ($($size:literal),*) => { $( test::<$size>(width)?; )* }; }
+            run!(1, 2, 5, 7, 8, 13, 16, 31, 32);
+        }
+
+    }
+
+    proptest! {
+        // fewer cases, as compressing png (PngFilter::Smallest) is slow on debug build
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        // png round-trips through a reference decoder to the same pixels
+        #[test]
+        fn png_roundtrip(
+            seed in seed_strategy(),
+            output_dim in (1_usize..160, 1_usize..160),
+            filter in png_filter_strategy(),
+            reduce_bit_depth in any::<bool>(),
+            interlaced in any::<bool>(),
+            compressed in any::<bool>(),
+        ) {
+            let options = PngOptions {
+                filter,
+                reduce_bit_depth,
+                interlaced,
+                ..Default::default()
+            };
+
+            fn test<const S: usize>(
+                seed: &[u8],
+                output_dim: (usize, usize),
+                options: PngOptions,
+                compressed: bool,
+            ) -> Result<(), TestCaseError> {
+                let png = match compressed {
+                    #[cfg(feature = "compressed_png")]
+                    true => Blockies::<S>::compressed_png_data_with_options(seed, output_dim, options),
+                    _ => Blockies::<S>::png_data_with_options(seed, output_dim, options),
+                };
+                let expected: Vec<RgbPixel> =
+                    Blockies::<S>::data(seed).scale(output_dim).into_iter().flatten().collect();
+
+                prop_assert_eq!(decode_png(&png, output_dim), expected);
+                Ok(())
+            }
+            macro_rules! run {
+                ($($size:literal),*) => { $( test::<$size>(&seed, output_dim, options, compressed)?; )* };
+            }
+            run!(1, 5, 8, 16, 32);
+        }
+    }
+
+    proptest! {
+        // ansi sequence output has (height) lines of (width) blocks for ascii,
+        // and (height / 2, rounded up) lines of (width) blocks for utf-8
+        #[test]
+        fn ansiseq_dimension(seed in seed_strategy(), output_dim in (0_usize..100, 0_usize..100)) {
+            fn test<const S: usize>(seed: &[u8], output_dim: (usize, usize)) -> Result<(), TestCaseError> {
+                let ascii = Blockies::<S>::ansiseq_data(seed, output_dim, false);
+                prop_assert_eq!(ascii.len(), output_dim.1);
+                prop_assert!(ascii.iter().all(|line| strip_ansiseq(line) == " ".repeat(output_dim.0 * 2)));
+
+                let utf8 = Blockies::<S>::ansiseq_data(seed, output_dim, true);
+                prop_assert_eq!(utf8.len(), (output_dim.1 + 1) / 2);
+                prop_assert!(utf8.iter().all(|line| is_utf8_blocks(&strip_ansiseq(line), output_dim.0)));
+                Ok(())
+            }
+            macro_rules! run { ($($size:literal),*) => { $( test::<$size>(&seed, output_dim)?; )* }; }
+            run!(1, 5, 8, 16);
+        }
+    }
+
+    proptest! {
+        // bmp and tga are encoded for any dimension including 0, with the length given by the headers
+        #[test]
+        fn bmp_tga_length(seed in seed_strategy(), output_dim in (0_usize..100, 0_usize..100)) {
+            let bmp = EthBlockies::bmp_data(seed.as_slice(), output_dim);
+            let file_size = u32::from_le_bytes(bmp[2..6].try_into().unwrap()) as usize;
+            let pixel_offset = u32::from_le_bytes(bmp[10..14].try_into().unwrap()) as usize;
+            let bytes_per_row = ((output_dim.0 + 1) / 2 + 3) / 4 * 4;
+            prop_assert_eq!(bmp.len(), file_size);
+            prop_assert_eq!(bmp.len() - pixel_offset, bytes_per_row * output_dim.1);
+
+            let tga = EthBlockies::tga_data(seed.as_slice(), output_dim).unwrap();
+            let header: &[u8] = &[(output_dim.0 as u16).to_le_bytes(), (output_dim.1 as u16).to_le_bytes()].concat();
+            prop_assert_eq!(&tga[12..16], header);
+            prop_assert_eq!(tga.len(), 18 + 3 * 3 + output_dim.0 * output_dim.1 + 26);
+        }
+    }
+
+    // tga header has no room for width or height over u16::MAX
+    #[test]
+    fn tga_dimension_limit() {
+        assert!(EthBlockies::tga_data("seed", (u16::MAX as usize, 1)).is_some());
+        assert!(EthBlockies::tga_data("seed", (u16::MAX as usize + 1, 1)).is_none());
+        assert!(EthBlockies::tga_data("seed", (1, u16::MAX as usize + 1)).is_none());
+        assert!(EthBlockies::tga_data("seed", (usize::MAX, usize::MAX)).is_none());
+    }
+
+    // webp header holds width and height of 1-16384
+    #[cfg(feature = "webp_lossless")]
+    #[test]
+    fn webp_dimension_limit() {
+        assert!(EthBlockies::webp_data("seed", (1 << 14, 1)).is_some());
+        assert!(EthBlockies::webp_data("seed", (1, 1 << 14)).is_some());
+        assert!(EthBlockies::webp_data("seed", (0, 1)).is_none());
+        assert!(EthBlockies::webp_data("seed", (1, 0)).is_none());
+        assert!(EthBlockies::webp_data("seed", ((1 << 14) + 1, 1)).is_none());
+        assert!(EthBlockies::webp_data("seed", (usize::MAX, usize::MAX)).is_none());
+    }
+
+    // decode png to rgb pixels with a reference decoder, checking the dimension
+    fn decode_png(png: &[u8], output_dim: (usize, usize)) -> Vec<RgbPixel> {
+        let mut decoder = png::Decoder::new(png);
+        decoder.set_transformations(png::Transformations::EXPAND);
+        let mut reader = decoder.read_info().expect("png header");
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).expect("png data");
+
+        assert_eq!((info.width as usize, info.height as usize), output_dim);
+        assert_eq!(info.bit_depth, png::BitDepth::Eight);
+        let channels = match info.color_type {
+            png::ColorType::Rgb => 3,
+            png::ColorType::Rgba => 4,
+            color_type => panic!("unexpected color type: {:?}", color_type),
+        };
+
+        buf[..info.buffer_size()]
+            .chunks_exact(channels)
+            .map(|pixel| (pixel[0], pixel[1], pixel[2]))
+            .collect()
+    }
+
+    // check if the line consists of (width) upper/lower half blocks
+    fn is_utf8_blocks(line: &str, width: usize) -> bool {
+        line.chars().count() == width && line.chars().all(|c| c == '\u{2580}' || c == '\u{2584}')
+    }
+
+    // remove ansi escape sequences (ESC '[' ... final byte)
+    fn strip_ansiseq(line: &str) -> String {
+        let mut chars = line.chars();
+        let mut stripped = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => {
+                    chars.find(|c| c.is_ascii_alphabetic());
+                }
+                c => stripped.push(c),
+            }
+        }
+        stripped
+    }
+}
@@ -0,0 +1,283 @@
+// Tests on 'serve' subcommand of the binary, over a localhost http server.
+
+#[cfg(all(test, feature = "server"))]
+#[allow(clippy::module_inception)]
+mod server {
+    use eth_blockies::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::process::{Child, Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    // server process, killed on drop
+    struct Server {
+        child: Child,
+        addr: String,
+    }
+
+    impl Server {
+        fn spawn() -> Self {
+            let mut child = Command::new(env!("CARGO_BIN_EXE_eth-blockies"))
+                .args(["serve", "--listen=127.0.0.1:0", "--threads=2"])
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("failed to spawn server");
+
+            let mut line = String::new();
+            BufReader::new(child.stderr.take().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            let addr = line
+                .trim()
+                .strip_prefix("Listening on http://")
+                .unwrap_or_else(|| panic!("unexpected server output: {}", line))
+                .to_owned();
+
+            Self { child, addr }
+        }
+
+        // send a raw request, and get (status code, headers, body)
+        fn request(&self, method: &str, target: &str, headers: &[&str]) -> Response {
+            let mut stream = TcpStream::connect(&self.addr).unwrap();
+            let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\n", method, target, self.addr);
+            headers
+                .iter()
+                .for_each(|header| request += &format!("{}\r\n", header));
+            request += "\r\n";
+            stream.write_all(request.as_bytes()).unwrap();
+
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).unwrap();
+
+            let header_end = response
+                .windows(4)
+                .position(|w| w == b"\r\n\r\n")
+                .expect("no end of headers");
+            let head = String::from_utf8(response[..header_end].to_vec()).unwrap();
+            let mut lines = head.split("\r\n");
+            let status = lines
+                .next()
+                .unwrap()
+                .split(' ')
+                .nth(1)
+                .unwrap()
+                .parse()
+                .unwrap();
+            let headers = lines
+                .map(|line| {
+                    let (name, value) = line.split_once(':').unwrap();
+                    (name.trim().to_ascii_lowercase(), value.trim().to_owned())
+                })
+                .collect();
+
+            Response {
+                status,
+                headers,
+                body: response[header_end + 4..].to_vec(),
+            }
+        }
+    }
+
+    impl Drop for Server {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    struct Response {
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    }
+
+    impl Response {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    #[cfg(feature = "compressed_png")]
+    fn expected_png<const S: usize>(seed: &str, dim: (usize, usize)) -> Vec<u8> {
+        Blockies::<S>::compressed_png_data(seed, dim)
+    }
+    #[cfg(not(feature = "compressed_png"))]
+    fn expected_png<const S: usize>(seed: &str, dim: (usize, usize)) -> Vec<u8> {
+        Blockies::<S>::png_data(seed, dim)
+    }
+
+    #[test]
+    fn serve_images() {
+        let server = Server::spawn();
+
+        // png, with default size and dimension
+        let png = server.request("GET", "/generic_seed.png", &[]);
+        assert_eq!(png.status, 200);
+        assert_eq!(png.header("content-type"), Some("image/png"));
+        assert_eq!(png.body, expected_png::<8>("generic_seed", (128, 128)));
+        assert!(png.header("cache-control").is_some());
+
+        // png, with size and dimension
+        let png = server.request("GET", "/generic_seed.png?size=16&dim=64x32", &[]);
+        assert_eq!(png.status, 200);
+        assert_eq!(png.body, expected_png::<16>("generic_seed", (64, 32)));
+
+        // svg
+        let svg = server.request("GET", "/generic%20seed.svg?dim=100", &[]);
+        assert_eq!(svg.status, 200);
+        assert_eq!(svg.header("content-type"), Some("image/svg+xml"));
+        assert_eq!(
+            svg.body,
+            EthBlockies::svg_data("generic seed", (100, 100)).into_bytes()
+        );
+
+        // svg, clipped with circle
+        let svg = server.request("GET", "/generic_seed.svg?dim=100&transparent=1", &[]);
+        assert_eq!(svg.status, 200);
+        assert_eq!(
+            svg.body,
+            EthBlockies::svg_data_with_options(
+                "generic_seed",
+                (100, 100),
+                RenderOptions {
+                    mask: Mask::Circle,
+                    ..Default::default()
+                }
+            )
+            .into_bytes()
+        );
+
+        // ico, containing png
+        let ico = server.request("GET", "/generic_seed.ico?dim=32", &[]);
+        assert_eq!(ico.status, 200);
+        assert_eq!(ico.body[..4], [0, 0, 1, 0]);
+        assert!(ico
+            .body
+            .ends_with(&expected_png::<8>("generic_seed", (32, 32))));
+        // bits per pixel of the indexed png (bit depth in IHDR)
+        let png = &ico.body[22..];
+        assert_eq!(png[25], 3);
+        assert_eq!(ico.body[12..14], [png[24], 0]);
+
+        // ethseed
+        let addr = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+        assert_eq!(
+            server
+                .request("GET", &format!("/{}.png?ethseed=1", addr), &[])
+                .body,
+            expected_png::<8>(&addr.to_ascii_lowercase(), (128, 128))
+        );
+
+        // head
+        let head = server.request("HEAD", "/generic_seed.png", &[]);
+        assert_eq!(head.status, 200);
+        assert!(head.body.is_empty());
+    }
+
+    #[test]
+    fn serve_conditional_requests() {
+        let server = Server::spawn();
+
+        let png = server.request("GET", "/generic_seed.png", &[]);
+        let etag = png.header("etag").expect("no etag").to_owned();
+
+        let not_modified = server.request(
+            "GET",
+            "/generic_seed.png",
+            &[&format!("If-None-Match: {}", etag)],
+        );
+        assert_eq!(not_modified.status, 304);
+        assert!(not_modified.body.is_empty());
+
+        let modified = server.request(
+            "GET",
+            "/generic_seed.png",
+            &["If-None-Match: \"other-etag\""],
+        );
+        assert_eq!(modified.status, 200);
+        assert_eq!(modified.body, png.body);
+
+        // different images get different etags
+        let other = server.request("GET", "/generic_seed.png?size=9", &[]);
+        assert_ne!(other.header("etag"), Some(etag.as_str()));
+    }
+
+    #[test]
+    fn serve_errors() {
+        let server = Server::spawn();
+
+        [
+            ("/generic_seed.png?size=99", 400),
+            ("/generic_seed.png?size=0", 400),
+            ("/generic_seed.png?dim=0", 400),
+            ("/generic_seed.png?dim=9999", 400),
+            ("/generic_seed.ico?dim=512", 400),
+            ("/generic_seed.png?unknown=1", 400),
+            ("/generic_seed.gif", 404),
+            ("/", 404),
+        ]
+        .iter()
+        .for_each(|(target, status)| {
+            assert_eq!(
+                server.request("GET", target, &[]).status,
+                *status,
+                "{}",
+                target
+            )
+        });
+
+        let post = server.request("POST", "/generic_seed.png", &[]);
+        assert_eq!(post.status, 405);
+        assert!(post.header("allow").is_some());
+    }
+
+    #[test]
+    fn serve_slow_requests() {
+        let server = Server::spawn();
+
+        // occupy all workers (2) with requests sent slowly, byte by byte
+        let slow_clients: Vec<_> = (0..2)
+            .map(|_| {
+                let addr = server.addr.clone();
+                std::thread::spawn(move || {
+                    let mut stream = TcpStream::connect(addr).unwrap();
+                    b"GET /generic_seed.png HTTP/1.1\r\n"
+                        .iter()
+                        .try_for_each(|byte| {
+                            std::thread::sleep(Duration::from_millis(500));
+                            stream.write_all(&[*byte])
+                        })
+                        // closed by the server before the end of the request
+                        .is_err()
+                })
+            })
+            .collect();
+        std::thread::sleep(Duration::from_millis(100));
+
+        // served after the time limit of the slow requests
+        let start = Instant::now();
+        assert_eq!(server.request("GET", "/generic_seed.png", &[]).status, 200);
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        slow_clients
+            .into_iter()
+            .for_each(|client| assert!(client.join().unwrap()));
+    }
+
+    #[test]
+    fn serve_invalid_threads() {
+        ["--threads=0", "--threads=257", "--threads=x"]
+            .iter()
+            .for_each(|threads| {
+                let status = Command::new(env!("CARGO_BIN_EXE_eth-blockies"))
+                    .args(["serve", "--listen=127.0.0.1:0", threads])
+                    .stderr(Stdio::null())
+                    .status()
+                    .unwrap();
+                assert!(!status.success(), "{}", threads);
+            });
+    }
+}
@@ -1 +1,9 @@
+#[allow(
+    clippy::module_inception,
+    clippy::map_collect_result_unit,
+    clippy::redundant_closure
+)]
 mod algorithm_validation;
+mod cli;
+mod property;
+mod server;
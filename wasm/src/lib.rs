@@ -0,0 +1,211 @@
+//! WebAssembly API of [`eth_blockies`], to get Ethereum-style blocky identicon in browsers and Node.js
+//!
+//! Build the npm package (with TypeScript types) with [wasm-pack](https://github.com/rustwasm/wasm-pack):
+//! `wasm-pack build wasm --target bundler` (or `--target web`, `--target nodejs`).
+//!
+//! # Example (TypeScript)
+//!
+//! ```ts
+//! import { pngDataUri, svg, indexedData, toEthaddrSeed } from "eth-blockies-wasm";
+//!
+//! const seed = toEthaddrSeed("0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC");
+//!
+//! const uri: string = pngDataUri(seed, 8, 64); // <img src={uri} />
+//! const svgData: string = svg(seed); // size: 8, dim: 128 by default
+//!
+//! const indexed = indexedData(seed);
+//! const palette: Uint8Array = indexed.palette; // [ bg_r, bg_g, bg_b, color_r, ... ]
+//! const data: Uint8Array = indexed.data; // color class indices (0, 1, 2), row-major
+//! indexed.free(); // or let it be freed on garbage collection, with weak refs of wasm-bindgen
+//! ```
+
+use eth_blockies::*;
+use wasm_bindgen::prelude::*;
+
+const MIN_BLOCKIES_SIZE: usize = 1;
+const MAX_BLOCKIES_SIZE: usize = 32;
+const DEFAULT_BLOCKIES_SIZE: usize = 8;
+const DEFAULT_DIMENSION: usize = 128;
+
+// list of generator functions, one for each blockies size
+type FuncList<'a, F> = [&'a F; MAX_BLOCKIES_SIZE - MIN_BLOCKIES_SIZE + 1];
+type DataUriFn<'a> = dyn Fn(&str, (usize, usize)) -> String + 'a;
+type SvgFn<'a> = dyn Fn(&str, (usize, usize)) -> String + 'a;
+type IndexedFn<'a> = dyn Fn(&str) -> (RgbPalette, Vec<u8>) + 'a;
+
+macro_rules! init_const_blockies_arr {
+
+    // wrapper
+    ( $func:ident ) => {
+        init_const_blockies_arr!(@gen_arr
+            $func, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+            22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+        )
+    };
+
+    // gen array of calls of generic fn, with given blockies_sizes (args)
+    (@gen_arr $func:ident, $( $size:literal ), *) => {
+        [
+            $(
+                &$func::<$size>,
+            )*
+        ]
+    };
+
+}
+
+fn gen_png_data_uri<const S: usize>(seed: &str, dimension: (usize, usize)) -> String {
+    Blockies::<S>::compressed_png_data_base64(seed, dimension, true)
+}
+
+fn gen_svg<const S: usize>(seed: &str, dimension: (usize, usize)) -> String {
+    Blockies::<S>::svg_data(seed, dimension)
+}
+
+fn gen_indexed_data<const S: usize>(seed: &str) -> (RgbPalette, Vec<u8>) {
+    let (palette, data) = Blockies::<S>::indexed_data(seed);
+    (palette, data.flatten().into_iter().map(u8::from).collect())
+}
+
+/// Error on invalid arguments, thrown as `Error` in JavaScript
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for JsValue {
+    fn from(error: Error) -> Self {
+        JsError::new(&error.message).into()
+    }
+}
+
+fn check_size(size: usize) -> Result<usize, Error> {
+    match (MIN_BLOCKIES_SIZE..=MAX_BLOCKIES_SIZE).contains(&size) {
+        true => Ok(size),
+        false => Err(Error::new(format!(
+            "Invalid blockies size: must be {}-{}, got {}",
+            MIN_BLOCKIES_SIZE, MAX_BLOCKIES_SIZE, size
+        ))),
+    }
+}
+
+fn check_dimension(dim: usize) -> Result<(usize, usize), Error> {
+    match dim {
+        0 => Err(Error::new(
+            "Invalid dimension: must be positive, got 0".to_owned(),
+        )),
+        _ => Ok((dim, dim)),
+    }
+}
+
+/// Get data uri of png image of blockies (`data:image/png;base64,...`), usable as `src` of `<img>`
+///
+/// # Arguments
+///
+/// * `seed` - Input seed (use [`to_ethaddr_seed()`] first for Ethereum addresses)
+/// * `size` - Blockies size (1-32, `8` for Ethereum blockies)
+/// * `dim` - Width and height of the image
+#[wasm_bindgen(js_name = pngDataUri)]
+pub fn png_data_uri(seed: &str, size: usize, dim: usize) -> Result<String, Error> {
+    let (size, dimension) = (check_size(size)?, check_dimension(dim)?);
+    let func_list: FuncList<DataUriFn<'_>> = init_const_blockies_arr!(gen_png_data_uri);
+
+    Ok(func_list[size - 1](seed, dimension))
+}
+
+/// Get svg image data of blockies
+///
+/// # Arguments
+///
+/// * `seed` - Input seed (use [`to_ethaddr_seed()`] first for Ethereum addresses)
+/// * `size` - Blockies size (1-32, `8` by default)
+/// * `dim` - Width and height of the image (`128` by default)
+#[wasm_bindgen]
+pub fn svg(seed: &str, size: Option<usize>, dim: Option<usize>) -> Result<String, Error> {
+    let (size, dimension) = (
+        check_size(size.unwrap_or(DEFAULT_BLOCKIES_SIZE))?,
+        check_dimension(dim.unwrap_or(DEFAULT_DIMENSION))?,
+    );
+    let func_list: FuncList<SvgFn<'_>> = init_const_blockies_arr!(gen_svg);
+
+    Ok(func_list[size - 1](seed, dimension))
+}
+
+/// Indexed data of blockies, with its rgb palette
+#[wasm_bindgen]
+pub struct IndexedData {
+    size: usize,
+    palette: Vec<u8>,
+    data: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl IndexedData {
+    /// Blockies size (width and height of `data`)
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Rgb palette, as 9 bytes of
+    /// `[ bg_r, bg_g, bg_b, color_r, color_g, color_b, spot_r, spot_g, spot_b ]`
+    #[wasm_bindgen(getter)]
+    pub fn palette(&self) -> Vec<u8> {
+        self.palette.clone()
+    }
+
+    /// `size * size` bytes of color class indices (row-major),
+    /// each of which is `0` (bg color), `1` (color) or `2` (spot color)
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+}
+
+/// Get indexed data of blockies, with its rgb palette
+///
+/// # Arguments
+///
+/// * `seed` - Input seed (use [`to_ethaddr_seed()`] first for Ethereum addresses)
+/// * `size` - Blockies size (1-32, `8` by default)
+#[wasm_bindgen(js_name = indexedData)]
+pub fn indexed_data(seed: &str, size: Option<usize>) -> Result<IndexedData, Error> {
+    let size = check_size(size.unwrap_or(DEFAULT_BLOCKIES_SIZE))?;
+    let func_list: FuncList<IndexedFn<'_>> = init_const_blockies_arr!(gen_indexed_data);
+    let (palette, data) = func_list[size - 1](seed);
+
+    Ok(IndexedData {
+        size,
+        palette: palette.iter().flat_map(|&(r, g, b)| [r, g, b]).collect(),
+        data,
+    })
+}
+
+/// Canonicalize Ethereum address (to lowercase + set '0x' prefix), to get Ethereum blockies correctly
+///
+/// # Arguments
+///
+/// * `seed` - Ethereum address (with or without '0x' prefix)
+#[wasm_bindgen(js_name = toEthaddrSeed)]
+pub fn to_ethaddr_seed(seed: &str) -> Result<String, Error> {
+    let ethaddr = seed.to_ethaddr_seed();
+
+    match ethaddr[..2] == *b"0x" && ethaddr[2..].iter().all(u8::is_ascii_hexdigit) {
+        true => Ok(ethaddr.iter().map(|&c| c as char).collect()),
+        false => Err(Error::new(format!("Invalid Ethereum address: '{}'", seed))),
+    }
+}
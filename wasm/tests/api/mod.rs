@@ -0,0 +1,86 @@
+// Call the WebAssembly API natively, and compare the results with the library.
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod api {
+    use eth_blockies::*;
+    use eth_blockies_wasm::*;
+
+    const ADDR: &str = "0xe686c14FF9C11038F2B1c9aD617F2346CFB817dC";
+
+    #[test]
+    fn png_data_uri_same_as_library() {
+        assert_eq!(
+            png_data_uri("generic_seed", 8, 64).unwrap(),
+            EthBlockies::compressed_png_data_base64("generic_seed", (64, 64), true)
+        );
+        assert_eq!(
+            png_data_uri("generic_seed", 32, 100).unwrap(),
+            Blockies::<32>::compressed_png_data_base64("generic_seed", (100, 100), true)
+        );
+        assert!(png_data_uri("generic_seed", 1, 1)
+            .unwrap()
+            .starts_with("data:image/png;base64,"));
+    }
+
+    #[test]
+    fn svg_same_as_library() {
+        assert_eq!(
+            svg("generic_seed", None, None).unwrap(),
+            EthBlockies::svg_data("generic_seed", (128, 128))
+        );
+        assert_eq!(
+            svg("generic_seed", Some(16), Some(64)).unwrap(),
+            Blockies::<16>::svg_data("generic_seed", (64, 64))
+        );
+    }
+
+    #[test]
+    fn indexed_data_same_as_library() {
+        let indexed = indexed_data(&to_ethaddr_seed(ADDR).unwrap(), None).unwrap();
+        let (palette, data) = EthBlockies::indexed_data(ADDR.to_ethaddr_seed());
+
+        assert_eq!(indexed.size(), 8);
+        assert_eq!(
+            indexed.palette(),
+            palette
+                .iter()
+                .flat_map(|&(r, g, b)| [r, g, b])
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            indexed.data(),
+            data.flatten().into_iter().map(u8::from).collect::<Vec<_>>()
+        );
+
+        let indexed = indexed_data("generic_seed", Some(5)).unwrap();
+        assert_eq!(indexed.size(), 5);
+        assert_eq!(indexed.data().len(), 5 * 5);
+    }
+
+    #[test]
+    fn to_ethaddr_seed_canonicalized() {
+        let expected = "0xe686c14ff9c11038f2b1c9ad617f2346cfb817dc";
+
+        assert_eq!(to_ethaddr_seed(ADDR).unwrap(), expected);
+        assert_eq!(to_ethaddr_seed(&ADDR[2..]).unwrap(), expected);
+        assert_eq!(
+            to_ethaddr_seed(&ADDR.to_uppercase()[2..]).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(png_data_uri("seed", 0, 64).is_err());
+        assert!(png_data_uri("seed", 33, 64).is_err());
+        assert!(png_data_uri("seed", 8, 0).is_err());
+        assert!(svg("seed", Some(0), None).is_err());
+        assert!(svg("seed", None, Some(0)).is_err());
+        assert!(indexed_data("seed", Some(33)).is_err());
+
+        assert!(to_ethaddr_seed("0x1234").is_err());
+        assert!(to_ethaddr_seed("0xz686c14ff9c11038f2b1c9ad617f2346cfb817dc").is_err());
+        assert!(to_ethaddr_seed("1xe686c14ff9c11038f2b1c9ad617f2346cfb817dc").is_err());
+    }
+}